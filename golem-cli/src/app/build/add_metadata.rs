@@ -12,10 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::app::build::is_up_to_date;
 use crate::app::build::task_result_marker::{AddMetadataMarkerHash, TaskResultMarker};
+use crate::app::build::{is_up_to_date, log_skipping_up_to_date_task};
 use crate::app::context::ApplicationContext;
-use crate::log::{log_action, log_skipping_up_to_date, LogColorize, LogIndent};
+use crate::log::{log_action, LogColorize, LogIndent};
+use crate::model::app::AppBuildStep;
 use crate::wasm_rpc_stubgen::commands::metadata::add_metadata;
 
 pub async fn add_metadata_to_selected_components(
@@ -24,15 +25,24 @@ pub async fn add_metadata_to_selected_components(
     log_action("Adding", "metadata to components");
     let _indent = LogIndent::new();
 
+    // When the post-link step is filtered out, its output is not produced (or not refreshed)
+    // by this build, so the linked WASM is used directly
+    let post_link_step_enabled = ctx.config.should_run_step(AppBuildStep::PostLink);
+
     for component_name in ctx.selected_component_names() {
-        let linked_wasm = ctx.application.component_linked_wasm(component_name);
+        let linked_wasm = if post_link_step_enabled {
+            ctx.application
+                .component_post_linked_wasm(component_name, ctx.profile())
+        } else {
+            ctx.application.component_linked_wasm(component_name)
+        };
         let final_linked_wasm = ctx
             .application
             .component_final_linked_wasm(component_name, ctx.profile());
 
         let root_package_name = ctx.wit.root_package_name(component_name)?;
 
-        let task_result_marker = TaskResultMarker::new(
+        let mut task_result_marker = TaskResultMarker::new(
             &ctx.application.task_result_marker_dir(),
             AddMetadataMarkerHash {
                 component_name,
@@ -41,14 +51,19 @@ pub async fn add_metadata_to_selected_components(
         )?;
 
         if is_up_to_date(
-            ctx.config.skip_up_to_date_checks || !task_result_marker.is_up_to_date(),
+            ctx,
+            &mut task_result_marker,
             || vec![linked_wasm.clone()],
             || [final_linked_wasm.clone()],
         ) {
-            log_skipping_up_to_date(format!(
-                "adding metadata to {}",
-                component_name.as_str().log_color_highlight(),
-            ));
+            log_skipping_up_to_date_task(
+                ctx,
+                Some(component_name),
+                format!(
+                    "adding metadata to {}",
+                    component_name.as_str().log_color_highlight(),
+                ),
+            );
             continue;
         }
 
@@ -0,0 +1,420 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::http::BlockingClientThread;
+use crate::fs;
+use crate::log::{log_action, LogColorize};
+use crate::model::app::{BuildCacheLocation, BuildProfileName};
+use crate::version;
+use anyhow::{anyhow, bail, Context};
+use reqwest::StatusCode;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+use url::Url;
+use uuid::Uuid;
+
+pub trait BuildCacheBackend: Send + Sync {
+    fn describe(&self) -> String;
+
+    /// Returns None if the key is not found in the cache
+    fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>>;
+
+    fn put(&self, key: &str, content: &[u8]) -> anyhow::Result<()>;
+}
+
+/// Build cache shared between builds (and machines), used for restoring the outputs of tasks,
+/// which were built before with the same inputs.
+pub struct BuildCache {
+    backend: Box<dyn BuildCacheBackend>,
+}
+
+impl BuildCache {
+    pub fn new(location: &BuildCacheLocation) -> Self {
+        let backend: Box<dyn BuildCacheBackend> = match location {
+            BuildCacheLocation::LocalDir(dir) => Box::new(LocalDirBuildCacheBackend {
+                dir: dir.to_path_buf(),
+            }),
+            BuildCacheLocation::Http(base_url) => Box::new(HttpBuildCacheBackend {
+                base_url: base_url.clone(),
+                client: BlockingClientThread::new(),
+            }),
+        };
+        Self { backend }
+    }
+
+    pub fn describe(&self) -> String {
+        self.backend.describe()
+    }
+
+    /// Cache key based on the task hash, the hash of its sources and the selected build profile.
+    /// The CLI version is also part of the key, as it affects generated and linked outputs.
+    pub fn key(task_hash: &str, sources_hash: &str, profile: Option<&BuildProfileName>) -> String {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(version().as_bytes());
+        hasher.update(task_hash.as_bytes());
+        hasher.update(sources_hash.as_bytes());
+        if let Some(profile) = profile {
+            hasher.update(profile.as_str().as_bytes());
+        }
+        hasher.finalize().to_hex().to_string()
+    }
+
+    /// Restores all the targets from the cache, returns false if any of them is missing.
+    /// The content hashes of the targets are verified before anything is written, and
+    /// an error is returned on mismatch.
+    pub fn restore(&self, key: &str, targets: &[PathBuf]) -> anyhow::Result<bool> {
+        let Some(hashes) = self.backend.get(key)? else {
+            debug!(key, "build cache miss");
+            return Ok(false);
+        };
+        let hashes = String::from_utf8(hashes)
+            .with_context(|| anyhow!("Invalid build cache entry {}", key))?;
+        let hashes = hashes.lines().collect::<Vec<_>>();
+        if hashes.len() != targets.len() {
+            bail!(
+                "Invalid build cache entry {}, expected {} target hashes, found {}",
+                key,
+                targets.len(),
+                hashes.len()
+            );
+        }
+
+        let mut contents = Vec::with_capacity(targets.len());
+        for (idx, (target, expected_hash)) in targets.iter().zip(hashes).enumerate() {
+            let target_key = target_key(key, idx);
+            let Some(content) = self.backend.get(&target_key)? else {
+                debug!(key, target = %target.display(), "build cache miss");
+                return Ok(false);
+            };
+            let hash = content_hash(&content);
+            if hash != expected_hash {
+                bail!(
+                    "Content hash mismatch for build cache entry {}, expected {}, actual {}",
+                    target_key,
+                    expected_hash,
+                    hash
+                );
+            }
+            contents.push((target, content));
+        }
+
+        for (target, content) in contents {
+            log_action(
+                "Restoring",
+                format!(
+                    "{} from build cache {}",
+                    target.log_color_highlight(),
+                    self.backend.describe().log_color_highlight()
+                ),
+            );
+            fs::write(target, content)?;
+        }
+
+        Ok(true)
+    }
+
+    /// Stores the targets in the cache, skipped if any of them is not a file.
+    /// The content hashes of the targets are stored under the key itself, after all the
+    /// targets were stored, so partially stored entries are handled as a cache miss.
+    pub fn store(&self, key: &str, targets: &[PathBuf]) -> anyhow::Result<()> {
+        for target in targets {
+            if !target.is_file() {
+                debug!(key, target = %target.display(), "build cache skipping non-file target");
+                return Ok(());
+            }
+        }
+
+        let mut hashes = String::new();
+        for (idx, target) in targets.iter().enumerate() {
+            log_action(
+                "Storing",
+                format!(
+                    "{} in build cache {}",
+                    target.log_color_highlight(),
+                    self.backend.describe().log_color_highlight()
+                ),
+            );
+            let content = fs::read(target)?;
+            hashes.push_str(&content_hash(&content));
+            hashes.push('\n');
+            self.backend.put(&target_key(key, idx), &content)?;
+        }
+
+        self.backend.put(key, hashes.as_bytes())
+    }
+}
+
+fn target_key(key: &str, target_idx: usize) -> String {
+    format!("{}-{}", key, target_idx)
+}
+
+fn content_hash(content: &[u8]) -> String {
+    blake3::hash(content).to_hex().to_string()
+}
+
+pub struct LocalDirBuildCacheBackend {
+    dir: PathBuf,
+}
+
+impl BuildCacheBackend for LocalDirBuildCacheBackend {
+    fn describe(&self) -> String {
+        self.dir.display().to_string()
+    }
+
+    fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let path = self.dir.join(key);
+        if path.exists() {
+            Ok(Some(fs::read(path)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn put(&self, key: &str, content: &[u8]) -> anyhow::Result<()> {
+        // Writing to a temporary file first, so concurrent builds never see partial entries
+        let path = self.dir.join(key);
+        let temp_path = self.dir.join(format!("{}.{}.tmp", key, Uuid::new_v4()));
+        fs::write(&temp_path, content)?;
+        std::fs::rename(&temp_path, &path).with_context(|| {
+            anyhow!(
+                "Failed to move {} to {}",
+                temp_path.log_color_highlight(),
+                path.log_color_highlight()
+            )
+        })
+    }
+}
+
+/// Uses plain GET and PUT requests relative to the base URL, where the cache key is used as
+/// the last path segment.
+pub struct HttpBuildCacheBackend {
+    base_url: Url,
+    client: BlockingClientThread,
+}
+
+impl HttpBuildCacheBackend {
+    fn key_url(&self, key: &str) -> anyhow::Result<Url> {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .map_err(|_| anyhow!("Invalid build cache base URL: {}", self.base_url))?
+            .pop_if_empty()
+            .push(key);
+        Ok(url)
+    }
+}
+
+impl BuildCacheBackend for HttpBuildCacheBackend {
+    fn describe(&self) -> String {
+        self.base_url.to_string()
+    }
+
+    fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let url = self.key_url(key)?;
+        self.client.run(move |client| {
+            let response = client.get(url.clone()).send()?;
+            match response.status() {
+                StatusCode::NOT_FOUND => Ok(None),
+                status if status.is_success() => Ok(Some(response.bytes()?.to_vec())),
+                status => bail!("GET {} failed with status {}", url, status),
+            }
+        })
+    }
+
+    fn put(&self, key: &str, content: &[u8]) -> anyhow::Result<()> {
+        let url = self.key_url(key)?;
+        let content = content.to_vec();
+        self.client.run(move |client| {
+            let status = client.put(url.clone()).body(content).send()?.status();
+            if status.is_success() {
+                Ok(())
+            } else {
+                bail!("PUT {} failed with status {}", url, status)
+            }
+        })
+    }
+}
+
+/// Paths of targets which do not contain glob patterns, as only those can be restored
+/// from the cache before the task is executed.
+pub fn literal_targets(build_dir: &Path, targets: &[String]) -> Option<Vec<PathBuf>> {
+    targets
+        .iter()
+        .map(|target| (!target.contains(['*', '?', '[', '{'])).then(|| build_dir.join(target)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::build::cache::BuildCache;
+    use crate::fs;
+    use crate::model::app::BuildCacheLocation;
+    use assert2::check;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::{Arc, Mutex};
+    use test_r::test;
+    use url::Url;
+
+    #[test]
+    fn local_dir_build_cache_store_and_restore() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache = BuildCache::new(&BuildCacheLocation::LocalDir(
+            cache_dir.path().to_path_buf(),
+        ));
+
+        check_store_and_restore(&cache);
+    }
+
+    #[test]
+    fn http_build_cache_store_and_restore() {
+        let cache = BuildCache::new(&BuildCacheLocation::Http(start_file_server()));
+
+        check_store_and_restore(&cache);
+    }
+
+    #[test]
+    fn build_cache_restore_verifies_content_hashes() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache = BuildCache::new(&BuildCacheLocation::LocalDir(
+            cache_dir.path().to_path_buf(),
+        ));
+
+        let work_dir = tempfile::tempdir().unwrap();
+        let targets = vec![
+            work_dir.path().join("target/component.wasm"),
+            work_dir.path().join("target/linked.wasm"),
+        ];
+        fs::write(&targets[0], "component").unwrap();
+        fs::write(&targets[1], "linked").unwrap();
+        cache.store("key", &targets).unwrap();
+        fs::remove(&targets[0]).unwrap();
+        fs::remove(&targets[1]).unwrap();
+
+        fs::write(cache_dir.path().join("key-1"), "corrupted").unwrap();
+
+        check!(cache.restore("key", &targets).is_err());
+        check!(!targets[0].exists());
+        check!(!targets[1].exists());
+    }
+
+    #[test]
+    fn build_cache_partially_stored_entry_is_a_miss() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache = BuildCache::new(&BuildCacheLocation::LocalDir(
+            cache_dir.path().to_path_buf(),
+        ));
+
+        let work_dir = tempfile::tempdir().unwrap();
+        let targets = vec![work_dir.path().join("target/component.wasm")];
+        fs::write(&targets[0], "component").unwrap();
+        cache.store("key", &targets).unwrap();
+        fs::remove(&targets[0]).unwrap();
+
+        fs::remove(cache_dir.path().join("key")).unwrap();
+
+        check!(!cache.restore("key", &targets).unwrap());
+        check!(!targets[0].exists());
+    }
+
+    fn check_store_and_restore(cache: &BuildCache) {
+        let work_dir = tempfile::tempdir().unwrap();
+        let targets = vec![
+            work_dir.path().join("target/component.wasm"),
+            work_dir.path().join("target/linked.wasm"),
+        ];
+
+        check!(!cache.restore("key", &targets).unwrap());
+
+        fs::write(&targets[0], "component").unwrap();
+        fs::write(&targets[1], "linked").unwrap();
+        cache.store("key", &targets).unwrap();
+
+        fs::remove(&targets[0]).unwrap();
+        fs::remove(&targets[1]).unwrap();
+        check!(!cache.restore("other-key", &targets).unwrap());
+        check!(!targets[0].exists());
+
+        check!(cache.restore("key", &targets).unwrap());
+        check!(fs::read_to_string(&targets[0]).unwrap() == "component");
+        check!(fs::read_to_string(&targets[1]).unwrap() == "linked");
+    }
+
+    // Minimal file server for GET and PUT requests, keeping the entries in memory
+    fn start_file_server() -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let entries = Arc::new(Mutex::new(HashMap::<String, Vec<u8>>::new()));
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                handle_file_server_request(stream, &entries);
+            }
+        });
+
+        Url::parse(&format!("http://127.0.0.1:{}/cache/", port)).unwrap()
+    }
+
+    fn handle_file_server_request(
+        mut stream: TcpStream,
+        entries: &Mutex<HashMap<String, Vec<u8>>>,
+    ) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut request_line = request_line.split_whitespace();
+        let method = request_line.next().unwrap().to_string();
+        let path = request_line.next().unwrap().to_string();
+
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+
+        let (status, body) = match method.as_str() {
+            "PUT" => {
+                entries.lock().unwrap().insert(path, body);
+                ("201 Created", vec![])
+            }
+            "GET" => match entries.lock().unwrap().get(&path) {
+                Some(content) => ("200 OK", content.clone()),
+                None => ("404 Not Found", vec![]),
+            },
+            _ => ("405 Method Not Allowed", vec![]),
+        };
+
+        let mut response = format!(
+            "HTTP/1.1 {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+            status,
+            body.len()
+        )
+        .into_bytes();
+        response.extend(body);
+        stream.write_all(&response).unwrap();
+    }
+}
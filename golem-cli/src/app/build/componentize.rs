@@ -13,57 +13,208 @@
 // limitations under the License.
 
 use crate::app::build::external_command::execute_external_command;
+use crate::app::build::log_build_warning;
 use crate::app::context::ApplicationContext;
-use crate::log::{log_action, log_warn_action, LogColorize, LogIndent};
+use crate::log::{log_action, log_captured_lines, LogCapture, LogColorize, LogIndent};
 use crate::model::app::{AppComponentName, DependencyType};
 use crate::wasm_rpc_stubgen::wit_resolve::ExportedFunction;
 use anyhow::{anyhow, Context};
 use heck::ToLowerCamelCase;
-use std::collections::{BTreeSet, HashMap};
+use itertools::Itertools;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::panic::AssertUnwindSafe;
+use std::sync::mpsc;
 
 pub fn componentize(ctx: &mut ApplicationContext) -> anyhow::Result<()> {
-    log_action("Building", "components");
-    let _indent = LogIndent::new();
-
     let components_to_build = components_to_build(ctx);
-    for component_name in components_to_build {
-        let component_properties = ctx
-            .application
-            .component_properties(&component_name, ctx.profile());
-
-        if component_properties.build.is_empty() {
-            log_warn_action(
-                "Skipping",
-                format!(
-                    "building {}, no build steps",
-                    component_name.as_str().log_color_highlight(),
-                ),
-            );
-            continue;
-        }
+    let jobs = ctx.config.build_jobs;
 
+    if jobs > 1 && components_to_build.len() > 1 {
         log_action(
             "Building",
-            format!("{}", component_name.as_str().log_color_highlight()),
+            format!(
+                "components, using up to {} parallel jobs",
+                jobs.to_string().log_color_highlight()
+            ),
         );
         let _indent = LogIndent::new();
 
-        let env_vars = build_step_env_vars(ctx, &component_name)
-            .context("Failed to get env vars for build step")?;
+        build_components_in_parallel(ctx, components_to_build, jobs)
+    } else {
+        log_action("Building", "components");
+        let _indent = LogIndent::new();
 
-        for build_step in &component_properties.build {
-            execute_external_command(
-                ctx,
-                ctx.application.component_source_dir(&component_name),
-                build_step,
-                env_vars.clone(),
-            )?;
+        for component_name in components_to_build {
+            build_component(ctx, &component_name)?;
         }
+
+        Ok(())
+    }
+}
+
+fn build_component(
+    ctx: &ApplicationContext,
+    component_name: &AppComponentName,
+) -> anyhow::Result<()> {
+    let component_properties = ctx
+        .application
+        .component_properties(component_name, ctx.profile());
+
+    if component_properties.build.is_empty() {
+        log_build_warning(
+            ctx,
+            Some(component_name),
+            "Skipping",
+            format!(
+                "building {}, no build steps",
+                component_name.as_str().log_color_highlight(),
+            ),
+        );
+        return Ok(());
+    }
+
+    log_action(
+        "Building",
+        format!("{}", component_name.as_str().log_color_highlight()),
+    );
+    let _indent = LogIndent::new();
+
+    let env_vars = build_step_env_vars(ctx, component_name)
+        .context("Failed to get env vars for build step")?;
+
+    for (step_index, build_step) in component_properties.build.iter().enumerate() {
+        execute_external_command(
+            ctx,
+            Some(component_name),
+            step_index,
+            ctx.application.component_source_dir(component_name),
+            build_step,
+            env_vars.clone(),
+        )?;
     }
 
     Ok(())
 }
 
+/// Builds the components in parallel, respecting the WASM library dependencies between them
+fn build_components_in_parallel(
+    ctx: &ApplicationContext,
+    components_to_build: BTreeSet<AppComponentName>,
+    jobs: usize,
+) -> anyhow::Result<()> {
+    let pending_dependencies = components_to_build
+        .iter()
+        .map(|component_name| {
+            (
+                component_name.clone(),
+                ctx.application
+                    .component_dependencies(component_name)
+                    .iter()
+                    .filter(|dep| {
+                        dep.dep_type == DependencyType::Wasm
+                            && components_to_build.contains(&dep.name)
+                    })
+                    .map(|dep| dep.name.clone())
+                    .collect::<BTreeSet<_>>(),
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    build_in_dependency_order(pending_dependencies, jobs, |component_name| {
+        build_component(ctx, component_name)
+    })
+}
+
+/// Builds components on separate threads, a component is only started after all of its
+/// dependencies are built, and no new builds are started after the first failure. Log output
+/// of every component is buffered and written out when its build is finished.
+fn build_in_dependency_order<F>(
+    mut pending_dependencies: BTreeMap<AppComponentName, BTreeSet<AppComponentName>>,
+    jobs: usize,
+    build: F,
+) -> anyhow::Result<()>
+where
+    F: Fn(&AppComponentName) -> anyhow::Result<()> + Sync,
+{
+    std::thread::scope(|scope| {
+        let (sender, receiver) =
+            mpsc::channel::<(AppComponentName, anyhow::Result<()>, Vec<String>)>();
+        let mut running = 0usize;
+        let mut first_error: Option<anyhow::Error> = None;
+
+        loop {
+            if first_error.is_none() {
+                let ready_components = pending_dependencies
+                    .iter()
+                    .filter(|(_, dependencies)| dependencies.is_empty())
+                    .map(|(component_name, _)| component_name.clone())
+                    .take(jobs - running)
+                    .collect::<Vec<_>>();
+
+                for component_name in ready_components {
+                    pending_dependencies.remove(&component_name);
+                    running += 1;
+
+                    let sender = sender.clone();
+                    let build = &build;
+                    scope.spawn(move || {
+                        let log_capture = LogCapture::new();
+                        let result =
+                            std::panic::catch_unwind(AssertUnwindSafe(|| build(&component_name)))
+                                .unwrap_or_else(|_| {
+                                    Err(anyhow!(
+                                        "Unexpected panic while building {}",
+                                        component_name.as_str().log_color_error_highlight()
+                                    ))
+                                });
+                        let log_lines = log_capture.finish();
+                        // The receiver only goes away on scope exit, after all threads are joined
+                        sender
+                            .send((component_name, result, log_lines))
+                            .expect("Failed to send component build result");
+                    });
+                }
+            }
+
+            if running == 0 {
+                break;
+            }
+
+            let (component_name, result, log_lines) = receiver
+                .recv()
+                .expect("Failed to receive component build result");
+            running -= 1;
+
+            log_captured_lines(log_lines);
+
+            match result {
+                Ok(()) => {
+                    for dependencies in pending_dependencies.values_mut() {
+                        dependencies.remove(&component_name);
+                    }
+                }
+                Err(err) => {
+                    if first_error.is_none() {
+                        first_error = Some(err);
+                    }
+                }
+            }
+        }
+
+        match first_error {
+            Some(err) => Err(err),
+            None if !pending_dependencies.is_empty() => Err(anyhow!(
+                "Cannot build components because of cyclic WASM library dependencies: {}",
+                pending_dependencies
+                    .keys()
+                    .map(|component_name| component_name.as_str().log_color_error_highlight())
+                    .join(", ")
+            )),
+            None => Ok(()),
+        }
+    })
+}
+
 fn components_to_build(ctx: &ApplicationContext) -> BTreeSet<AppComponentName> {
     let mut components_to_build = BTreeSet::new();
     let mut remaining: Vec<_> = ctx.selected_component_names().iter().cloned().collect();
@@ -139,3 +290,133 @@ fn jco_async_export_args(
     }
     Ok(result)
 }
+
+#[cfg(test)]
+mod test {
+    use crate::app::build::componentize::build_in_dependency_order;
+    use crate::model::app::AppComponentName;
+    use anyhow::anyhow;
+    use assert2::check;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use test_r::test;
+
+    fn dependencies(
+        dependencies: &[(&str, &[&str])],
+    ) -> BTreeMap<AppComponentName, BTreeSet<AppComponentName>> {
+        dependencies
+            .iter()
+            .map(|(component_name, dependencies)| {
+                (
+                    AppComponentName::from(*component_name),
+                    dependencies
+                        .iter()
+                        .map(|dependency| AppComponentName::from(*dependency))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn dependent_component_waits_for_its_dependency() {
+        let events = Mutex::new(Vec::<String>::new());
+
+        let result = build_in_dependency_order(
+            dependencies(&[
+                ("app:lib", &[]),
+                ("app:main", &["app:lib"]),
+                ("app:other", &[]),
+            ]),
+            4,
+            |component_name| {
+                events
+                    .lock()
+                    .unwrap()
+                    .push(format!("start {}", component_name));
+                // Giving the dependent a chance to start early, if it was not waiting
+                std::thread::sleep(Duration::from_millis(50));
+                events
+                    .lock()
+                    .unwrap()
+                    .push(format!("finish {}", component_name));
+                Ok(())
+            },
+        );
+
+        check!(result.is_ok());
+        let events = events.into_inner().unwrap();
+        let position = |event: &str| events.iter().position(|e| e == event).unwrap();
+        check!(events.len() == 6);
+        check!(position("finish app:lib") < position("start app:main"));
+        check!(position("start app:other") < position("start app:main"));
+    }
+
+    #[test]
+    fn failed_dependency_stops_scheduling_dependents() {
+        let built = Mutex::new(BTreeSet::<String>::new());
+
+        let result = build_in_dependency_order(
+            dependencies(&[
+                ("app:lib", &[]),
+                ("app:main", &["app:lib"]),
+                ("app:other", &["app:main"]),
+            ]),
+            4,
+            |component_name| {
+                built.lock().unwrap().insert(component_name.to_string());
+                if component_name.as_str() == "app:lib" {
+                    Err(anyhow!("lib failed"))
+                } else {
+                    Ok(())
+                }
+            },
+        );
+
+        check!(result.unwrap_err().to_string() == "lib failed");
+        check!(built.into_inner().unwrap() == BTreeSet::from(["app:lib".to_string()]));
+    }
+
+    #[test]
+    fn parallel_builds_are_limited_by_jobs() {
+        let running = Mutex::new((0usize, 0usize));
+
+        let result = build_in_dependency_order(
+            dependencies(&[
+                ("app:a", &[]),
+                ("app:b", &[]),
+                ("app:c", &[]),
+                ("app:d", &[]),
+            ]),
+            2,
+            |_| {
+                {
+                    let mut running = running.lock().unwrap();
+                    running.0 += 1;
+                    running.1 = running.1.max(running.0);
+                }
+                std::thread::sleep(Duration::from_millis(20));
+                running.lock().unwrap().0 -= 1;
+                Ok(())
+            },
+        );
+
+        check!(result.is_ok());
+        check!(running.into_inner().unwrap().1 <= 2);
+    }
+
+    #[test]
+    fn cyclic_dependencies_are_reported() {
+        let result = build_in_dependency_order(
+            dependencies(&[("app:a", &["app:b"]), ("app:b", &["app:a"]), ("app:c", &[])]),
+            2,
+            |_| Ok(()),
+        );
+
+        let err = result.unwrap_err().to_string();
+        check!(
+            err.starts_with("Cannot build components because of cyclic WASM library dependencies")
+        );
+    }
+}
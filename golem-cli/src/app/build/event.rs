@@ -0,0 +1,154 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::app::{AppBuildStep, AppComponentName, BuildProfileName};
+use chrono::Utc;
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Structured build events, emitted as NDJSON lines on stdout when JSON output is requested.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum BuildEvent {
+    StepStarted,
+    #[serde(rename_all = "camelCase")]
+    StepFinished {
+        duration_ms: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    SkippedUpToDate {
+        task: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    RestoredFromCache {
+        task: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    CommandExecuted {
+        command: String,
+        dir: PathBuf,
+        duration_ms: u64,
+        exit_code: Option<i32>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Warning {
+        message: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BuildEventLine<'a> {
+    timestamp: String,
+    #[serde(flatten)]
+    event: &'a BuildEvent,
+    step: Option<AppBuildStep>,
+    component: Option<&'a str>,
+    profile: Option<&'a str>,
+}
+
+/// Writes build events for the currently running build step. Every event is written as
+/// a single line, so events of components built in parallel are never interleaved.
+pub struct BuildEvents {
+    profile: Option<BuildProfileName>,
+    step: Mutex<Option<AppBuildStep>>,
+}
+
+impl BuildEvents {
+    pub fn new(profile: Option<BuildProfileName>) -> Self {
+        Self {
+            profile,
+            step: Mutex::new(None),
+        }
+    }
+
+    pub fn set_step(&self, step: Option<AppBuildStep>) {
+        *self.step.lock().unwrap() = step;
+    }
+
+    pub fn emit(&self, component_name: Option<&AppComponentName>, event: &BuildEvent) {
+        let line = self.to_line(component_name, event);
+        let mut stdout = std::io::stdout().lock();
+        // Same as println, failing to write to stdout is not recoverable
+        writeln!(stdout, "{}", line).expect("Failed to write build event");
+    }
+
+    fn to_line(&self, component_name: Option<&AppComponentName>, event: &BuildEvent) -> String {
+        serde_json::to_string(&BuildEventLine {
+            timestamp: Utc::now().to_rfc3339(),
+            event,
+            step: *self.step.lock().unwrap(),
+            component: component_name.map(|component_name| component_name.as_str()),
+            profile: self.profile.as_ref().map(|profile| profile.as_str()),
+        })
+        .expect("Failed to serialize build event")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::build::event::{BuildEvent, BuildEvents};
+    use crate::model::app::{AppBuildStep, AppComponentName};
+    use assert2::check;
+    use serde_json::json;
+    use std::path::PathBuf;
+    use test_r::test;
+
+    #[test]
+    fn build_event_lines() {
+        let events = BuildEvents::new(Some("release".to_string().into()));
+        events.set_step(Some(AppBuildStep::Componentize));
+
+        let component_name = AppComponentName::from("app:a");
+        let line = events.to_line(
+            Some(&component_name),
+            &BuildEvent::CommandExecuted {
+                command: "cargo build".to_string(),
+                dir: PathBuf::from("components/a"),
+                duration_ms: 42,
+                exit_code: Some(0),
+            },
+        );
+        check!(!line.contains('\n'));
+
+        let mut value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        check!(value.as_object_mut().unwrap().remove("timestamp").is_some());
+        check!(
+            value
+                == json!({
+                    "event": "command-executed",
+                    "command": "cargo build",
+                    "dir": "components/a",
+                    "durationMs": 42,
+                    "exitCode": 0,
+                    "step": "componentize",
+                    "component": "app:a",
+                    "profile": "release",
+                })
+        );
+
+        events.set_step(None);
+        let value: serde_json::Value =
+            serde_json::from_str(&events.to_line(None, &BuildEvent::StepStarted)).unwrap();
+        check!(value["event"] == "step-started");
+        check!(value["step"].is_null());
+        check!(value["component"].is_null());
+    }
+}
@@ -12,19 +12,42 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::app::build::cache::literal_targets;
+use crate::app::build::event::BuildEvent;
 use crate::app::build::task_result_marker::{ResolvedExternalCommandMarkerHash, TaskResultMarker};
-use crate::app::build::{delete_path_logged, is_up_to_date, valid_env_vars};
+use crate::app::build::{
+    build_cache_key, delete_path_logged, is_up_to_date, log_build_warning,
+    log_skipping_restored_task, log_skipping_up_to_date_task, restore_from_build_cache,
+    store_in_build_cache, valid_env_vars,
+};
 use crate::app::context::ApplicationContext;
-use crate::app::error::CustomCommandError;
+use crate::app::error::{CustomCommandError, ExternalCommandError};
+use crate::diagnose::is_tool_available;
+use crate::fs;
 use crate::fs::compile_and_collect_globs;
-use crate::log::{log_action, log_skipping_up_to_date, LogColorize, LogIndent};
+use crate::log::{is_log_captured, log_action, log_warn_action, logln, LogColorize, LogIndent};
+use crate::model::app::AppComponentName;
 use crate::model::app_raw;
-use anyhow::{anyhow, Context};
-use std::collections::HashMap;
-use std::path::Path;
-use std::process::Command;
+use anyhow::{anyhow, bail, Context};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 use tracing::debug;
 
+/// Number of the last stderr lines included in the error of a failed command
+const EXTERNAL_COMMAND_ERROR_STDERR_LINES: usize = 20;
+
+const TIMEOUT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Time given to the output readers after a timed out command was killed. Background processes
+/// started by the command can keep the output pipes open, so the readers are not waited for
+/// after this.
+const TIMED_OUT_OUTPUT_GRACE_PERIOD: Duration = Duration::from_secs(1);
+
 pub fn execute_custom_command(
     ctx: &ApplicationContext,
     command_name: &str,
@@ -51,9 +74,15 @@ pub fn execute_custom_command(
         );
         let _indent = LogIndent::new();
 
-        for step in &command.value {
-            if let Err(error) = execute_external_command(ctx, &command.source, step, HashMap::new())
-            {
+        for (step_index, step) in command.value.iter().enumerate() {
+            if let Err(error) = execute_external_command(
+                ctx,
+                None,
+                step_index,
+                &command.source,
+                step,
+                HashMap::new(),
+            ) {
                 return Err(CustomCommandError::CommandError { error });
             }
         }
@@ -74,9 +103,11 @@ pub fn execute_custom_command(
             );
             let _indent = LogIndent::new();
 
-            for step in custom_command {
+            for (step_index, step) in custom_command.iter().enumerate() {
                 if let Err(error) = execute_external_command(
                     ctx,
+                    Some(component_name),
+                    step_index,
                     ctx.application.component_source_dir(component_name),
                     step,
                     HashMap::new(),
@@ -92,6 +123,8 @@ pub fn execute_custom_command(
 
 pub fn execute_external_command(
     ctx: &ApplicationContext,
+    component_name: Option<&AppComponentName>,
+    step_index: usize,
     base_build_dir: &Path,
     command: &app_raw::ExternalCommand,
     additional_env_vars: HashMap<String, String>,
@@ -102,17 +135,48 @@ pub fn execute_external_command(
         .map(|dir| base_build_dir.join(dir))
         .unwrap_or_else(|| base_build_dir.to_path_buf());
 
-    let task_result_marker = TaskResultMarker::new(
+    if let Some(condition) = &command.when {
+        let condition_dir = if build_dir.exists() {
+            &build_dir
+        } else {
+            base_build_dir
+        };
+        if let Some(unmet_condition) = unmet_condition(condition, condition_dir) {
+            let task = format!(
+                "executing external command '{}' in directory {}",
+                command.command.log_color_highlight(),
+                build_dir.log_color_highlight()
+            );
+            ctx.emit_build_event(component_name, || BuildEvent::SkippedByCondition {
+                task: task.clone(),
+                condition: unmet_condition.clone(),
+            });
+            log_warn_action(
+                "Skipping",
+                format!(
+                    "{}, condition not met: {}",
+                    task,
+                    unmet_condition.log_color_highlight()
+                ),
+            );
+            return Ok(());
+        }
+    }
+
+    // The task hash does not depend on the location of the application, so task results
+    // (and build cache entries) are still valid in a fresh checkout at a different path
+    let relative_build_dir = build_dir
+        .strip_prefix(&ctx.app_root_dir)
+        .unwrap_or(&build_dir);
+
+    let mut task_result_marker = TaskResultMarker::new(
         &ctx.application.task_result_marker_dir(),
         ResolvedExternalCommandMarkerHash {
-            build_dir: &build_dir,
+            build_dir: relative_build_dir,
             command,
         },
     )?;
 
-    let skip_up_to_date_checks =
-        ctx.config.skip_up_to_date_checks || !task_result_marker.is_up_to_date();
-
     debug!(
         command = ?command,
         "execute external command"
@@ -125,21 +189,64 @@ pub fn execute_external_command(
         map
     };
 
-    let command_string = envsubst::substitute(&command.command, &env_vars)
-        .context("Failed to substitute env vars in command")?;
+    let env_file = command
+        .env_file
+        .as_ref()
+        .map(|env_file| build_dir.join(env_file));
+    let command_env_vars = command_env_vars(command, env_file.as_deref(), env_vars.clone())?;
+    let timeout = command.timeout_duration()?;
+
+    let command_string = {
+        let mut env_vars = env_vars;
+        env_vars.extend(command_env_vars.clone());
+        envsubst::substitute(&command.command, &env_vars)
+            .context("Failed to substitute env vars in command")?
+    };
 
+    let mut build_cache_entry: Option<(String, Vec<PathBuf>)> = None;
     if !command.sources.is_empty() && !command.targets.is_empty() {
-        let sources = compile_and_collect_globs(&build_dir, &command.sources)?;
+        let mut sources = compile_and_collect_globs(&build_dir, &command.sources)?;
+        sources.extend(env_file.clone());
         let targets = compile_and_collect_globs(&build_dir, &command.targets)?;
 
-        if is_up_to_date(skip_up_to_date_checks, || sources, || targets) {
-            log_skipping_up_to_date(format!(
-                "executing external command '{}' in directory {}",
-                command_string.log_color_highlight(),
-                build_dir.log_color_highlight()
-            ));
+        if is_up_to_date(ctx, &mut task_result_marker, || sources.clone(), || targets) {
+            log_skipping_up_to_date_task(
+                ctx,
+                component_name,
+                format!(
+                    "executing external command '{}' in directory {}",
+                    command_string.log_color_highlight(),
+                    build_dir.log_color_highlight()
+                ),
+            );
             return Ok(());
         }
+
+        if let Some(cache_targets) = literal_targets(&build_dir, &command.targets) {
+            let cache_key = build_cache_key(
+                ctx,
+                ResolvedExternalCommandMarkerHash {
+                    build_dir: relative_build_dir,
+                    command,
+                },
+                sources,
+            )?;
+
+            if restore_from_build_cache(ctx, component_name, cache_key.as_deref(), &cache_targets) {
+                log_skipping_restored_task(
+                    ctx,
+                    component_name,
+                    format!(
+                        "executing external command '{}' in directory {}",
+                        command_string.log_color_highlight(),
+                        build_dir.log_color_highlight()
+                    ),
+                );
+                return task_result_marker.success();
+            }
+
+            build_cache_entry = cache_key.map(|cache_key| (cache_key, cache_targets));
+        }
     }
 
     log_action(
@@ -174,29 +281,523 @@ pub fn execute_external_command(
             }
         }
 
-        let command_tokens = shlex::split(&command_string).ok_or_else(|| {
-            anyhow::anyhow!("Failed to parse external command: {}", command_string)
-        })?;
-        if command_tokens.is_empty() {
-            return Err(anyhow!("Empty command!"));
-        }
+        let mut process = if command.shell {
+            shell_command(&command_string)
+        } else {
+            let command_tokens = shlex::split(&command_string).ok_or_else(|| {
+                anyhow::anyhow!("Failed to parse external command: {}", command_string)
+            })?;
+            if command_tokens.is_empty() {
+                return Err(anyhow!("Empty command!"));
+            }
+
+            let mut process = Command::new(command_tokens[0].clone());
+            process.args(command_tokens.iter().skip(1));
+            process
+        };
+        process.current_dir(&build_dir).envs(&command_env_vars);
+
+        // When logs are captured (e.g. for parallel builds), the command output is only logged
+        // after the command is finished, so it stays together with the related log lines
+        let log_captured = is_log_captured();
+        let attempts = command.retries.unwrap_or_default() + 1;
+        let mut attempt = 1;
+        let output = loop {
+            let started = Instant::now();
+            let output = execute_with_captured_output(&mut process, !log_captured, timeout)?;
 
-        let result = Command::new(command_tokens[0].clone())
-            .args(command_tokens.iter().skip(1))
-            .current_dir(build_dir)
-            .status()
-            .with_context(|| "Failed to execute command".to_string())?;
+            ctx.emit_build_event(component_name, || BuildEvent::CommandExecuted {
+                command: command_string.clone(),
+                dir: build_dir.clone(),
+                duration_ms: started.elapsed().as_millis() as u64,
+                exit_code: output.status.code(),
+            });
 
-        if result.success() {
+            if log_captured {
+                output.stdout.iter().chain(&output.stderr).for_each(logln);
+            }
+
+            if output.success() || attempt == attempts {
+                break output;
+            }
+
+            attempt += 1;
+            log_build_warning(
+                ctx,
+                component_name,
+                "Retrying",
+                format!(
+                    "external command '{}'{}, attempt {} of {}",
+                    command_string.log_color_highlight(),
+                    if output.timed_out {
+                        " after timeout"
+                    } else {
+                        ""
+                    },
+                    attempt.to_string().log_color_highlight(),
+                    attempts.to_string().log_color_highlight()
+                ),
+            );
+        };
+
+        task_result_marker.save_output(&output.stdout, &output.stderr)?;
+
+        if output.success() {
             Ok(())
         } else {
-            Err(anyhow!(format!(
-                "Command failed with exit code: {}",
-                result
-                    .code()
-                    .map(|code| code.to_string().log_color_error_highlight().to_string())
-                    .unwrap_or_else(|| "?".to_string())
-            )))
-        }
-    })())
+            Err(anyhow!(ExternalCommandError {
+                exit_code: output.status.code(),
+                timeout: timeout.filter(|_| output.timed_out),
+                attempts,
+                component_name: component_name.cloned(),
+                step_index,
+                command: command_string.clone(),
+                dir: build_dir.clone(),
+                stderr_tail: output.stderr_tail(),
+                stderr_log: task_result_marker.stderr_log_file_path().to_path_buf(),
+            }))
+        }
+    })())?;
+
+    if let Some((cache_key, cache_targets)) = build_cache_entry {
+        store_in_build_cache(ctx, component_name, Some(&cache_key), &cache_targets);
+    }
+
+    Ok(())
+}
+
+/// Returns the description of the first condition which is not met, unknown conditions are
+/// ignored, as those are already reported as warnings during manifest validation.
+fn unmet_condition(condition: &app_raw::ExternalCommandCondition, dir: &Path) -> Option<String> {
+    if let Some(os) = &condition.os {
+        if os != std::env::consts::OS && os != std::env::consts::FAMILY {
+            return Some(format!("os: {}", os));
+        }
+    }
+
+    if let Some(arch) = &condition.arch {
+        if arch != std::env::consts::ARCH {
+            return Some(format!("arch: {}", arch));
+        }
+    }
+
+    if let Some(name) = &condition.env_set {
+        if std::env::var_os(name).is_none() {
+            return Some(format!("envSet: {}", name));
+        }
+    }
+
+    for (name, value) in &condition.env_equals {
+        if std::env::var(name).ok().as_ref() != Some(value) {
+            return Some(format!("envEquals: {}={}", name, value));
+        }
+    }
+
+    if let Some(tool) = &condition.tool_available {
+        if !is_tool_available(dir, tool) {
+            return Some(format!("toolAvailable: {}", tool));
+        }
+    }
+
+    None
+}
+
+struct CommandOutput {
+    status: ExitStatus,
+    timed_out: bool,
+    stdout: Vec<String>,
+    stderr: Vec<String>,
+}
+
+impl CommandOutput {
+    fn success(&self) -> bool {
+        !self.timed_out && self.status.success()
+    }
+
+    /// The last stderr lines, which are included in the error of a failed command
+    fn stderr_tail(&self) -> Vec<String> {
+        self.stderr
+            .iter()
+            .skip(
+                self.stderr
+                    .len()
+                    .saturating_sub(EXTERNAL_COMMAND_ERROR_STDERR_LINES),
+            )
+            .cloned()
+            .collect()
+    }
+}
+
+/// Executes the command with piped stdout and stderr, and collects the output lines. With
+/// live_output the lines are also logged as soon as they are read. The command is killed
+/// if it does not finish within the timeout.
+fn execute_with_captured_output(
+    process: &mut Command,
+    live_output: bool,
+    timeout: Option<Duration>,
+) -> anyhow::Result<CommandOutput> {
+    let mut child = process
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| "Failed to execute command".to_string())?;
+
+    // Both streams are read concurrently, otherwise a full pipe buffer could block the command
+    let stdout = OutputReader::spawn(
+        child.stdout.take().expect("Missing piped stdout"),
+        live_output,
+    );
+    let stderr = OutputReader::spawn(
+        child.stderr.take().expect("Missing piped stderr"),
+        live_output,
+    );
+
+    let (status, timed_out) = wait_with_timeout(&mut child, timeout)?;
+
+    let output_deadline = timed_out.then(|| Instant::now() + TIMED_OUT_OUTPUT_GRACE_PERIOD);
+    Ok(CommandOutput {
+        status,
+        timed_out,
+        stdout: stdout.finish(output_deadline)?,
+        stderr: stderr.finish(output_deadline)?,
+    })
+}
+
+struct OutputReader {
+    lines: Arc<Mutex<Vec<String>>>,
+    handle: JoinHandle<anyhow::Result<()>>,
+}
+
+impl OutputReader {
+    fn spawn<R: Read + Send + 'static>(output: R, live_output: bool) -> Self {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let handle = {
+            let lines = lines.clone();
+            std::thread::spawn(move || read_output_lines(output, live_output, &lines))
+        };
+        Self { lines, handle }
+    }
+
+    /// Returns the output lines once the output is closed. With a deadline the reader is only
+    /// waited for until the deadline, and the lines read so far are returned.
+    fn finish(self, deadline: Option<Instant>) -> anyhow::Result<Vec<String>> {
+        if let Some(deadline) = deadline {
+            while !self.handle.is_finished() && Instant::now() < deadline {
+                std::thread::sleep(TIMEOUT_POLL_INTERVAL);
+            }
+        }
+
+        if deadline.is_none() || self.handle.is_finished() {
+            self.handle.join().expect("Failed to join output reader")?;
+        }
+
+        Ok(std::mem::take(
+            &mut *self.lines.lock().expect("Failed to lock output lines"),
+        ))
+    }
+}
+
+/// Returns the exit status, and whether the command was killed because of the timeout
+fn wait_with_timeout(
+    child: &mut Child,
+    timeout: Option<Duration>,
+) -> anyhow::Result<(ExitStatus, bool)> {
+    let context = || "Failed to wait for command".to_string();
+
+    let Some(timeout) = timeout else {
+        return Ok((child.wait().with_context(context)?, false));
+    };
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait().with_context(context)? {
+            return Ok((status, false));
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            child
+                .kill()
+                .with_context(|| "Failed to kill timed out command".to_string())?;
+            return Ok((child.wait().with_context(context)?, true));
+        }
+
+        std::thread::sleep(TIMEOUT_POLL_INTERVAL.min(deadline - now));
+    }
+}
+
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut process = Command::new("cmd");
+        process.arg("/C").arg(command);
+        process
+    } else {
+        let mut process = Command::new("sh");
+        process.arg("-c").arg(command);
+        process
+    }
+}
+
+/// Env vars set for the command, the ones from the env file are overridden by the ones
+/// defined in the env map. Values in the env map can reference other env vars.
+fn command_env_vars(
+    command: &app_raw::ExternalCommand,
+    env_file: Option<&Path>,
+    mut substitution_env_vars: HashMap<String, String>,
+) -> anyhow::Result<BTreeMap<String, String>> {
+    let mut env_vars = BTreeMap::new();
+
+    if let Some(env_file) = env_file {
+        let env_file_vars = parse_env_file(&fs::read_to_string(env_file)?).with_context(|| {
+            anyhow!(
+                "Failed to parse env file {}",
+                env_file.log_color_highlight()
+            )
+        })?;
+        substitution_env_vars.extend(env_file_vars.clone());
+        env_vars.extend(env_file_vars);
+    }
+
+    for (name, value) in &command.env {
+        let value = envsubst::substitute(value, &substitution_env_vars).with_context(|| {
+            anyhow!(
+                "Failed to substitute env vars in env var {}",
+                name.log_color_highlight()
+            )
+        })?;
+        substitution_env_vars.insert(name.clone(), value.clone());
+        env_vars.insert(name.clone(), value);
+    }
+
+    Ok(env_vars)
+}
+
+/// Parses env files in the usual dotenv format: KEY=VALUE lines, with optional export prefix
+/// and quoted values, empty lines and lines starting with # are ignored.
+fn parse_env_file(content: &str) -> anyhow::Result<Vec<(String, String)>> {
+    let mut env_vars = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((name, value)) = line.split_once('=') else {
+            bail!("Missing '=' in line {}", idx + 1);
+        };
+
+        let name = name.trim();
+        if name.is_empty() {
+            bail!("Missing env var name in line {}", idx + 1);
+        }
+
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|quote| {
+                value
+                    .strip_prefix(*quote)
+                    .and_then(|value| value.strip_suffix(*quote))
+            })
+            .unwrap_or(value);
+
+        env_vars.push((name.to_string(), value.to_string()));
+    }
+    Ok(env_vars)
+}
+
+fn read_output_lines<R: Read>(
+    output: R,
+    live_output: bool,
+    lines: &Mutex<Vec<String>>,
+) -> anyhow::Result<()> {
+    let mut reader = BufReader::new(output);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if reader
+            .read_until(b'\n', &mut line)
+            .with_context(|| "Failed to read command output".to_string())?
+            == 0
+        {
+            break;
+        }
+        let line = String::from_utf8_lossy(&line)
+            .trim_end_matches(['\n', '\r'])
+            .to_string();
+        if live_output {
+            logln(&line);
+        }
+        lines
+            .lock()
+            .expect("Failed to lock output lines")
+            .push(line);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::build::external_command::{
+        execute_with_captured_output, parse_env_file, read_output_lines, shell_command,
+        unmet_condition,
+    };
+    use crate::app::build::task_result_marker::{ComponentGeneratorMarkerHash, TaskResultMarker};
+    use crate::app::error::ExternalCommandError;
+    use crate::log::is_log_captured;
+    use crate::model::app::AppComponentName;
+    use crate::model::app_raw::ExternalCommandCondition;
+    use assert2::check;
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+    use test_r::test;
+
+    #[test]
+    fn output_lines_are_split_and_trimmed() {
+        let lines = Mutex::new(Vec::new());
+        read_output_lines(&b"first\r\nsecond\n\ninvalid \xff\nlast"[..], false, &lines).unwrap();
+        check!(
+            lines.into_inner().unwrap() == vec!["first", "second", "", "invalid \u{FFFD}", "last"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn timeout_with_background_process_holding_the_output() {
+        let started = Instant::now();
+        let output = execute_with_captured_output(
+            &mut shell_command("echo started; sleep 30 & sleep 30"),
+            false,
+            Some(Duration::from_millis(200)),
+        )
+        .unwrap();
+
+        check!(started.elapsed() < Duration::from_secs(10));
+        check!(output.timed_out);
+        check!(!output.success());
+        check!(output.stdout == vec!["started"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failed_command_reports_stderr_tail() {
+        // Default mode: sequential build, without captured logs and build events
+        check!(!is_log_captured());
+
+        let marker_dir = tempfile::tempdir().unwrap();
+        let task_result_marker = TaskResultMarker::new(
+            marker_dir.path(),
+            ComponentGeneratorMarkerHash {
+                component_name: &AppComponentName::from("app:component"),
+                generator_kind: "test",
+            },
+        )
+        .unwrap();
+
+        let output = execute_with_captured_output(
+            &mut shell_command(
+                "echo output; for i in $(seq 1 25); do echo \"error $i\" >&2; done; exit 3",
+            ),
+            !is_log_captured(),
+            None,
+        )
+        .unwrap();
+        task_result_marker
+            .save_output(&output.stdout, &output.stderr)
+            .unwrap();
+
+        check!(!output.success());
+        check!(output.status.code() == Some(3));
+        check!(output.stdout == vec!["output"]);
+
+        let stderr_tail = output.stderr_tail();
+        check!(stderr_tail == (6..=25).map(|i| format!("error {}", i)).collect::<Vec<_>>());
+
+        let stderr_log = task_result_marker.stderr_log_file_path().to_path_buf();
+        let saved_stderr = std::fs::read_to_string(&stderr_log).unwrap();
+        check!(saved_stderr.lines().count() == 25);
+        check!(saved_stderr.ends_with("error 25\n"));
+
+        let error = ExternalCommandError {
+            exit_code: output.status.code(),
+            timeout: None,
+            attempts: 1,
+            component_name: None,
+            step_index: 0,
+            command: "failing".to_string(),
+            dir: PathBuf::from("."),
+            stderr_tail,
+            stderr_log,
+        }
+        .to_string();
+        check!(error.contains("last 20 lines of stderr:"));
+        check!(error.contains("    error 6\n"));
+        check!(!error.contains("    error 5\n"));
+        check!(error.ends_with("    error 25"));
+    }
+
+    #[test]
+    fn env_file_parsing() {
+        let env_vars = parse_env_file(
+            "# comment\n\nA=1\nexport B = two words \nC=\"quoted = value\"\nD='single'\nE=\n",
+        )
+        .unwrap();
+        check!(
+            env_vars
+                == vec![
+                    ("A".to_string(), "1".to_string()),
+                    ("B".to_string(), "two words".to_string()),
+                    ("C".to_string(), "quoted = value".to_string()),
+                    ("D".to_string(), "single".to_string()),
+                    ("E".to_string(), "".to_string()),
+                ]
+        );
+
+        check!(parse_env_file("A=1\nINVALID").is_err());
+    }
+
+    #[test]
+    fn command_conditions() {
+        let dir = Path::new(".");
+        let met = ExternalCommandCondition {
+            os: Some(std::env::consts::OS.to_string()),
+            arch: Some(std::env::consts::ARCH.to_string()),
+            env_set: Some("PATH".to_string()),
+            ..ExternalCommandCondition::default()
+        };
+        check!(unmet_condition(&met, dir).is_none());
+
+        let os_family = ExternalCommandCondition {
+            os: Some(std::env::consts::FAMILY.to_string()),
+            ..ExternalCommandCondition::default()
+        };
+        check!(unmet_condition(&os_family, dir).is_none());
+
+        let unmet_os = ExternalCommandCondition {
+            os: Some("unknown-os".to_string()),
+            ..ExternalCommandCondition::default()
+        };
+        check!(unmet_condition(&unmet_os, dir) == Some("os: unknown-os".to_string()));
+
+        let unmet_env = ExternalCommandCondition {
+            env_equals: BTreeMap::from([(
+                "GOLEM_CLI_TEST_UNDEFINED_ENV_VAR".to_string(),
+                "1".to_string(),
+            )]),
+            ..ExternalCommandCondition::default()
+        };
+        check!(unmet_condition(&unmet_env, dir).is_some());
+
+        let unmet_tool = ExternalCommandCondition {
+            tool_available: Some("golem-cli-test-undefined-tool".to_string()),
+            ..ExternalCommandCondition::default()
+        };
+        check!(
+            unmet_condition(&unmet_tool, dir)
+                == Some("toolAvailable: golem-cli-test-undefined-tool".to_string())
+        );
+    }
 }
@@ -13,11 +13,14 @@
 // limitations under the License.
 
 use crate::app::build::task_result_marker::{ComponentGeneratorMarkerHash, TaskResultMarker};
-use crate::app::build::{delete_path_logged, env_var_flag, is_up_to_date};
+use crate::app::build::{
+    build_cache_key, delete_path_logged, env_var_flag, is_up_to_date, log_skipping_up_to_date_task,
+    restore_from_build_cache, store_in_build_cache,
+};
 use crate::app::context::ApplicationContext;
 use crate::fs;
 use crate::fs::PathExtra;
-use crate::log::{log_action, log_skipping_up_to_date, LogColorize, LogIndent};
+use crate::log::{log_action, LogColorize, LogIndent};
 use crate::model::app::{AppComponentName, DependencyType, DependentComponent};
 use crate::wasm_rpc_stubgen::cargo::regenerate_cargo_package_component;
 use crate::wasm_rpc_stubgen::commands;
@@ -77,7 +80,7 @@ fn create_generated_base_wit(
         .application
         .component_source_wit(component_name, ctx.profile());
     let component_generated_base_wit = ctx.application.component_generated_base_wit(component_name);
-    let task_result_marker = TaskResultMarker::new(
+    let mut task_result_marker = TaskResultMarker::new(
         &ctx.application.task_result_marker_dir(),
         ComponentGeneratorMarkerHash {
             component_name,
@@ -85,17 +88,22 @@ fn create_generated_base_wit(
         },
     )?;
 
+    let dep_graph_up_to_date = ctx.wit.is_dep_graph_up_to_date(component_name)?;
     if is_up_to_date(
-        ctx.config.skip_up_to_date_checks
-            || !task_result_marker.is_up_to_date()
-            || !ctx.wit.is_dep_graph_up_to_date(component_name)?,
+        ctx,
+        &mut task_result_marker,
         || [component_source_wit.clone()],
         || [component_generated_base_wit.clone()],
-    ) {
-        log_skipping_up_to_date(format!(
-            "creating generated base wit directory for {}",
-            component_name.as_str().log_color_highlight()
-        ));
+    ) && dep_graph_up_to_date
+    {
+        log_skipping_up_to_date_task(
+            ctx,
+            Some(component_name),
+            format!(
+                "creating generated base wit directory for {}",
+                component_name.as_str().log_color_highlight()
+            ),
+        );
         Ok(false)
     } else {
         log_action(
@@ -194,7 +202,7 @@ fn create_generated_wit(
     let component_generated_wit = ctx
         .application
         .component_generated_wit(component_name, ctx.profile());
-    let task_result_marker = TaskResultMarker::new(
+    let mut task_result_marker = TaskResultMarker::new(
         &ctx.application.task_result_marker_dir(),
         ComponentGeneratorMarkerHash {
             component_name,
@@ -202,17 +210,22 @@ fn create_generated_wit(
         },
     )?;
 
+    let dep_graph_up_to_date = ctx.wit.is_dep_graph_up_to_date(component_name)?;
     if is_up_to_date(
-        ctx.config.skip_up_to_date_checks
-            || !task_result_marker.is_up_to_date()
-            || !ctx.wit.is_dep_graph_up_to_date(component_name)?,
+        ctx,
+        &mut task_result_marker,
         || [component_generated_base_wit.clone()],
         || [component_generated_wit.clone()],
-    ) {
-        log_skipping_up_to_date(format!(
-            "creating generated wit directory for {}",
-            component_name.as_str().log_color_highlight()
-        ));
+    ) && dep_graph_up_to_date
+    {
+        log_skipping_up_to_date_task(
+            ctx,
+            Some(component_name),
+            format!(
+                "creating generated wit directory for {}",
+                component_name.as_str().log_color_highlight()
+            ),
+        );
         Ok(false)
     } else {
         log_action(
@@ -264,10 +277,14 @@ fn update_cargo_toml(
 
     skip_up_to_date_checks |= skip_up_to_date_checks || ctx.config.skip_up_to_date_checks;
     if !skip_up_to_date_checks && task_result_marker.is_up_to_date() {
-        log_skipping_up_to_date(format!(
-            "updating Cargo.toml for {}",
-            component_name.as_str().log_color_highlight()
-        ));
+        log_skipping_up_to_date_task(
+            ctx,
+            Some(component_name),
+            format!(
+                "updating Cargo.toml for {}",
+                component_name.as_str().log_color_highlight()
+            ),
+        );
         return Ok(());
     }
 
@@ -304,7 +321,7 @@ async fn build_client(
 
     let client_wasm = ctx.application.client_wasm(&component.name);
     let client_wit = ctx.application.client_wit(&component.name);
-    let task_result_marker = TaskResultMarker::new(
+    let mut task_result_marker = TaskResultMarker::new(
         &ctx.application.task_result_marker_dir(),
         ComponentGeneratorMarkerHash {
             component_name: &component.name,
@@ -313,8 +330,9 @@ async fn build_client(
     )?;
 
     if is_up_to_date(
-        ctx.config.skip_up_to_date_checks || !task_result_marker.is_up_to_date(),
-        || client_sources,
+        ctx,
+        &mut task_result_marker,
+        || client_sources.clone(),
         || {
             if component.dep_type == DependencyType::StaticWasmRpc {
                 vec![client_wit.clone(), client_wasm.clone()]
@@ -324,12 +342,29 @@ async fn build_client(
         },
     ) {
         // TODO: message based on type
-        log_skipping_up_to_date(format!(
-            "generating WASM RPC client for {}",
-            component.name.as_str().log_color_highlight()
-        ));
+        log_skipping_up_to_date_task(
+            ctx,
+            Some(&component.name),
+            format!(
+                "generating WASM RPC client for {}",
+                component.name.as_str().log_color_highlight()
+            ),
+        );
         Ok(false)
     } else {
+        let build_cache_key = if component.dep_type == DependencyType::StaticWasmRpc {
+            build_cache_key(
+                ctx,
+                ComponentGeneratorMarkerHash {
+                    component_name: &component.name,
+                    generator_kind: "client",
+                },
+                client_sources,
+            )?
+        } else {
+            None
+        };
+
         task_result_marker.result(
             async {
                 match component.dep_type {
@@ -357,19 +392,45 @@ async fn build_client(
                         );
                         fs::create_dir_all(&client_wit_root)?;
 
-                        let offline = ctx.config.offline;
-                        commands::generate::build(
-                            ctx.component_stub_def(
+                        let cache_targets = [client_wasm.clone()];
+                        if restore_from_build_cache(
+                            ctx,
+                            Some(&component.name),
+                            build_cache_key.as_deref(),
+                            &cache_targets,
+                        ) {
+                            let stub_def = ctx.component_stub_def(
                                 &component.name,
                                 ctx.application
                                     .component_properties(&component.name, ctx.profile())
                                     .is_ephemeral(),
-                            )?,
-                            &client_wasm,
-                            &client_wit,
-                            offline,
-                        )
-                        .await?;
+                            )?;
+                            commands::generate::generate_and_copy_client_wit(
+                                stub_def,
+                                &client_wit,
+                            )?;
+                        } else {
+                            let offline = ctx.config.offline;
+                            commands::generate::build(
+                                ctx.component_stub_def(
+                                    &component.name,
+                                    ctx.application
+                                        .component_properties(&component.name, ctx.profile())
+                                        .is_ephemeral(),
+                                )?,
+                                &client_wasm,
+                                &client_wit,
+                                offline,
+                            )
+                            .await?;
+
+                            store_in_build_cache(
+                                ctx,
+                                Some(&component.name),
+                                build_cache_key.as_deref(),
+                                &cache_targets,
+                            );
+                        }
 
                         if !env_var_flag("WASM_RPC_KEEP_CLIENT_DIR") {
                             delete_path_logged("client temp build dir", &client_wit_root)?;
@@ -12,11 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::app::build::is_up_to_date;
 use crate::app::build::task_result_marker::{LinkRpcMarkerHash, TaskResultMarker};
+use crate::app::build::{
+    build_cache_key, is_up_to_date, log_skipping_restored_task, log_skipping_up_to_date_task,
+    restore_from_build_cache, store_in_build_cache,
+};
 use crate::app::context::ApplicationContext;
 use crate::fs;
-use crate::log::{log_action, log_skipping_up_to_date, LogColorize, LogIndent};
+use crate::log::{log_action, LogColorize, LogIndent};
 use crate::model::app::DependencyType;
 use crate::wasm_rpc_stubgen::commands;
 use itertools::Itertools;
@@ -59,7 +62,7 @@ pub async fn link(ctx: &ApplicationContext) -> anyhow::Result<()> {
             .component_wasm(component_name, ctx.profile());
         let linked_wasm = ctx.application.component_linked_wasm(component_name);
 
-        let task_result_marker = TaskResultMarker::new(
+        let mut task_result_marker = TaskResultMarker::new(
             &ctx.application.task_result_marker_dir(),
             LinkRpcMarkerHash {
                 component_name,
@@ -110,7 +113,8 @@ pub async fn link(ctx: &ApplicationContext) -> anyhow::Result<()> {
         }
 
         if is_up_to_date(
-            ctx.config.skip_up_to_date_checks || !task_result_marker.is_up_to_date(),
+            ctx,
+            &mut task_result_marker,
             || {
                 let mut inputs = wasms_to_compose_with.clone();
                 inputs.push(component_wasm.clone());
@@ -118,10 +122,50 @@ pub async fn link(ctx: &ApplicationContext) -> anyhow::Result<()> {
             },
             || [linked_wasm.clone()],
         ) {
-            log_skipping_up_to_date(format!(
-                "linking dependencies for {}",
-                component_name.as_str().log_color_highlight(),
-            ));
+            log_skipping_up_to_date_task(
+                ctx,
+                Some(component_name),
+                format!(
+                    "linking dependencies for {}",
+                    component_name.as_str().log_color_highlight(),
+                ),
+            );
+            continue;
+        }
+
+        // Without static dependencies the component WASM is only copied, no need to cache that
+        let build_cache_key = if wasms_to_compose_with.is_empty() {
+            None
+        } else {
+            build_cache_key(
+                ctx,
+                LinkRpcMarkerHash {
+                    component_name,
+                    dependencies: &static_dependencies,
+                },
+                wasms_to_compose_with
+                    .iter()
+                    .cloned()
+                    .chain([component_wasm.clone()]),
+            )?
+        };
+        let cache_targets = [linked_wasm.clone()];
+
+        if restore_from_build_cache(
+            ctx,
+            Some(component_name),
+            build_cache_key.as_deref(),
+            &cache_targets,
+        ) {
+            log_skipping_restored_task(
+                ctx,
+                Some(component_name),
+                format!(
+                    "linking dependencies for {}",
+                    component_name.as_str().log_color_highlight(),
+                ),
+            );
+            task_result_marker.success()?;
             continue;
         }
 
@@ -167,6 +211,13 @@ pub async fn link(ctx: &ApplicationContext) -> anyhow::Result<()> {
             }
             .await,
         )?;
+
+        store_in_build_cache(
+            ctx,
+            Some(component_name),
+            build_cache_key.as_deref(),
+            &cache_targets,
+        );
     }
 
     Ok(())
@@ -13,48 +13,100 @@
 // limitations under the License.
 
 use crate::app::build::add_metadata::add_metadata_to_selected_components;
+use crate::app::build::cache::BuildCache;
 use crate::app::build::componentize::componentize;
+use crate::app::build::event::BuildEvent;
 use crate::app::build::gen_rpc::gen_rpc;
 use crate::app::build::link::link;
+use crate::app::build::post_link::post_link;
+use crate::app::build::task_result_marker::{
+    task_hash, TaskResultMarker, TaskResultMarkerHashInput,
+};
 use crate::app::context::ApplicationContext;
 use crate::fs;
-use crate::log::{log_warn_action, LogColorize};
-use crate::model::app::AppBuildStep;
+use crate::log::{
+    log_skipping_restored_from_build_cache, log_skipping_up_to_date, log_warn_action, LogColorize,
+};
+use crate::model::app::{AppBuildStep, AppComponentName, UpToDateCheckMode};
 use anyhow::{anyhow, Context};
 use chrono::{DateTime, Utc};
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::time::{Instant, SystemTime};
 use tracing::debug;
 use walkdir::WalkDir;
 
 pub mod add_metadata;
+pub mod cache;
 pub mod clean;
 pub mod componentize;
+pub mod event;
 pub mod external_command;
 pub mod gen_rpc;
 pub mod link;
+pub mod post_link;
 pub mod task_result_marker;
 
 pub async fn build_app(ctx: &mut ApplicationContext) -> anyhow::Result<()> {
     if ctx.config.should_run_step(AppBuildStep::GenRpc) {
-        gen_rpc(ctx).await?;
+        let started = step_started(ctx, AppBuildStep::GenRpc);
+        let result = gen_rpc(ctx).await;
+        step_finished(ctx, started, result)?;
     }
     if ctx.config.should_run_step(AppBuildStep::Componentize) {
-        componentize(ctx)?;
+        let started = step_started(ctx, AppBuildStep::Componentize);
+        let result = componentize(ctx);
+        step_finished(ctx, started, result)?;
     }
     if ctx.config.should_run_step(AppBuildStep::Link) {
-        link(ctx).await?;
+        let started = step_started(ctx, AppBuildStep::Link);
+        let result = link(ctx).await;
+        step_finished(ctx, started, result)?;
+    }
+    if ctx.config.should_run_step(AppBuildStep::PostLink) {
+        let started = step_started(ctx, AppBuildStep::PostLink);
+        let result = post_link(ctx);
+        step_finished(ctx, started, result)?;
     }
     if ctx.config.should_run_step(AppBuildStep::AddMetadata) {
-        add_metadata_to_selected_components(ctx).await?;
+        let started = step_started(ctx, AppBuildStep::AddMetadata);
+        let result = add_metadata_to_selected_components(ctx).await;
+        step_finished(ctx, started, result)?;
     }
 
     Ok(())
 }
 
+fn step_started(ctx: &ApplicationContext, step: AppBuildStep) -> Instant {
+    if let Some(build_events) = ctx.build_events() {
+        build_events.set_step(Some(step));
+        build_events.emit(None, &BuildEvent::StepStarted);
+    }
+    Instant::now()
+}
+
+fn step_finished(
+    ctx: &ApplicationContext,
+    started: Instant,
+    result: anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    if let Some(build_events) = ctx.build_events() {
+        let event = match &result {
+            Ok(()) => BuildEvent::StepFinished {
+                duration_ms: started.elapsed().as_millis() as u64,
+            },
+            Err(err) => BuildEvent::Error {
+                message: format!("{:#}", err),
+            },
+        };
+        build_events.emit(None, &event);
+        build_events.set_step(None);
+    }
+    result
+}
+
 fn env_var_flag(name: &str) -> bool {
     std::env::var(name)
         .ok()
@@ -112,7 +164,239 @@ fn delete_path_logged(context: &str, path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn is_up_to_date<S, T, FS, FT>(skip_check: bool, sources: FS, targets: FT) -> bool
+fn is_up_to_date<S, T, FS, FT>(
+    ctx: &ApplicationContext,
+    task_result_marker: &mut TaskResultMarker,
+    sources: FS,
+    targets: FT,
+) -> bool
+where
+    S: IntoIterator<Item = PathBuf>,
+    T: IntoIterator<Item = PathBuf>,
+    FS: FnOnce() -> S,
+    FT: FnOnce() -> T,
+{
+    match ctx.config.up_to_date_check {
+        UpToDateCheckMode::Timestamp => is_up_to_date_by_timestamps(
+            ctx.config.skip_up_to_date_checks || !task_result_marker.is_up_to_date(),
+            sources,
+            targets,
+        ),
+        UpToDateCheckMode::ContentHash => is_up_to_date_by_content_hash(
+            ctx.config.skip_up_to_date_checks,
+            &ctx.app_root_dir,
+            task_result_marker,
+            sources,
+            targets,
+        ),
+    }
+}
+
+/// Source paths are hashed relative to the application root directory, so the result does not
+/// depend on the location of the application (e.g. a fresh checkout, or a CI workspace).
+fn is_up_to_date_by_content_hash<S, T, FS, FT>(
+    skip_check: bool,
+    app_root_dir: &Path,
+    task_result_marker: &mut TaskResultMarker,
+    sources: FS,
+    targets: FT,
+) -> bool
+where
+    S: IntoIterator<Item = PathBuf>,
+    T: IntoIterator<Item = PathBuf>,
+    FS: FnOnce() -> S,
+    FT: FnOnce() -> T,
+{
+    // Sources are hashed even when the check is skipped, so the hash is saved on success
+    let up_to_date = match sources_content_hash(sources(), app_root_dir) {
+        Some(sources_hash) => {
+            debug!(%sources_hash, "sources content hash");
+            task_result_marker.is_up_to_date_by_sources_hash(sources_hash)
+        }
+        None => {
+            debug!("missing sources, not up-to-date");
+            false
+        }
+    };
+
+    if skip_check {
+        debug!("skipping up-to-date check");
+        return false;
+    }
+
+    if !up_to_date {
+        debug!("changed sources or command, not up-to-date");
+        return false;
+    }
+
+    let mut targets = targets().into_iter().peekable();
+    if targets.peek().is_none() || !targets.all(|target| target.exists()) {
+        debug!("missing targets, not up-to-date");
+        return false;
+    }
+
+    debug!("up to date result based on content hashes");
+    true
+}
+
+/// Hashes the paths and contents of all the source files, directories are walked recursively.
+/// Paths are hashed relative to the application root directory. Returns None if any of the
+/// sources is missing or cannot be read, while an empty set of sources (e.g. globs without
+/// matches) still has a stable hash.
+fn sources_content_hash<I: IntoIterator<Item = PathBuf>>(
+    sources: I,
+    app_root_dir: &Path,
+) -> Option<String> {
+    let mut files = BTreeSet::<PathBuf>::new();
+    for source in sources {
+        if fs::metadata(&source).ok()?.is_dir() {
+            files.extend(
+                WalkDir::new(&source)
+                    .into_iter()
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| !entry.file_type().is_dir())
+                    .map(|entry| entry.into_path()),
+            );
+        } else {
+            files.insert(source);
+        }
+    }
+
+    let mut hasher = blake3::Hasher::new();
+    for file in files {
+        let mut file_hasher = blake3::Hasher::new();
+        file_hasher
+            .update_reader(std::fs::File::open(&file).ok()?)
+            .ok()?;
+
+        let path = file.strip_prefix(app_root_dir).unwrap_or(&file);
+        hasher.update(path.to_string_lossy().as_bytes());
+        hasher.update(file_hasher.finalize().as_bytes());
+    }
+
+    Some(hasher.finalize().to_hex().to_string())
+}
+
+/// Build cache key for the task, the task hash input and the sources should not depend on
+/// the location of the application, so the key can be shared between machines.
+/// Returns None if the build cache is not enabled or any of the sources is missing.
+fn build_cache_key<T, S>(
+    ctx: &ApplicationContext,
+    task: T,
+    sources: S,
+) -> anyhow::Result<Option<String>>
+where
+    T: TaskResultMarkerHashInput,
+    S: IntoIterator<Item = PathBuf>,
+{
+    if ctx.build_cache().is_none() {
+        return Ok(None);
+    }
+
+    let Some(sources_hash) = sources_content_hash(sources, &ctx.app_root_dir) else {
+        debug!("missing sources, not using build cache");
+        return Ok(None);
+    };
+
+    Ok(Some(BuildCache::key(
+        &task_hash(&task)?,
+        &sources_hash,
+        ctx.profile(),
+    )))
+}
+
+/// Returns true if all the targets were restored from the build cache. Restoring is skipped
+/// when up-to-date checks are skipped. Cache errors are only reported as warnings, and handled
+/// as a cache miss.
+fn restore_from_build_cache(
+    ctx: &ApplicationContext,
+    component_name: Option<&AppComponentName>,
+    build_cache_key: Option<&str>,
+    targets: &[PathBuf],
+) -> bool {
+    if ctx.config.skip_up_to_date_checks {
+        return false;
+    }
+
+    match (ctx.build_cache(), build_cache_key) {
+        (Some(build_cache), Some(key)) => match build_cache.restore(key, targets) {
+            Ok(restored) => restored,
+            Err(err) => {
+                log_build_warning(
+                    ctx,
+                    component_name,
+                    "Ignoring",
+                    format!(
+                        "build cache {} for restoring, error: {:#}",
+                        build_cache.describe().log_color_highlight(),
+                        err
+                    ),
+                );
+                false
+            }
+        },
+        _ => false,
+    }
+}
+
+/// Stores the targets in the build cache, cache errors are only reported as warnings.
+fn store_in_build_cache(
+    ctx: &ApplicationContext,
+    component_name: Option<&AppComponentName>,
+    build_cache_key: Option<&str>,
+    targets: &[PathBuf],
+) {
+    if let (Some(build_cache), Some(key)) = (ctx.build_cache(), build_cache_key) {
+        if let Err(err) = build_cache.store(key, targets) {
+            log_build_warning(
+                ctx,
+                component_name,
+                "Ignoring",
+                format!(
+                    "build cache {} for storing, error: {:#}",
+                    build_cache.describe().log_color_highlight(),
+                    err
+                ),
+            );
+        }
+    }
+}
+
+fn log_skipping_up_to_date_task(
+    ctx: &ApplicationContext,
+    component_name: Option<&AppComponentName>,
+    task: String,
+) {
+    ctx.emit_build_event(component_name, || BuildEvent::SkippedUpToDate {
+        task: task.clone(),
+    });
+    log_skipping_up_to_date(task);
+}
+
+fn log_skipping_restored_task(
+    ctx: &ApplicationContext,
+    component_name: Option<&AppComponentName>,
+    task: String,
+) {
+    ctx.emit_build_event(component_name, || BuildEvent::RestoredFromCache {
+        task: task.clone(),
+    });
+    log_skipping_restored_from_build_cache(task);
+}
+
+fn log_build_warning(
+    ctx: &ApplicationContext,
+    component_name: Option<&AppComponentName>,
+    action: &str,
+    subject: String,
+) {
+    ctx.emit_build_event(component_name, || BuildEvent::Warning {
+        message: format!("{} {}", action, subject),
+    });
+    log_warn_action(action, subject);
+}
+
+fn is_up_to_date_by_timestamps<S, T, FS, FT>(skip_check: bool, sources: FS, targets: FT) -> bool
 where
     S: IntoIterator<Item = PathBuf>,
     T: IntoIterator<Item = PathBuf>,
@@ -194,3 +478,141 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::app::build::is_up_to_date_by_content_hash;
+    use crate::app::build::task_result_marker::{ComponentGeneratorMarkerHash, TaskResultMarker};
+    use crate::fs;
+    use crate::model::app::AppComponentName;
+    use assert2::check;
+    use std::path::{Path, PathBuf};
+    use test_r::test;
+
+    fn task_result_marker(dir: &Path) -> TaskResultMarker {
+        TaskResultMarker::new(
+            dir,
+            ComponentGeneratorMarkerHash {
+                component_name: &AppComponentName::from("app:component"),
+                generator_kind: "test",
+            },
+        )
+        .unwrap()
+    }
+
+    /// Task results are stored in the temp dir of the application, like for real builds
+    fn task_result_marker_dir(app_dir: &Path) -> PathBuf {
+        app_dir.join("golem-temp/task-results")
+    }
+
+    fn check_up_to_date(app_dir: &Path, sources: &[PathBuf], targets: &[PathBuf]) -> bool {
+        let mut task_result_marker = task_result_marker(&task_result_marker_dir(app_dir));
+        let up_to_date = is_up_to_date_by_content_hash(
+            false,
+            app_dir,
+            &mut task_result_marker,
+            || sources.to_vec(),
+            || targets.to_vec(),
+        );
+        if !up_to_date {
+            task_result_marker.success().unwrap();
+        }
+        up_to_date
+    }
+
+    #[test]
+    fn content_hash_up_to_date_until_sources_change() {
+        let work_dir = tempfile::tempdir().unwrap();
+        let sources = vec![work_dir.path().join("src")];
+        let targets = vec![work_dir.path().join("target/component.wasm")];
+        fs::write_str(work_dir.path().join("src/lib.rs"), "v1").unwrap();
+        fs::write_str(&targets[0], "wasm").unwrap();
+
+        check!(!check_up_to_date(work_dir.path(), &sources, &targets));
+        check!(check_up_to_date(work_dir.path(), &sources, &targets));
+
+        // Touching the sources without changing the content keeps the task up-to-date
+        fs::write_str(work_dir.path().join("src/lib.rs"), "v1").unwrap();
+        check!(check_up_to_date(work_dir.path(), &sources, &targets));
+
+        fs::write_str(work_dir.path().join("src/lib.rs"), "v2").unwrap();
+        check!(!check_up_to_date(work_dir.path(), &sources, &targets));
+        check!(check_up_to_date(work_dir.path(), &sources, &targets));
+
+        fs::write_str(work_dir.path().join("src/other.rs"), "v2").unwrap();
+        check!(!check_up_to_date(work_dir.path(), &sources, &targets));
+    }
+
+    #[test]
+    fn content_hash_not_up_to_date_with_missing_sources_or_targets() {
+        let work_dir = tempfile::tempdir().unwrap();
+        let sources = vec![work_dir.path().join("src/lib.rs")];
+        let targets = vec![work_dir.path().join("target/component.wasm")];
+        fs::write_str(&sources[0], "v1").unwrap();
+        fs::write_str(&targets[0], "wasm").unwrap();
+
+        check!(!check_up_to_date(work_dir.path(), &sources, &targets));
+        check!(check_up_to_date(work_dir.path(), &sources, &targets));
+
+        fs::remove(&targets[0]).unwrap();
+        check!(!check_up_to_date(work_dir.path(), &sources, &targets));
+
+        fs::write_str(&targets[0], "wasm").unwrap();
+        fs::remove(&sources[0]).unwrap();
+        check!(!check_up_to_date(work_dir.path(), &sources, &targets));
+        check!(!check_up_to_date(work_dir.path(), &sources, &targets));
+    }
+
+    #[test]
+    fn content_hash_up_to_date_without_matching_sources() {
+        let work_dir = tempfile::tempdir().unwrap();
+        let targets = vec![work_dir.path().join("target/component.wasm")];
+        fs::write_str(&targets[0], "wasm").unwrap();
+
+        check!(!check_up_to_date(work_dir.path(), &[], &targets));
+        check!(check_up_to_date(work_dir.path(), &[], &targets));
+    }
+
+    #[test]
+    fn content_hash_up_to_date_after_moving_the_app_dir() {
+        let parent_dir = tempfile::tempdir().unwrap();
+        let app_dir = parent_dir.path().join("app");
+        fs::write_str(app_dir.join("src/lib.rs"), "v1").unwrap();
+        fs::write_str(app_dir.join("target/component.wasm"), "wasm").unwrap();
+
+        let sources = vec![app_dir.join("src")];
+        let targets = vec![app_dir.join("target/component.wasm")];
+        check!(!check_up_to_date(&app_dir, &sources, &targets));
+        check!(check_up_to_date(&app_dir, &sources, &targets));
+
+        let app_dir = parent_dir.path().join("moved-app");
+        std::fs::rename(parent_dir.path().join("app"), &app_dir).unwrap();
+
+        let sources = vec![app_dir.join("src")];
+        let targets = vec![app_dir.join("target/component.wasm")];
+        check!(check_up_to_date(&app_dir, &sources, &targets));
+
+        fs::write_str(app_dir.join("src/lib.rs"), "v2").unwrap();
+        check!(!check_up_to_date(&app_dir, &sources, &targets));
+    }
+
+    #[test]
+    fn content_hash_check_skipped() {
+        let work_dir = tempfile::tempdir().unwrap();
+        let sources = vec![work_dir.path().join("src/lib.rs")];
+        let targets = vec![work_dir.path().join("target/component.wasm")];
+        fs::write_str(&sources[0], "v1").unwrap();
+        fs::write_str(&targets[0], "wasm").unwrap();
+
+        check!(!check_up_to_date(work_dir.path(), &sources, &targets));
+
+        let mut task_result_marker = task_result_marker(&task_result_marker_dir(work_dir.path()));
+        check!(!is_up_to_date_by_content_hash(
+            true,
+            work_dir.path(),
+            &mut task_result_marker,
+            || sources.clone(),
+            || targets.clone(),
+        ));
+    }
+}
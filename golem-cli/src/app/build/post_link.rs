@@ -0,0 +1,168 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::build::task_result_marker::{PostLinkMarkerHash, TaskResultMarker};
+use crate::app::build::{is_up_to_date, log_skipping_up_to_date_task};
+use crate::app::context::ApplicationContext;
+use crate::fs;
+use crate::log::{log_action, LogColorize, LogIndent};
+use crate::model::app::{AppComponentName, PostLinkProperties};
+use crate::model::text::fmt::format_binary_size;
+use crate::wasm_rpc_stubgen::commands::strip::{
+    is_debug_custom_section, strip_custom_sections, validate,
+};
+use anyhow::{anyhow, Context};
+use std::path::Path;
+
+pub fn post_link(ctx: &ApplicationContext) -> anyhow::Result<()> {
+    let component_names = ctx
+        .selected_component_names()
+        .iter()
+        .filter(|component_name| {
+            ctx.application
+                .component_properties(component_name, ctx.profile())
+                .post_link
+                .is_enabled()
+        })
+        .collect::<Vec<_>>();
+
+    if component_names.is_empty() {
+        return Ok(());
+    }
+
+    log_action("Post-linking", "components");
+    let _indent = LogIndent::new();
+
+    for component_name in component_names {
+        let post_link = &ctx
+            .application
+            .component_properties(component_name, ctx.profile())
+            .post_link;
+        let linked_wasm = ctx.application.component_linked_wasm(component_name);
+        let post_linked_wasm = ctx
+            .application
+            .component_post_linked_wasm(component_name, ctx.profile());
+
+        let mut task_result_marker = TaskResultMarker::new(
+            &ctx.application.task_result_marker_dir(),
+            PostLinkMarkerHash {
+                component_name,
+                post_link,
+            },
+        )?;
+
+        if is_up_to_date(
+            ctx,
+            &mut task_result_marker,
+            || vec![linked_wasm.clone()],
+            || [post_linked_wasm.clone()],
+        ) {
+            log_skipping_up_to_date_task(
+                ctx,
+                Some(component_name),
+                format!(
+                    "post-linking {}",
+                    component_name.as_str().log_color_highlight(),
+                ),
+            );
+        } else {
+            task_result_marker.result(post_link_component(
+                component_name,
+                post_link,
+                &linked_wasm,
+                &post_linked_wasm,
+            ))?;
+        }
+
+        if post_link.is_stripping() {
+            log_size_report(ctx, component_name)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn post_link_component(
+    component_name: &AppComponentName,
+    post_link: &PostLinkProperties,
+    linked_wasm: &Path,
+    post_linked_wasm: &Path,
+) -> anyhow::Result<()> {
+    let mut wasm = fs::read(linked_wasm)?;
+
+    if post_link.is_stripping() {
+        log_action(
+            "Stripping",
+            format!(
+                "{} from {}",
+                if post_link.strip_custom_sections {
+                    "custom sections"
+                } else {
+                    "debug info"
+                },
+                component_name.as_str().log_color_highlight(),
+            ),
+        );
+        wasm = strip_custom_sections(&wasm, |name| {
+            post_link.strip_custom_sections
+                || (post_link.strip_debug_info && is_debug_custom_section(name))
+        })
+        .with_context(|| anyhow!("Failed to strip {}", linked_wasm.log_color_highlight()))?;
+    }
+
+    if post_link.validate {
+        log_action(
+            "Validating",
+            component_name.as_str().log_color_highlight().to_string(),
+        );
+        validate(&wasm).with_context(|| {
+            anyhow!(
+                "Invalid component {}",
+                component_name.as_str().log_color_error_highlight()
+            )
+        })?;
+    }
+
+    fs::write(post_linked_wasm, wasm)
+}
+
+fn log_size_report(
+    ctx: &ApplicationContext,
+    component_name: &AppComponentName,
+) -> anyhow::Result<()> {
+    let file_size = |path: &Path| -> anyhow::Result<u64> { Ok(fs::metadata(path)?.len()) };
+
+    let raw_size = file_size(
+        &ctx.application
+            .component_wasm(component_name, ctx.profile()),
+    )?;
+    let linked_size = file_size(&ctx.application.component_linked_wasm(component_name))?;
+    let stripped_size = file_size(
+        &ctx.application
+            .component_post_linked_wasm(component_name, ctx.profile()),
+    )?;
+
+    log_action(
+        "Size",
+        format!(
+            "of {}: raw {}, linked {}, stripped {}",
+            component_name.as_str().log_color_highlight(),
+            format_binary_size(&raw_size).log_color_highlight(),
+            format_binary_size(&linked_size).log_color_highlight(),
+            format_binary_size(&stripped_size).log_color_highlight(),
+        ),
+    );
+
+    Ok(())
+}
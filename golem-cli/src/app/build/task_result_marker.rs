@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::fs;
-use crate::model::app::{AppComponentName, DependentComponent};
+use crate::model::app::{AppComponentName, DependentComponent, PostLinkProperties};
 use crate::model::app_raw;
 use anyhow::{anyhow, Context};
 use itertools::Itertools;
@@ -82,6 +82,28 @@ impl TaskResultMarkerHashInput for LinkRpcMarkerHash<'_> {
     }
 }
 
+pub struct PostLinkMarkerHash<'a> {
+    pub component_name: &'a AppComponentName,
+    pub post_link: &'a PostLinkProperties,
+}
+
+impl TaskResultMarkerHashInput for PostLinkMarkerHash<'_> {
+    fn task_kind() -> &'static str {
+        "PostLinkMarkerHash"
+    }
+
+    fn hash_input(&self) -> anyhow::Result<Vec<u8>> {
+        Ok(format!(
+            "{}#{}#{}#{}",
+            self.component_name,
+            self.post_link.strip_debug_info,
+            self.post_link.strip_custom_sections,
+            self.post_link.validate
+        )
+        .into_bytes())
+    }
+}
+
 pub struct AddMetadataMarkerHash<'a> {
     pub component_name: &'a AppComponentName,
     pub root_package_name: PackageName,
@@ -97,22 +119,33 @@ impl TaskResultMarkerHashInput for AddMetadataMarkerHash<'_> {
     }
 }
 
+pub fn task_hash<T: TaskResultMarkerHashInput>(task: &T) -> anyhow::Result<String> {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(T::task_kind().as_bytes());
+    hasher.update(&task.hash_input()?);
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
 pub struct TaskResultMarker {
     success_marker_file_path: PathBuf,
     failure_marker_file_path: PathBuf,
+    sources_hash_file_path: PathBuf,
+    stdout_log_file_path: PathBuf,
+    stderr_log_file_path: PathBuf,
     success_before: bool,
     failure_before: bool,
+    sources_hash: Option<String>,
 }
 
 static TASK_RESULT_MARKER_SUCCESS_SUFFIX: &str = "-success";
 static TASK_RESULT_MARKER_FAILURE_SUFFIX: &str = "-failure";
+static TASK_RESULT_MARKER_SOURCES_HASH_SUFFIX: &str = "-sources-hash";
+static TASK_RESULT_MARKER_STDOUT_LOG_SUFFIX: &str = "-stdout.log";
+static TASK_RESULT_MARKER_STDERR_LOG_SUFFIX: &str = "-stderr.log";
 
 impl TaskResultMarker {
     pub fn new<T: TaskResultMarkerHashInput>(dir: &Path, task: T) -> anyhow::Result<Self> {
-        let mut hasher = blake3::Hasher::new();
-        hasher.update(T::task_kind().as_bytes());
-        hasher.update(&task.hash_input()?);
-        let hex_hash = hasher.finalize().to_hex().to_string();
+        let hex_hash = task_hash(&task)?;
 
         let success_marker_file_path = dir.join(format!(
             "{}{}",
@@ -122,6 +155,18 @@ impl TaskResultMarker {
             "{}{}",
             &hex_hash, TASK_RESULT_MARKER_FAILURE_SUFFIX
         ));
+        let sources_hash_file_path = dir.join(format!(
+            "{}{}",
+            &hex_hash, TASK_RESULT_MARKER_SOURCES_HASH_SUFFIX
+        ));
+        let stdout_log_file_path = dir.join(format!(
+            "{}{}",
+            &hex_hash, TASK_RESULT_MARKER_STDOUT_LOG_SUFFIX
+        ));
+        let stderr_log_file_path = dir.join(format!(
+            "{}{}",
+            &hex_hash, TASK_RESULT_MARKER_STDERR_LOG_SUFFIX
+        ));
 
         let success_marker_exists = success_marker_file_path.exists();
         let failure_marker_exists = failure_marker_file_path.exists();
@@ -140,13 +185,20 @@ impl TaskResultMarker {
             if failure_marker_exists {
                 fs::remove(&failure_marker_file_path)?
             }
+            if sources_hash_file_path.exists() {
+                fs::remove(&sources_hash_file_path)?
+            }
         }
 
         Ok(Self {
             success_marker_file_path,
             failure_marker_file_path,
+            sources_hash_file_path,
+            stdout_log_file_path,
+            stderr_log_file_path,
             success_before,
             failure_before,
+            sources_hash: None,
         })
     }
 
@@ -154,7 +206,41 @@ impl TaskResultMarker {
         !self.failure_before && self.success_before
     }
 
+    /// Checks the sources hash against the one saved by the previous successful run,
+    /// and remembers it, so it can be saved on success.
+    pub fn is_up_to_date_by_sources_hash(&mut self, sources_hash: String) -> bool {
+        let up_to_date = self.is_up_to_date()
+            && fs::read_to_string(&self.sources_hash_file_path)
+                .ok()
+                .is_some_and(|previous_sources_hash| previous_sources_hash == sources_hash);
+        self.sources_hash = Some(sources_hash);
+        up_to_date
+    }
+
+    pub fn stderr_log_file_path(&self) -> &Path {
+        &self.stderr_log_file_path
+    }
+
+    /// Saves the captured output of the last run of the task, next to the result markers
+    pub fn save_output(&self, stdout: &[String], stderr: &[String]) -> anyhow::Result<()> {
+        fn lines_to_string(lines: &[String]) -> String {
+            lines.iter().map(|line| format!("{}\n", line)).collect()
+        }
+
+        fs::write_str(&self.stdout_log_file_path, lines_to_string(stdout))?;
+        fs::write_str(&self.stderr_log_file_path, lines_to_string(stderr))
+    }
+
     pub fn success(&self) -> anyhow::Result<()> {
+        match &self.sources_hash {
+            Some(sources_hash) => fs::write_str(&self.sources_hash_file_path, sources_hash)?,
+            None => {
+                // The task was run without hashing the sources, so an older hash would be stale
+                if self.sources_hash_file_path.exists() {
+                    fs::remove(&self.sources_hash_file_path)?
+                }
+            }
+        }
         fs::write_str(&self.success_marker_file_path, "")
     }
 
@@ -180,3 +266,75 @@ impl TaskResultMarker {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::app::build::task_result_marker::{ComponentGeneratorMarkerHash, TaskResultMarker};
+    use crate::model::app::AppComponentName;
+    use assert2::check;
+    use std::path::Path;
+    use test_r::test;
+
+    fn task_result_marker(dir: &Path) -> TaskResultMarker {
+        TaskResultMarker::new(
+            dir,
+            ComponentGeneratorMarkerHash {
+                component_name: &AppComponentName::from("app:component"),
+                generator_kind: "test",
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn sources_hash_is_saved_on_success() {
+        let marker_dir = tempfile::tempdir().unwrap();
+
+        let mut marker = task_result_marker(marker_dir.path());
+        check!(!marker.is_up_to_date_by_sources_hash("hash-1".to_string()));
+        marker.success().unwrap();
+
+        let mut marker = task_result_marker(marker_dir.path());
+        check!(marker.is_up_to_date_by_sources_hash("hash-1".to_string()));
+        marker.success().unwrap();
+
+        let mut marker = task_result_marker(marker_dir.path());
+        check!(!marker.is_up_to_date_by_sources_hash("hash-2".to_string()));
+        marker.success().unwrap();
+
+        let mut marker = task_result_marker(marker_dir.path());
+        check!(marker.is_up_to_date_by_sources_hash("hash-2".to_string()));
+    }
+
+    #[test]
+    fn sources_hash_is_not_up_to_date_after_failure() {
+        let marker_dir = tempfile::tempdir().unwrap();
+
+        let mut marker = task_result_marker(marker_dir.path());
+        check!(!marker.is_up_to_date_by_sources_hash("hash".to_string()));
+        marker.success().unwrap();
+
+        let mut marker = task_result_marker(marker_dir.path());
+        check!(marker.is_up_to_date_by_sources_hash("hash".to_string()));
+        marker.failure().unwrap();
+
+        let mut marker = task_result_marker(marker_dir.path());
+        check!(!marker.is_up_to_date_by_sources_hash("hash".to_string()));
+    }
+
+    #[test]
+    fn sources_hash_is_removed_when_not_hashed_on_success() {
+        let marker_dir = tempfile::tempdir().unwrap();
+
+        let mut marker = task_result_marker(marker_dir.path());
+        check!(!marker.is_up_to_date_by_sources_hash("hash".to_string()));
+        marker.success().unwrap();
+
+        let marker = task_result_marker(marker_dir.path());
+        check!(marker.is_up_to_date());
+        marker.success().unwrap();
+
+        let mut marker = task_result_marker(marker_dir.path());
+        check!(!marker.is_up_to_date_by_sources_hash("hash".to_string()));
+    }
+}
@@ -13,15 +13,19 @@
 // limitations under the License.
 
 use crate::app::build::build_app;
+use crate::app::build::cache::BuildCache;
 use crate::app::build::clean::clean_app;
+use crate::app::build::event::{BuildEvent, BuildEvents};
 use crate::app::build::external_command::execute_custom_command;
 use crate::app::error::{format_warns, AppValidationError, CustomCommandError};
+use crate::app::graph::AppGraph;
+use crate::app::remote_include::resolve_includes;
 use crate::fs::{compile_and_collect_globs, PathExtra};
 use crate::log::{log_action, log_warn_action, logln, LogColorize, LogIndent};
 use crate::model::app::{
-    includes_from_yaml_file, AppComponentName, Application, ApplicationComponentSelectMode,
-    ApplicationConfig, ApplicationSourceMode, BuildProfileName, ComponentStubInterfaces,
-    DynamicHelpSections, DEFAULT_CONFIG_FILE_NAME,
+    includes_from_yaml_file, temp_dir_from_yaml_file, AppComponentName, Application,
+    ApplicationComponentSelectMode, ApplicationConfig, ApplicationSourceMode, BuildProfileName,
+    ComponentStubInterfaces, DynamicHelpSections, DEFAULT_CONFIG_FILE_NAME,
 };
 use crate::model::app_raw;
 use crate::validation::{ValidatedResult, ValidationBuilder};
@@ -42,31 +46,43 @@ pub struct ApplicationContext {
     pub application: Application,
     pub wit: ResolvedWitApplication,
     pub calling_working_dir: PathBuf,
+    /// Directory of the root application manifest
+    pub app_root_dir: PathBuf,
     component_stub_defs: HashMap<AppComponentName, StubDefinition>,
     common_wit_deps: OnceLock<anyhow::Result<WitDepsResolver>>,
     component_generated_base_wit_deps: HashMap<AppComponentName, WitDepsResolver>,
     selected_component_names: BTreeSet<AppComponentName>,
+    build_cache: Option<BuildCache>,
+    build_events: Option<BuildEvents>,
 }
 
 impl ApplicationContext {
     pub fn new(config: ApplicationConfig) -> anyhow::Result<Option<ApplicationContext>> {
-        let Some(app_and_calling_working_dir) = load_app(&config) else {
+        let Some(app_and_dirs) = load_app(&config) else {
             return Ok(None);
         };
 
+        let build_cache = config.build_cache.as_ref().map(BuildCache::new);
+        let build_events = config
+            .build_events
+            .then(|| BuildEvents::new(config.profile.clone()));
+
         let ctx = to_anyhow(
             "Failed to create application context, see problems above",
-            app_and_calling_working_dir.and_then(|(application, calling_working_dir)| {
+            app_and_dirs.and_then(|(application, dirs)| {
                 ResolvedWitApplication::new(&application, config.profile.as_ref()).map(|wit| {
                     ApplicationContext {
                         config,
                         application,
                         wit,
-                        calling_working_dir,
+                        calling_working_dir: dirs.calling_working_dir,
+                        app_root_dir: dirs.app_root_dir,
                         component_stub_defs: HashMap::new(),
                         common_wit_deps: OnceLock::new(),
                         component_generated_base_wit_deps: HashMap::new(),
                         selected_component_names: BTreeSet::new(),
+                        build_cache,
+                        build_events,
                     }
                 })
             }),
@@ -250,6 +266,25 @@ impl ApplicationContext {
         Ok(())
     }
 
+    pub fn build_cache(&self) -> Option<&BuildCache> {
+        self.build_cache.as_ref()
+    }
+
+    pub fn build_events(&self) -> Option<&BuildEvents> {
+        self.build_events.as_ref()
+    }
+
+    /// Emits a build event if build events are enabled, the event is only created when needed
+    pub fn emit_build_event<F: FnOnce() -> BuildEvent>(
+        &self,
+        component_name: Option<&AppComponentName>,
+        event: F,
+    ) {
+        if let Some(build_events) = &self.build_events {
+            build_events.emit(component_name, &event());
+        }
+    }
+
     pub fn profile(&self) -> Option<&BuildProfileName> {
         self.config.profile.as_ref()
     }
@@ -653,27 +688,66 @@ impl ApplicationContext {
     }
 }
 
-fn load_app(config: &ApplicationConfig) -> Option<ValidatedResult<(Application, PathBuf)>> {
-    let result =
-        collect_sources(&config.app_source_mode)?.and_then(|(sources, calling_working_dir)| {
-            sources
-                .into_iter()
-                .map(|source| {
-                    ValidatedResult::from_result(app_raw::ApplicationWithSource::from_yaml_file(
-                        source,
-                    ))
-                })
-                .collect::<ValidatedResult<Vec<_>>>()
-                .and_then(Application::from_raw_apps)
-                .map(|app| (app, calling_working_dir))
+/// Loads the component dependency graph directly from the manifests, without resolving and
+/// validating the application, so invalid dependencies can also be shown.
+pub fn load_app_graph(app_source_mode: &ApplicationSourceMode) -> anyhow::Result<Option<AppGraph>> {
+    let Some(raw_apps) = load_raw_apps(app_source_mode) else {
+        return Ok(None);
+    };
+
+    to_anyhow(
+        "Failed to load application manifests, see problems above",
+        raw_apps.map(|(apps, _)| AppGraph::from_raw_apps(&apps)),
+    )
+    .map(Some)
+}
+
+/// Loads and validates the application manifests and the WIT of the components without creating
+/// the application context, so all the warnings and errors can be reported by the caller.
+pub fn validate_app(
+    app_source_mode: &ApplicationSourceMode,
+    profile: Option<&BuildProfileName>,
+) -> Option<ValidatedResult<Application>> {
+    let result = load_raw_apps(app_source_mode)?
+        .and_then(|(apps, _)| Application::from_raw_apps(apps))
+        .and_then(|application| {
+            ResolvedWitApplication::new(&application, profile).map(|_| application)
         });
 
     Some(result)
 }
 
+fn load_app(config: &ApplicationConfig) -> Option<ValidatedResult<(Application, AppDirs)>> {
+    let result = load_raw_apps(&config.app_source_mode)?
+        .and_then(|(apps, dirs)| Application::from_raw_apps(apps).map(|app| (app, dirs)));
+
+    Some(result)
+}
+
+fn load_raw_apps(
+    app_source_mode: &ApplicationSourceMode,
+) -> Option<ValidatedResult<(Vec<app_raw::ApplicationWithSource>, AppDirs)>> {
+    let result = collect_sources(app_source_mode)?.and_then(|(sources, dirs)| {
+        sources
+            .into_iter()
+            .map(|source| {
+                ValidatedResult::from_result(app_raw::ApplicationWithSource::from_yaml_file(source))
+            })
+            .collect::<ValidatedResult<Vec<_>>>()
+            .map(|apps| (apps, dirs))
+    });
+
+    Some(result)
+}
+
+struct AppDirs {
+    calling_working_dir: PathBuf,
+    app_root_dir: PathBuf,
+}
+
 fn collect_sources(
     mode: &ApplicationSourceMode,
-) -> Option<ValidatedResult<(BTreeSet<PathBuf>, PathBuf)>> {
+) -> Option<ValidatedResult<(BTreeSet<PathBuf>, AppDirs)>> {
     let calling_working_dir = std::env::current_dir()
         .expect("Failed to get current working directory")
         .canonicalize()
@@ -682,24 +756,38 @@ fn collect_sources(
     log_action("Collecting", "application manifests");
     let _indent = LogIndent::new();
 
-    fn collect_by_main_source(source: &Path) -> Option<ValidatedResult<BTreeSet<PathBuf>>> {
+    fn collect_by_main_source(
+        source: &Path,
+    ) -> Option<ValidatedResult<(BTreeSet<PathBuf>, PathBuf)>> {
         let source_ext = PathExtra::new(&source);
         let source_dir = source_ext.parent().unwrap();
         std::env::set_current_dir(source_dir).expect("Failed to set current dir for config parent");
 
         let includes = includes_from_yaml_file(source);
-        if includes.is_empty() {
+        let sources = if includes.is_empty() {
             Some(ValidatedResult::Ok(BTreeSet::from([source.to_path_buf()])))
         } else {
             Some(
-                ValidatedResult::from_result(compile_and_collect_globs(source_dir, &includes)).map(
-                    |mut sources| {
-                        sources.insert(0, source.to_path_buf());
-                        sources.into_iter().collect()
-                    },
-                ),
+                ValidatedResult::from_result(
+                    resolve_includes(source, &temp_dir_from_yaml_file(source), &includes).and_then(
+                        |(local_includes, remote_sources)| {
+                            compile_and_collect_globs(source_dir, &local_includes).map(
+                                |mut sources| {
+                                    sources.extend(remote_sources);
+                                    sources
+                                },
+                            )
+                        },
+                    ),
+                )
+                .map(|mut sources| {
+                    sources.insert(0, source.to_path_buf());
+                    sources.into_iter().collect()
+                }),
             )
-        }
+        };
+
+        sources.map(|sources| sources.map(|sources| (sources, source_dir.to_path_buf())))
     }
 
     let sources = match mode {
@@ -720,7 +808,7 @@ fn collect_sources(
 
     sources.map(|sources| {
         sources
-            .inspect(|sources| {
+            .inspect(|(sources, _)| {
                 if sources.is_empty() {
                     log_action("Found", "no sources");
                 } else {
@@ -736,7 +824,15 @@ fn collect_sources(
                     );
                 }
             })
-            .map(|sources| (sources, calling_working_dir))
+            .map(|(sources, app_root_dir)| {
+                (
+                    sources,
+                    AppDirs {
+                        calling_working_dir,
+                        app_root_dir,
+                    },
+                )
+            })
     })
 }
 
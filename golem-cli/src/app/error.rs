@@ -12,9 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::log::LogColorize;
+use crate::model::app::AppComponentName;
 use colored::Colorize;
 use itertools::Itertools;
 use std::fmt::{Display, Write};
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub struct AppValidationError {
@@ -61,3 +65,65 @@ pub enum CustomCommandError {
     CommandNotFound,
     CommandError { error: anyhow::Error },
 }
+
+/// Failed external command, with the details needed for finding the problem in CI logs
+#[derive(Debug, Clone)]
+pub struct ExternalCommandError {
+    pub exit_code: Option<i32>,
+    /// Only set if the command was killed because of the timeout
+    pub timeout: Option<Duration>,
+    pub attempts: u32,
+    pub component_name: Option<AppComponentName>,
+    pub step_index: usize,
+    pub command: String,
+    pub dir: PathBuf,
+    pub stderr_tail: Vec<String>,
+    pub stderr_log: PathBuf,
+}
+
+impl Display for ExternalCommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.timeout {
+            Some(timeout) => writeln!(
+                f,
+                "Command timed out after {}",
+                format!("{:?}", timeout).log_color_error_highlight()
+            )?,
+            None => writeln!(
+                f,
+                "Command failed with exit code: {}",
+                self.exit_code
+                    .map(|code| code.to_string().log_color_error_highlight().to_string())
+                    .unwrap_or_else(|| "?".to_string())
+            )?,
+        }
+        if let Some(component_name) = &self.component_name {
+            writeln!(
+                f,
+                "  component: {}",
+                component_name.as_str().log_color_highlight()
+            )?;
+        }
+        writeln!(f, "  step:      {}", self.step_index + 1)?;
+        if self.attempts > 1 {
+            writeln!(f, "  attempts:  {}", self.attempts)?;
+        }
+        writeln!(f, "  command:   {}", self.command.log_color_highlight())?;
+        writeln!(f, "  directory: {}", self.dir.log_color_highlight())?;
+        write!(f, "  stderr:    {}", self.stderr_log.log_color_highlight())?;
+        if !self.stderr_tail.is_empty() {
+            write!(
+                f,
+                "\n  last {} lines of stderr:\n{}",
+                self.stderr_tail.len(),
+                self.stderr_tail
+                    .iter()
+                    .map(|line| format!("    {}", line))
+                    .join("\n")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ExternalCommandError {}
@@ -0,0 +1,357 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::logln;
+use crate::model::app::DependencyType;
+use crate::model::app_raw;
+use crate::model::text::fmt::TextView;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Component dependency graph of the application, built directly from the raw manifests,
+/// so dependencies on missing components and dependency cycles can be shown too.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppGraph {
+    pub components: Vec<String>,
+    pub missing_components: Vec<String>,
+    pub dependencies: Vec<AppGraphDependency>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppGraphDependency {
+    pub source: String,
+    pub target: String,
+    #[serde(rename = "type")]
+    pub dep_type: String,
+    pub missing_target: bool,
+    pub cycle: bool,
+}
+
+impl AppGraph {
+    pub fn from_raw_apps(apps: &[app_raw::ApplicationWithSource]) -> Self {
+        let components = apps
+            .iter()
+            .flat_map(|app| app.application.components.keys().cloned())
+            .collect::<BTreeSet<_>>();
+
+        let edges = apps
+            .iter()
+            .flat_map(|app| {
+                app.application
+                    .dependencies
+                    .iter()
+                    .flat_map(|(source, dependencies)| {
+                        dependencies.iter().filter_map(|dependency| {
+                            dependency.target.as_ref().map(|target| {
+                                (
+                                    source.clone(),
+                                    target.clone(),
+                                    DependencyType::from_str(&dependency.type_)
+                                        .map(|dep_type| dep_type.as_str().to_string())
+                                        .unwrap_or_else(|_| dependency.type_.clone()),
+                                )
+                            })
+                        })
+                    })
+            })
+            .collect::<BTreeSet<_>>();
+
+        let missing_components = edges
+            .iter()
+            .flat_map(|(source, target, _)| [source, target])
+            .filter(|name| !components.contains(*name))
+            .cloned()
+            .collect::<BTreeSet<_>>();
+
+        let cycle_components = cycle_components(&edges);
+
+        let dependencies = edges
+            .into_iter()
+            .map(|(source, target, dep_type)| {
+                let cycle = source == target
+                    || cycle_components
+                        .get(&source)
+                        .is_some_and(|group| cycle_components.get(&target) == Some(group));
+                AppGraphDependency {
+                    missing_target: !components.contains(&target),
+                    source,
+                    target,
+                    dep_type,
+                    cycle,
+                }
+            })
+            .collect();
+
+        Self {
+            components: components.into_iter().collect(),
+            missing_components: missing_components.into_iter().collect(),
+            dependencies,
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        writeln!(dot, "digraph app {{").unwrap();
+        writeln!(dot, "  rankdir=LR;").unwrap();
+        writeln!(dot, "  node [shape=box];").unwrap();
+        for component in &self.components {
+            writeln!(dot, "  {};", dot_id(component)).unwrap();
+        }
+        for component in &self.missing_components {
+            writeln!(
+                dot,
+                "  {} [color=red, fontcolor=red, style=dashed, label={}];",
+                dot_id(component),
+                dot_id(&format!("{} (missing)", component))
+            )
+            .unwrap();
+        }
+        for dependency in &self.dependencies {
+            let highlight = if dependency.cycle || dependency.missing_target {
+                ", color=red, fontcolor=red"
+            } else {
+                ""
+            };
+            writeln!(
+                dot,
+                "  {} -> {} [label={}{}];",
+                dot_id(&dependency.source),
+                dot_id(&dependency.target),
+                dot_id(&dependency.dep_type),
+                highlight
+            )
+            .unwrap();
+        }
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        let node_ids = self
+            .components
+            .iter()
+            .chain(self.missing_components.iter())
+            .enumerate()
+            .map(|(idx, component)| (component.as_str(), format!("n{}", idx)))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut mermaid = String::new();
+        writeln!(mermaid, "flowchart LR").unwrap();
+        for component in &self.components {
+            writeln!(
+                mermaid,
+                "  {}[\"{}\"]",
+                node_ids[component.as_str()],
+                mermaid_label(component)
+            )
+            .unwrap();
+        }
+        for component in &self.missing_components {
+            writeln!(
+                mermaid,
+                "  {}[\"{} (missing)\"]:::missing",
+                node_ids[component.as_str()],
+                mermaid_label(component)
+            )
+            .unwrap();
+        }
+        let mut highlighted_links = Vec::new();
+        for (idx, dependency) in self.dependencies.iter().enumerate() {
+            writeln!(
+                mermaid,
+                "  {} -->|{}| {}",
+                node_ids[dependency.source.as_str()],
+                mermaid_label(&dependency.dep_type),
+                node_ids[dependency.target.as_str()]
+            )
+            .unwrap();
+            if dependency.cycle || dependency.missing_target {
+                highlighted_links.push(idx.to_string());
+            }
+        }
+        if !self.missing_components.is_empty() {
+            writeln!(
+                mermaid,
+                "  classDef missing stroke:#f00,color:#f00,stroke-dasharray:5 5"
+            )
+            .unwrap();
+        }
+        if !highlighted_links.is_empty() {
+            writeln!(
+                mermaid,
+                "  linkStyle {} stroke:#f00,color:#f00",
+                highlighted_links.join(",")
+            )
+            .unwrap();
+        }
+        mermaid
+    }
+}
+
+impl TextView for AppGraph {
+    fn log(&self) {
+        logln(self.to_dot());
+    }
+}
+
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn mermaid_label(name: &str) -> String {
+    name.replace('"', "#quot;")
+}
+
+/// Returns the components which are part of a dependency cycle, mapped to the id of their
+/// strongly connected component.
+fn cycle_components(edges: &BTreeSet<(String, String, String)>) -> BTreeMap<String, usize> {
+    let mut successors = BTreeMap::<&str, BTreeSet<&str>>::new();
+    for (source, target, _) in edges {
+        successors.entry(source).or_default().insert(target);
+        successors.entry(target).or_default();
+    }
+
+    // Tarjan's strongly connected components algorithm
+    struct State<'a> {
+        successors: &'a BTreeMap<&'a str, BTreeSet<&'a str>>,
+        next_index: usize,
+        indices: BTreeMap<&'a str, usize>,
+        low_links: BTreeMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        on_stack: BTreeSet<&'a str>,
+        groups: Vec<Vec<&'a str>>,
+    }
+
+    impl<'a> State<'a> {
+        fn visit(&mut self, node: &'a str) {
+            self.indices.insert(node, self.next_index);
+            self.low_links.insert(node, self.next_index);
+            self.next_index += 1;
+            self.stack.push(node);
+            self.on_stack.insert(node);
+
+            let successors = self.successors;
+            for &successor in &successors[node] {
+                if !self.indices.contains_key(successor) {
+                    self.visit(successor);
+                    let low_link = self.low_links[node].min(self.low_links[successor]);
+                    self.low_links.insert(node, low_link);
+                } else if self.on_stack.contains(successor) {
+                    let low_link = self.low_links[node].min(self.indices[successor]);
+                    self.low_links.insert(node, low_link);
+                }
+            }
+
+            if self.low_links[node] == self.indices[node] {
+                let mut group = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(member);
+                    group.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                self.groups.push(group);
+            }
+        }
+    }
+
+    let mut state = State {
+        successors: &successors,
+        next_index: 0,
+        indices: BTreeMap::new(),
+        low_links: BTreeMap::new(),
+        stack: Vec::new(),
+        on_stack: BTreeSet::new(),
+        groups: Vec::new(),
+    };
+    for &node in successors.keys() {
+        if !state.indices.contains_key(node) {
+            state.visit(node);
+        }
+    }
+
+    state
+        .groups
+        .into_iter()
+        .filter(|group| group.len() > 1)
+        .enumerate()
+        .flat_map(|(idx, group)| group.into_iter().map(move |node| (node.to_string(), idx)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::graph::{AppGraph, AppGraphDependency};
+    use crate::model::app_raw;
+    use assert2::check;
+    use indoc::indoc;
+    use std::path::PathBuf;
+    use test_r::test;
+
+    #[test]
+    fn graph_highlights_cycles_and_missing_targets() {
+        let app = app_raw::ApplicationWithSource::from_yaml_string(
+            PathBuf::from("golem.yaml"),
+            indoc! {"
+                components:
+                  app:a: {}
+                  app:b: {}
+                  app:c: {}
+                dependencies:
+                  app:a:
+                    - type: wasm-rpc
+                      target: app:b
+                  app:b:
+                    - type: static-wasm-rpc
+                      target: app:a
+                    - type: wasm
+                      target: app:c
+                  app:c:
+                    - type: wasm-rpc
+                      target: app:d
+            "}
+            .to_string(),
+        )
+        .unwrap();
+
+        let graph = AppGraph::from_raw_apps(&[app]);
+
+        let dependency = |source: &str, target: &str, dep_type: &str, missing_target, cycle| {
+            AppGraphDependency {
+                source: source.to_string(),
+                target: target.to_string(),
+                dep_type: dep_type.to_string(),
+                missing_target,
+                cycle,
+            }
+        };
+
+        check!(graph.components == vec!["app:a", "app:b", "app:c"]);
+        check!(graph.missing_components == vec!["app:d"]);
+        check!(
+            graph.dependencies
+                == vec![
+                    dependency("app:a", "app:b", "wasm-rpc", false, true),
+                    dependency("app:b", "app:a", "static-wasm-rpc", false, true),
+                    dependency("app:b", "app:c", "wasm", false, false),
+                    dependency("app:c", "app:d", "wasm-rpc", true, false),
+                ]
+        );
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::anyhow;
+use std::sync::{mpsc, OnceLock};
+
+type BlockingClientJob =
+    Box<dyn FnOnce(Result<&reqwest::blocking::Client, &reqwest::Error>) + Send>;
+
+/// Blocking HTTP client owned by a dedicated thread, so the same client (and its connection
+/// pool) can be reused for all the requests, regardless of the calling thread. Builds and
+/// manifest loading are executed both on runtime worker threads and on plain threads (for
+/// parallel component builds), so the client is never created or dropped on the calling
+/// thread. The thread is started on first use, and stops when the last sender is dropped.
+pub struct BlockingClientThread {
+    jobs: OnceLock<mpsc::Sender<BlockingClientJob>>,
+}
+
+impl BlockingClientThread {
+    pub fn new() -> Self {
+        Self {
+            jobs: OnceLock::new(),
+        }
+    }
+
+    pub fn run<T, F>(&self, f: F) -> anyhow::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&reqwest::blocking::Client) -> anyhow::Result<T> + Send + 'static,
+    {
+        let (result_tx, result_rx) = mpsc::channel();
+        let job: BlockingClientJob = Box::new(move |client| {
+            let result = client
+                .map_err(|err| anyhow!("Failed to create blocking HTTP client: {}", err))
+                .and_then(f);
+            let _ = result_tx.send(result);
+        });
+
+        self.jobs
+            .get_or_init(|| {
+                let (jobs_tx, jobs_rx) = mpsc::channel::<BlockingClientJob>();
+                std::thread::spawn(move || {
+                    let client = reqwest::blocking::Client::builder().build();
+                    for job in jobs_rx {
+                        job(client.as_ref());
+                    }
+                });
+                jobs_tx
+            })
+            .send(job)
+            .map_err(|_| anyhow!("The blocking HTTP client thread is not running"))?;
+
+        result_rx
+            .recv()
+            .map_err(|_| anyhow!("Unexpected panic while using the blocking HTTP client"))?
+    }
+}
+
+impl Default for BlockingClientThread {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,370 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! In place edits of application manifests. Edits are applied on the lines of the document
+//! (located using the [YamlSourceMap]), so the comments and the formatting of the untouched
+//! parts are kept.
+
+use crate::log::LogColorize;
+use crate::model::app::DependencyType;
+use crate::model::app_raw;
+use crate::model::source_map::YamlSourceMap;
+use anyhow::{anyhow, bail};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Adds a dependency to the `dependencies` section of the manifest
+pub fn add_dependency(
+    source: &Path,
+    yaml: &str,
+    component_name: &str,
+    dependency_type: DependencyType,
+    target_component_name: &str,
+) -> anyhow::Result<String> {
+    let app = app_raw::Application::from_yaml_str(yaml)?;
+    let source_map = YamlSourceMap::new(source.to_path_buf(), yaml);
+    let mut lines = yaml
+        .lines()
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>();
+
+    let dependency_lines = |indent: usize| {
+        let indent = " ".repeat(indent);
+        [
+            format!("{}- target: {}", indent, target_component_name),
+            format!("{}  type: {}", indent, dependency_type.as_str()),
+        ]
+    };
+
+    match app.dependencies.get(component_name) {
+        Some(dependencies) => {
+            if dependencies.iter().any(|dependency| {
+                dependency.target.as_deref() == Some(target_component_name)
+                    && DependencyType::from_str(&dependency.type_) == Ok(dependency_type)
+            }) {
+                bail!(
+                    "{} {} - {} is already defined in {}",
+                    dependency_type.describe(),
+                    component_name.log_color_highlight(),
+                    target_component_name.log_color_highlight(),
+                    source.log_color_highlight()
+                );
+            }
+
+            let last_index = dependencies.len().saturating_sub(1).to_string();
+            let (line_idx, dash_indent) = sequence_element_line(
+                source,
+                &source_map,
+                &lines,
+                &["dependencies", component_name, last_index.as_str()],
+            )?;
+            let insert_at = block_end(&lines, line_idx, dash_indent, false);
+            lines.splice(insert_at..insert_at, dependency_lines(dash_indent));
+        }
+        None => match source_map.key_span(&["dependencies"]) {
+            Some(dependencies_span) => {
+                let line_idx = dependencies_span.line - 1;
+                let indent = dependencies_span.column - 1;
+                let child_indent = match source_map.value_span(&["dependencies"]) {
+                    Some(value_span) if value_span.line > dependencies_span.line => {
+                        value_span.column - 1
+                    }
+                    _ => return Err(flow_style_error(source, "dependencies")),
+                };
+                let insert_at = block_end(&lines, line_idx, indent, true);
+                lines.splice(
+                    insert_at..insert_at,
+                    std::iter::once(format!("{}{}:", " ".repeat(child_indent), component_name))
+                        .chain(dependency_lines(child_indent)),
+                );
+            }
+            None => {
+                lines.push("dependencies:".to_string());
+                lines.push(format!("  {}:", component_name));
+                lines.extend(dependency_lines(2));
+            }
+        },
+    }
+
+    Ok(join_lines(lines, line_ending(yaml)))
+}
+
+/// Removes a dependency from the `dependencies` section of the manifest, returns None if the
+/// manifest does not define it. If the dependency type is not specified, then the first
+/// dependency with the target is removed.
+pub fn remove_dependency(
+    source: &Path,
+    yaml: &str,
+    component_name: &str,
+    dependency_type: Option<DependencyType>,
+    target_component_name: &str,
+) -> anyhow::Result<Option<String>> {
+    let app = app_raw::Application::from_yaml_str(yaml)?;
+    let Some(dependencies) = app.dependencies.get(component_name) else {
+        return Ok(None);
+    };
+    let Some(index) = dependencies.iter().position(|dependency| {
+        dependency.target.as_deref() == Some(target_component_name)
+            && dependency_type.is_none_or(|dependency_type| {
+                DependencyType::from_str(&dependency.type_) == Ok(dependency_type)
+            })
+    }) else {
+        return Ok(None);
+    };
+
+    let source_map = YamlSourceMap::new(source.to_path_buf(), yaml);
+    let mut lines = yaml
+        .lines()
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>();
+
+    let (line_idx, indent, is_key) = if dependencies.len() > 1 {
+        let index = index.to_string();
+        let (line_idx, indent) = sequence_element_line(
+            source,
+            &source_map,
+            &lines,
+            &["dependencies", component_name, index.as_str()],
+        )?;
+        (line_idx, indent, false)
+    } else {
+        // Removing the whole entry of the component, or the whole section if it was the only one
+        let path = if app.dependencies.len() > 1 {
+            vec!["dependencies", component_name]
+        } else {
+            vec!["dependencies"]
+        };
+        let span = source_map.key_span(&path).ok_or_else(|| {
+            anyhow!(
+                "Failed to locate {} in {}",
+                path.join("."),
+                source.display()
+            )
+        })?;
+        (span.line - 1, span.column - 1, true)
+    };
+
+    let remove_until = block_end(&lines, line_idx, indent, is_key);
+    lines.drain(line_idx..remove_until);
+
+    // Blank lines separating the removed block from its neighbours are not doubled
+    let is_blank = |line: Option<&String>| line.is_none_or(|line| line.trim().is_empty());
+    if line_idx > 0 && is_blank(lines.get(line_idx - 1)) && is_blank(lines.get(line_idx)) {
+        lines.remove(line_idx - 1);
+    }
+
+    Ok(Some(join_lines(lines, line_ending(yaml))))
+}
+
+/// Line index and the indentation of the dash of a block sequence element
+fn sequence_element_line(
+    source: &Path,
+    source_map: &YamlSourceMap,
+    lines: &[String],
+    path: &[&str],
+) -> anyhow::Result<(usize, usize)> {
+    let span = source_map.key_span(path).ok_or_else(|| {
+        anyhow!(
+            "Failed to locate {} in {}",
+            path.join("."),
+            source.display()
+        )
+    })?;
+    let line_idx = span.line - 1;
+    let prefix = lines[line_idx]
+        .chars()
+        .take(span.column - 1)
+        .collect::<String>();
+
+    if prefix.trim() != "-" {
+        return Err(flow_style_error(source, &path[..path.len() - 1].join(".")));
+    }
+
+    Ok((line_idx, prefix.find('-').unwrap()))
+}
+
+fn flow_style_error(source: &Path, path: &str) -> anyhow::Error {
+    anyhow!(
+        "Cannot edit {} in {}, only block style YAML collections are supported, please edit it manually",
+        path.log_color_highlight(),
+        source.log_color_highlight()
+    )
+}
+
+/// Index of the line after the block starting at the given line. The block contains the
+/// following lines which are indented more than the given indentation, trailing blank and
+/// comment lines are not considered part of the block. For mapping keys, sequence elements
+/// on the same indentation (e.g. `deps:\n- target: ...`) are also part of the block.
+fn block_end(lines: &[String], start: usize, indent: usize, is_key: bool) -> usize {
+    let mut end = start + 1;
+    for (idx, line) in lines.iter().enumerate().skip(start + 1) {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let line_indent = line.len() - trimmed.len();
+        if line_indent < indent || (line_indent == indent && !(is_key && trimmed.starts_with('-')))
+        {
+            break;
+        }
+        end = idx + 1;
+    }
+    end
+}
+
+/// Line ending of the document, so edits keep CRLF line endings
+fn line_ending(yaml: &str) -> &'static str {
+    if yaml.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+fn join_lines(lines: Vec<String>, line_ending: &str) -> String {
+    let mut yaml = lines.join(line_ending);
+    yaml.push_str(line_ending);
+    yaml
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::manifest_edit::{add_dependency, remove_dependency};
+    use crate::model::app::DependencyType;
+    use assert2::check;
+    use indoc::indoc;
+    use std::path::Path;
+    use test_r::test;
+
+    #[test]
+    fn add_and_remove_dependencies() {
+        let source = Path::new("golem.yaml");
+        let yaml = indoc! {"
+            # Shared components
+            components:
+              app:a:
+                template: rust
+
+            dependencies:
+              app:a:
+              - target: app:b # the first one
+                type: wasm-rpc
+
+            # Custom commands
+            customCommands:
+              hello:
+              - command: echo hello
+        "};
+
+        let yaml = add_dependency(
+            source,
+            yaml,
+            "app:a",
+            DependencyType::StaticWasmRpc,
+            "app:c",
+        )
+        .unwrap();
+        let yaml = add_dependency(source, &yaml, "app:b", DependencyType::Wasm, "app:c").unwrap();
+        check!(
+            yaml == indoc! {"
+                # Shared components
+                components:
+                  app:a:
+                    template: rust
+
+                dependencies:
+                  app:a:
+                  - target: app:b # the first one
+                    type: wasm-rpc
+                  - target: app:c
+                    type: static-wasm-rpc
+                  app:b:
+                  - target: app:c
+                    type: wasm
+
+                # Custom commands
+                customCommands:
+                  hello:
+                  - command: echo hello
+            "}
+        );
+        check!(add_dependency(source, &yaml, "app:b", DependencyType::Wasm, "app:c").is_err());
+
+        let yaml = remove_dependency(source, &yaml, "app:a", None, "app:b")
+            .unwrap()
+            .unwrap();
+        let yaml = remove_dependency(source, &yaml, "app:b", None, "app:c")
+            .unwrap()
+            .unwrap();
+        check!(remove_dependency(source, &yaml, "app:b", None, "app:c")
+            .unwrap()
+            .is_none());
+        let yaml = remove_dependency(
+            source,
+            &yaml,
+            "app:a",
+            Some(DependencyType::StaticWasmRpc),
+            "app:c",
+        )
+        .unwrap()
+        .unwrap();
+        check!(
+            yaml == indoc! {"
+                # Shared components
+                components:
+                  app:a:
+                    template: rust
+
+                # Custom commands
+                customCommands:
+                  hello:
+                  - command: echo hello
+            "}
+        );
+
+        let yaml = add_dependency(
+            source,
+            &yaml,
+            "app:a",
+            DependencyType::DynamicWasmRpc,
+            "app:b",
+        )
+        .unwrap();
+        check!(yaml.ends_with(indoc! {"
+            - command: echo hello
+            dependencies:
+              app:a:
+              - target: app:b
+                type: wasm-rpc
+        "}));
+    }
+
+    #[test]
+    fn crlf_line_endings_are_kept() {
+        let source = Path::new("golem.yaml");
+        let yaml = "components:\r\n  app:a:\r\n    template: rust\r\n\r\ndependencies:\r\n  app:a:\r\n  - target: app:b\r\n    type: wasm-rpc\r\n";
+
+        let yaml = add_dependency(source, yaml, "app:a", DependencyType::Wasm, "app:c").unwrap();
+        check!(
+            yaml == "components:\r\n  app:a:\r\n    template: rust\r\n\r\ndependencies:\r\n  app:a:\r\n  - target: app:b\r\n    type: wasm-rpc\r\n  - target: app:c\r\n    type: wasm\r\n"
+        );
+
+        let yaml = remove_dependency(source, &yaml, "app:a", None, "app:b")
+            .unwrap()
+            .unwrap();
+        check!(
+            yaml == "components:\r\n  app:a:\r\n    template: rust\r\n\r\ndependencies:\r\n  app:a:\r\n  - target: app:c\r\n    type: wasm\r\n"
+        );
+    }
+}
@@ -15,3 +15,8 @@
 pub mod build;
 pub mod context;
 pub mod error;
+pub mod graph;
+pub mod http;
+pub mod manifest_edit;
+pub mod remote_include;
+pub mod watch;
@@ -0,0 +1,365 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::http::BlockingClientThread;
+use crate::fs;
+use crate::log::{log_action, LogColorize};
+use anyhow::{anyhow, bail, Context};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use url::Url;
+
+pub const INCLUDES_LOCK_FILE_NAME: &str = "golem.lock.yaml";
+
+/// Manifest include which is not part of the application directory, either an HTTP(S) URL, or
+/// a file in a git repository, using the `git+<repository>#<ref>:<path>` form
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RemoteInclude {
+    Url(Url),
+    Git {
+        repository: String,
+        reference: String,
+        path: String,
+    },
+}
+
+impl RemoteInclude {
+    /// Returns None for local (glob) includes
+    pub fn parse(include: &str) -> Option<anyhow::Result<Self>> {
+        if include.starts_with("http://") || include.starts_with("https://") {
+            Some(
+                Url::parse(include)
+                    .map(RemoteInclude::Url)
+                    .with_context(|| anyhow!("Invalid include URL: {}", include)),
+            )
+        } else {
+            include.strip_prefix("git+").map(|git| {
+                Self::parse_git(git).ok_or_else(|| {
+                    anyhow!(
+                        "Invalid git include: {}, expected {}",
+                        include,
+                        "git+<repository>#<ref>:<path>"
+                    )
+                })
+            })
+        }
+    }
+
+    fn parse_git(git: &str) -> Option<Self> {
+        let (repository, reference_and_path) = git.rsplit_once('#')?;
+        let (reference, path) = reference_and_path.split_once(':')?;
+        (!repository.is_empty() && !reference.is_empty() && !path.is_empty()).then(|| {
+            RemoteInclude::Git {
+                repository: repository.to_string(),
+                reference: reference.to_string(),
+                path: path.to_string(),
+            }
+        })
+    }
+
+    fn fetch(&self, client: &BlockingClientThread) -> anyhow::Result<Vec<u8>> {
+        match self {
+            RemoteInclude::Url(url) => {
+                let url = url.clone();
+                client.run(move |client| {
+                    let response = client.get(url.clone()).send()?;
+                    let status = response.status();
+                    if !status.is_success() {
+                        bail!("GET {} failed with status {}", url, status);
+                    }
+                    Ok(response.bytes()?.to_vec())
+                })
+            }
+            RemoteInclude::Git {
+                repository,
+                reference,
+                path,
+            } => {
+                let temp_dir = tempfile::tempdir()?;
+                let repository_dir = temp_dir.path();
+                git(repository_dir, &["init", "--quiet"])?;
+                git(
+                    repository_dir,
+                    &[
+                        "fetch",
+                        "--quiet",
+                        "--depth",
+                        "1",
+                        repository.as_str(),
+                        reference.as_str(),
+                    ],
+                )?;
+                git(
+                    repository_dir,
+                    &["show", format!("FETCH_HEAD:{}", path).as_str()],
+                )
+            }
+        }
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> anyhow::Result<Vec<u8>> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Failed to execute git")?;
+
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(output.stdout)
+}
+
+/// Content hashes of the remote includes, stored next to the main application manifest
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncludesLock {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub includes: BTreeMap<String, LockedInclude>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedInclude {
+    pub hash: String,
+}
+
+impl IncludesLock {
+    fn load(lock_file: &Path) -> anyhow::Result<Self> {
+        if !lock_file.exists() {
+            return Ok(Self::default());
+        }
+
+        serde_yaml::from_str(&fs::read_to_string(lock_file)?).with_context(|| {
+            anyhow!(
+                "Failed to parse includes lock file {}",
+                lock_file.log_color_highlight()
+            )
+        })
+    }
+}
+
+/// Separates the local include patterns from the remote includes, and returns the vendored
+/// sources of the latter.
+///
+/// Remote includes are vendored into the temp dir, and they are only fetched again if the
+/// vendored source is missing or does not match the hash pinned in the lock file. Fetched
+/// content has to match the pinned hash too, new includes are added to the lock file.
+pub fn resolve_includes(
+    main_source: &Path,
+    temp_dir: &Path,
+    includes: &[String],
+) -> anyhow::Result<(Vec<String>, Vec<PathBuf>)> {
+    let mut local_includes = Vec::<String>::new();
+    let mut remote_includes = Vec::<(&String, RemoteInclude)>::new();
+    for include in includes {
+        match RemoteInclude::parse(include) {
+            Some(remote_include) => remote_includes.push((include, remote_include?)),
+            None => local_includes.push(include.clone()),
+        }
+    }
+
+    if remote_includes.is_empty() {
+        return Ok((local_includes, vec![]));
+    }
+
+    let lock_file = main_source
+        .parent()
+        .expect("Failed to get parent dir of the main source")
+        .join(INCLUDES_LOCK_FILE_NAME);
+    let lock = IncludesLock::load(&lock_file)?;
+    let mut updated_lock = IncludesLock::default();
+
+    let client = BlockingClientThread::new();
+    let mut remote_sources = Vec::<PathBuf>::new();
+    for (include, remote_include) in remote_includes {
+        let vendored_source = temp_dir.join("includes").join(format!(
+            "{}.yaml",
+            blake3::hash(include.as_bytes()).to_hex()
+        ));
+        let locked_hash = lock
+            .includes
+            .get(include)
+            .map(|locked| locked.hash.as_str());
+
+        let vendored_hash = vendored_source
+            .exists()
+            .then(|| fs::read(&vendored_source).map(|content| content_hash(&content)))
+            .transpose()?;
+
+        let hash = match vendored_hash {
+            Some(vendored_hash) if Some(vendored_hash.as_str()) == locked_hash => vendored_hash,
+            _ => {
+                log_action(
+                    "Fetching",
+                    format!("remote include {}", include.log_color_highlight()),
+                );
+                let content = remote_include.fetch(&client).with_context(|| {
+                    anyhow!(
+                        "Failed to fetch remote include {}",
+                        include.log_color_highlight()
+                    )
+                })?;
+                let hash = content_hash(&content);
+                if let Some(locked_hash) = locked_hash {
+                    if locked_hash != hash {
+                        bail!(
+                            "Content of remote include {} does not match the locked hash {}, got {}, remove it from {} to accept the new content",
+                            include.log_color_highlight(),
+                            locked_hash.log_color_highlight(),
+                            hash.log_color_error_highlight(),
+                            lock_file.log_color_highlight()
+                        );
+                    }
+                }
+                fs::write(&vendored_source, content)?;
+                hash
+            }
+        };
+
+        updated_lock
+            .includes
+            .insert(include.clone(), LockedInclude { hash });
+        remote_sources.push(vendored_source);
+    }
+
+    if updated_lock != lock {
+        log_action(
+            "Updating",
+            format!("includes lock file {}", lock_file.log_color_highlight()),
+        );
+        fs::write_str(&lock_file, serde_yaml::to_string(&updated_lock)?)?;
+    }
+
+    Ok((local_includes, remote_sources))
+}
+
+fn content_hash(content: &[u8]) -> String {
+    format!("blake3:{}", blake3::hash(content).to_hex())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::remote_include::{resolve_includes, RemoteInclude, INCLUDES_LOCK_FILE_NAME};
+    use crate::fs;
+    use assert2::{check, let_assert};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use test_r::test;
+
+    #[test]
+    fn parse_includes() {
+        check!(RemoteInclude::parse("components-*/golem.yaml").is_none());
+        let_assert!(
+            Some(Ok(RemoteInclude::Url(url))) =
+                RemoteInclude::parse("https://example.com/rust.yaml")
+        );
+        check!(url.as_str() == "https://example.com/rust.yaml");
+        check!(
+            RemoteInclude::parse(
+                "git+https://github.com/org/shared.git#v1.0.0:templates/rust.yaml"
+            )
+            .unwrap()
+            .unwrap()
+                == RemoteInclude::Git {
+                    repository: "https://github.com/org/shared.git".to_string(),
+                    reference: "v1.0.0".to_string(),
+                    path: "templates/rust.yaml".to_string(),
+                }
+        );
+        check!(
+            RemoteInclude::parse("git+https://github.com/org/shared.git")
+                .unwrap()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn vendor_and_lock_url_includes() {
+        let content = Arc::new(Mutex::new(Some("templates: {}\n".to_string())));
+        let base_url = start_server(content.clone());
+        let include = format!("{}templates/rust.yaml", base_url);
+
+        let app_dir = tempfile::tempdir().unwrap();
+        let main_source = app_dir.path().join("golem.yaml");
+        let temp_dir = app_dir.path().join("golem-temp");
+        let lock_file = app_dir.path().join(INCLUDES_LOCK_FILE_NAME);
+        let includes = vec!["components-*/golem.yaml".to_string(), include.clone()];
+
+        let (local_includes, remote_sources) =
+            resolve_includes(&main_source, &temp_dir, &includes).unwrap();
+        check!(local_includes == vec!["components-*/golem.yaml".to_string()]);
+        check!(remote_sources.len() == 1);
+        check!(fs::read_to_string(&remote_sources[0]).unwrap() == "templates: {}\n");
+        let lock = fs::read_to_string(&lock_file).unwrap();
+        check!(lock.contains(&include));
+
+        // Vendored and locked includes are used without fetching them
+        *content.lock().unwrap() = None;
+        let (_, vendored_sources) = resolve_includes(&main_source, &temp_dir, &includes).unwrap();
+        check!(vendored_sources == remote_sources);
+
+        // Changed content is rejected until the lock is removed
+        *content.lock().unwrap() = Some("templates:\n  rust: {}\n".to_string());
+        fs::remove(&remote_sources[0]).unwrap();
+        check!(resolve_includes(&main_source, &temp_dir, &includes).is_err());
+
+        fs::remove(&lock_file).unwrap();
+        let (_, remote_sources) = resolve_includes(&main_source, &temp_dir, &includes).unwrap();
+        check!(fs::read_to_string(&remote_sources[0]).unwrap() == "templates:\n  rust: {}\n");
+        check!(fs::read_to_string(&lock_file).unwrap() != lock);
+    }
+
+    // Minimal HTTP server, responding to all GET requests with the current content, or 404
+    fn start_server(content: Arc<Mutex<Option<String>>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim_end().is_empty() {
+                        break;
+                    }
+                }
+
+                let (status, body) = match content.lock().unwrap().clone() {
+                    Some(content) => ("200 OK", content),
+                    None => ("404 Not Found", String::new()),
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        format!("http://127.0.0.1:{}/", port)
+    }
+}
@@ -0,0 +1,351 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::context::ApplicationContext;
+use crate::fs::compile_and_collect_globs;
+use crate::log::{log_action, LogColorize, LogIndent};
+use crate::model::app::AppComponentName;
+use itertools::Itertools;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// Modification times of the watched sources
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchedSourcesSnapshot {
+    manifests: Snapshot,
+    components: BTreeMap<AppComponentName, Snapshot>,
+}
+
+/// Sources watched for the selected components: the manifests, the sources of the build steps
+/// and the source WIT directories.
+pub struct WatchedSources {
+    manifests: BTreeSet<PathBuf>,
+    components: BTreeMap<AppComponentName, Vec<(PathBuf, Vec<String>)>>,
+    dependents: BTreeMap<AppComponentName, BTreeSet<AppComponentName>>,
+}
+
+impl WatchedSources {
+    pub fn new(ctx: &ApplicationContext) -> Self {
+        let selected_component_names = ctx.selected_component_names();
+
+        let components = selected_component_names
+            .iter()
+            .map(|component_name| {
+                let component_source_dir = ctx.application.component_source_dir(component_name);
+                let properties = ctx
+                    .application
+                    .component_properties(component_name, ctx.profile());
+
+                let mut globs = properties
+                    .build
+                    .iter()
+                    .filter(|step| !step.sources.is_empty())
+                    .map(|step| {
+                        let build_dir = step
+                            .dir
+                            .as_ref()
+                            .map(|dir| component_source_dir.join(dir))
+                            .unwrap_or_else(|| component_source_dir.to_path_buf());
+                        (build_dir, step.sources.clone())
+                    })
+                    .collect::<Vec<_>>();
+                globs.push((
+                    ctx.application
+                        .component_source_wit(component_name, ctx.profile()),
+                    vec!["**/*.wit".to_string()],
+                ));
+
+                (component_name.clone(), globs)
+            })
+            .collect();
+
+        let mut dependents = BTreeMap::<AppComponentName, BTreeSet<AppComponentName>>::new();
+        for component_name in ctx.application.component_names() {
+            for dependency in ctx.application.component_dependencies(component_name) {
+                dependents
+                    .entry(dependency.name.clone())
+                    .or_default()
+                    .insert(component_name.clone());
+            }
+        }
+
+        Self {
+            manifests: ctx.application.sources().clone(),
+            components,
+            dependents,
+        }
+    }
+
+    pub fn snapshot(&self) -> anyhow::Result<WatchedSourcesSnapshot> {
+        Ok(WatchedSourcesSnapshot {
+            manifests: self.manifests_snapshot(),
+            components: self.components_snapshot()?,
+        })
+    }
+
+    /// Waits until some of the watched sources differ from the baseline snapshot, then waits
+    /// until no more changes are observed for the debounce duration. Returns the changed
+    /// components, extended with their (transitive) dependents. Manifest changes affect all the
+    /// watched components.
+    ///
+    /// The baseline should be taken before building, so changes made during the build are
+    /// not lost.
+    pub async fn wait_for_changes(
+        &self,
+        baseline: &WatchedSourcesSnapshot,
+        debounce: Duration,
+    ) -> anyhow::Result<BTreeSet<AppComponentName>> {
+        let mut snapshot = loop {
+            let snapshot = self.snapshot()?;
+            if snapshot != *baseline {
+                break snapshot;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        };
+
+        let mut last_change = tokio::time::Instant::now();
+        while last_change.elapsed() < debounce {
+            tokio::time::sleep(POLL_INTERVAL.min(debounce)).await;
+            let current_snapshot = self.snapshot()?;
+            if current_snapshot != snapshot {
+                snapshot = current_snapshot;
+                last_change = tokio::time::Instant::now();
+            }
+        }
+
+        Ok(self.changed_component_names(baseline, &snapshot))
+    }
+
+    fn changed_component_names(
+        &self,
+        baseline: &WatchedSourcesSnapshot,
+        snapshot: &WatchedSourcesSnapshot,
+    ) -> BTreeSet<AppComponentName> {
+        if snapshot.manifests != baseline.manifests {
+            log_action("Detected", "changes in application manifests");
+            return self.components.keys().cloned().collect();
+        }
+
+        let changed_component_names = self
+            .components
+            .keys()
+            .filter(|component_name| {
+                snapshot.components.get(*component_name) != baseline.components.get(*component_name)
+            })
+            .cloned()
+            .collect::<BTreeSet<_>>();
+
+        log_action(
+            "Detected",
+            format!(
+                "changes in components: {}",
+                changed_component_names
+                    .iter()
+                    .map(|component_name| component_name.as_str().log_color_highlight())
+                    .join(", ")
+            ),
+        );
+
+        self.with_dependents(changed_component_names)
+    }
+
+    fn with_dependents(
+        &self,
+        component_names: BTreeSet<AppComponentName>,
+    ) -> BTreeSet<AppComponentName> {
+        let mut affected = BTreeSet::new();
+        let mut queue = component_names.into_iter().collect::<Vec<_>>();
+        while let Some(component_name) = queue.pop() {
+            if !affected.insert(component_name.clone()) {
+                continue;
+            }
+            if let Some(dependents) = self.dependents.get(&component_name) {
+                queue.extend(dependents.iter().cloned());
+            }
+        }
+
+        let (affected, not_watched): (BTreeSet<_>, Vec<_>) = affected
+            .into_iter()
+            .partition(|component_name| self.components.contains_key(component_name));
+
+        if !not_watched.is_empty() {
+            log_action(
+                "Ignoring",
+                "affected components that are not selected for watching",
+            );
+            let _indent = LogIndent::new();
+            for component_name in not_watched {
+                log_action(
+                    "Ignoring",
+                    format!(
+                        "component {}",
+                        component_name.as_str().log_color_highlight()
+                    ),
+                );
+            }
+        }
+
+        affected
+    }
+
+    fn manifests_snapshot(&self) -> Snapshot {
+        self.manifests
+            .iter()
+            .map(|path| (path.clone(), modified(path)))
+            .collect()
+    }
+
+    fn components_snapshot(&self) -> anyhow::Result<BTreeMap<AppComponentName, Snapshot>> {
+        let mut snapshot = BTreeMap::new();
+        for (component_name, globs) in &self.components {
+            let mut component_snapshot = Snapshot::new();
+            for (dir, globs) in globs {
+                for path in compile_and_collect_globs(dir, globs)? {
+                    let modified = modified(&path);
+                    component_snapshot.insert(path, modified);
+                }
+            }
+            snapshot.insert(component_name.clone(), component_snapshot);
+        }
+
+        Ok(snapshot)
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::watch::WatchedSources;
+    use crate::model::app::AppComponentName;
+    use assert2::check;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::fs::File;
+    use std::path::Path;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+    use test_r::test;
+
+    fn touch(path: &Path, secs: u64) {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        File::create(path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    }
+
+    fn component_names(names: &[&str]) -> BTreeSet<AppComponentName> {
+        names.iter().map(|name| (*name).into()).collect()
+    }
+
+    // Component a depends on b, c is independent, every component has its sources in its own
+    // directory
+    fn watched_sources(dir: &Path) -> WatchedSources {
+        touch(&dir.join("golem.yaml"), 1);
+        for name in ["a", "b", "c"] {
+            touch(&dir.join(name).join("src/lib.rs"), 1);
+        }
+
+        WatchedSources {
+            manifests: BTreeSet::from([dir.join("golem.yaml")]),
+            components: ["a", "b", "c"]
+                .into_iter()
+                .map(|name| {
+                    (
+                        name.into(),
+                        vec![(dir.join(name), vec!["src/**/*.rs".to_string()])],
+                    )
+                })
+                .collect(),
+            dependents: BTreeMap::from([("b".into(), component_names(&["a"]))]),
+        }
+    }
+
+    #[test]
+    fn changed_sources_affect_the_component_and_its_dependents() {
+        let dir = TempDir::new().unwrap();
+        let sources = watched_sources(dir.path());
+        let baseline = sources.snapshot().unwrap();
+
+        check!(sources.snapshot().unwrap() == baseline);
+
+        touch(&dir.path().join("b/src/lib.rs"), 2);
+        let snapshot = sources.snapshot().unwrap();
+        check!(
+            sources.changed_component_names(&baseline, &snapshot) == component_names(&["a", "b"])
+        );
+
+        touch(&dir.path().join("c/src/new.rs"), 1);
+        let snapshot = sources.snapshot().unwrap();
+        check!(
+            sources.changed_component_names(&baseline, &snapshot)
+                == component_names(&["a", "b", "c"])
+        );
+    }
+
+    #[test]
+    fn deleted_sources_affect_the_component() {
+        let dir = TempDir::new().unwrap();
+        let sources = watched_sources(dir.path());
+        let baseline = sources.snapshot().unwrap();
+
+        std::fs::remove_file(dir.path().join("a/src/lib.rs")).unwrap();
+        let snapshot = sources.snapshot().unwrap();
+        check!(sources.changed_component_names(&baseline, &snapshot) == component_names(&["a"]));
+    }
+
+    #[test]
+    fn changed_manifests_affect_all_components() {
+        let dir = TempDir::new().unwrap();
+        let sources = watched_sources(dir.path());
+        let baseline = sources.snapshot().unwrap();
+
+        touch(&dir.path().join("golem.yaml"), 2);
+        let snapshot = sources.snapshot().unwrap();
+        check!(
+            sources.changed_component_names(&baseline, &snapshot)
+                == component_names(&["a", "b", "c"])
+        );
+    }
+
+    #[test]
+    async fn changes_made_after_the_baseline_are_not_lost() {
+        let dir = TempDir::new().unwrap();
+        let sources = watched_sources(dir.path());
+        let baseline = sources.snapshot().unwrap();
+
+        // E.g. a source changed while the build was running
+        touch(&dir.path().join("c/src/lib.rs"), 2);
+
+        let changed_component_names = tokio::time::timeout(
+            Duration::from_secs(10),
+            sources.wait_for_changes(&baseline, Duration::ZERO),
+        )
+        .await
+        .expect("Changes made after the baseline were not detected")
+        .unwrap();
+        check!(changed_component_names == component_names(&["c"]));
+    }
+}
@@ -21,6 +21,7 @@ use crate::command::profile::ProfileSubcommand;
 use crate::command::worker::WorkerSubcommand;
 use crate::config::{BuildProfileName, ProfileName};
 use crate::log::LogColorize;
+use crate::model::app::DependencyType;
 use crate::model::{Format, WorkerName};
 use crate::{command_name, version};
 use anyhow::{anyhow, bail, Context as AnyhowContext};
@@ -30,10 +31,13 @@ use clap::{self, CommandFactory, Subcommand};
 use clap::{Args, Parser};
 use clap_verbosity_flag::{ErrorLevel, LogLevel};
 use golem_client::model::ScanCursor;
+use itertools::Itertools;
 use lenient_bool::LenientBool;
 use std::collections::{BTreeSet, HashMap};
 use std::ffi::OsString;
 use std::path::PathBuf;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
 use uuid::Uuid;
 
 #[cfg(feature = "server-commands")]
@@ -89,7 +93,7 @@ impl Verbosity {
     }
 }
 
-#[derive(Debug, Default, Args)]
+#[derive(Debug, Clone, Default, Args)]
 pub struct GolemCliGlobalFlags {
     /// Output format, defaults to text, unless specified by the selected profile
     #[arg(long, short, global = true, display_order = 101)]
@@ -541,10 +545,11 @@ pub enum GolemCliSubcommand {
 
 pub mod shared_args {
     use crate::cloud::AccountId;
-    use crate::model::app::AppBuildStep;
+    use crate::model::app::{AppBuildStep, BuildCacheLocation, UpToDateCheckMode};
     use crate::model::{ComponentName, ProjectName, WorkerName, WorkerUpdateMode};
     use clap::Args;
     use golem_templates::model::GuestLanguage;
+    use std::num::NonZeroUsize;
 
     pub type ComponentTemplateName = String;
     pub type NewWorkerArgument = String;
@@ -612,7 +617,7 @@ pub mod shared_args {
 
     #[derive(Debug, Args)]
     pub struct ForceBuildArg {
-        /// When set to true will skip modification time based up-to-date checks, defaults to false
+        /// When set to true will skip up-to-date checks, defaults to false
         #[clap(long, default_value = "false")]
         pub force_build: bool,
     }
@@ -624,6 +629,15 @@ pub mod shared_args {
         pub step: Vec<AppBuildStep>,
         #[command(flatten)]
         pub force_build: ForceBuildArg,
+        /// Select how up-to-date checks are done, defaults to "timestamp"
+        #[clap(long, default_value = "timestamp")]
+        pub up_to_date_check: UpToDateCheckMode,
+        /// Maximum number of components to build in parallel, defaults to 1
+        #[clap(long, short, default_value = "1")]
+        pub jobs: NonZeroUsize,
+        /// Shared build cache for restoring and storing build outputs, either a local directory or an HTTP(S) base URL
+        #[clap(long)]
+        pub build_cache: Option<BuildCacheLocation>,
     }
 
     #[derive(Debug, Args)]
@@ -638,6 +652,20 @@ pub mod shared_args {
         pub worker_name: WorkerName,
     }
 
+    #[derive(Debug, Args)]
+    pub struct WorkerBulkArgs {
+        /// Filter for selecting multiple workers when `*` is used as the worker name
+        /// (e.g. `<COMPONENT>/*`), in form of `property op value`.
+        ///
+        /// Filter examples: `name = worker-name`, `version >= 0`, `status = Failed`, `env.var1 = value`.
+        /// Can be used multiple times (AND condition is applied between them)
+        #[arg(long)]
+        pub filter: Vec<String>,
+        /// Maximum number of workers processed in parallel when multiple workers are selected
+        #[arg(long, default_value = "8")]
+        pub concurrency: NonZeroUsize,
+    }
+
     #[derive(Debug, Args)]
     pub struct StreamArgs {
         /// Hide log levels in stream output
@@ -648,6 +676,16 @@ pub mod shared_args {
         pub stream_no_timestamp: bool,
     }
 
+    #[derive(Debug, Args, Default)]
+    pub struct DeployPlanArgs {
+        /// Only show what deploy would change for the components, without uploading them
+        #[clap(long)]
+        pub plan: bool,
+        /// Exit with a non-zero code if the plan contains any changes, useful in CI
+        #[clap(long, requires = "plan")]
+        pub fail_on_changes: bool,
+    }
+
     #[derive(Debug, Args, Default)]
     pub struct WorkerUpdateOrRedeployArgs {
         /// Update existing workers with auto or manual update mode
@@ -657,6 +695,41 @@ pub mod shared_args {
         /// Delete and recreate existing workers
         #[clap(long, short, conflicts_with_all = ["update_workers"])]
         pub redeploy_workers: bool,
+        #[command(flatten)]
+        pub rollout: WorkerRolloutArgs,
+    }
+
+    #[derive(Debug, Clone, Args)]
+    pub struct WorkerRolloutArgs {
+        /// Maximum number of workers updated or redeployed in parallel, defaults to 8
+        #[clap(long, default_value = "8")]
+        pub worker_concurrency: NonZeroUsize,
+        /// Roll out the given number of canary workers first, and only continue with the rest
+        /// of the workers if the canaries became healthy
+        #[clap(long, value_name = "NUMBER_OF_WORKERS")]
+        pub canary: Option<NonZeroUsize>,
+        /// Wait until all the workers are running the target version
+        #[clap(long)]
+        pub wait: bool,
+        /// Maximum time to wait for a worker (canary, or any worker when using --wait) to become
+        /// healthy in seconds, defaults to 60
+        #[clap(long, default_value = "60")]
+        pub wait_timeout: u64,
+        /// Stop the rollout if more than the given percentage of the workers failed to update or redeploy
+        #[clap(long, value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(0..=100))]
+        pub abort_threshold: Option<u8>,
+    }
+
+    impl Default for WorkerRolloutArgs {
+        fn default() -> Self {
+            Self {
+                worker_concurrency: NonZeroUsize::new(8).unwrap(),
+                canary: None,
+                wait: false,
+                wait_timeout: 60,
+                abort_threshold: None,
+            }
+        }
     }
 
     #[derive(Debug, Args)]
@@ -699,11 +772,14 @@ pub mod shared_args {
 
 pub mod app {
     use crate::command::shared_args::{
-        AppOptionalComponentNames, BuildArgs, ForceBuildArg, WorkerUpdateOrRedeployArgs,
+        AppOptionalComponentNames, BuildArgs, DeployPlanArgs, ForceBuildArg, WorkerRolloutArgs,
+        WorkerUpdateOrRedeployArgs,
     };
+    use crate::model::app::{AppGraphFormat, EnvironmentName};
     use crate::model::WorkerUpdateMode;
     use clap::Subcommand;
     use golem_templates::model::GuestLanguage;
+    use std::path::PathBuf;
 
     #[derive(Debug, Subcommand)]
     pub enum AppSubcommand {
@@ -730,6 +806,22 @@ pub mod app {
             force_build: ForceBuildArg,
             #[command(flatten)]
             update_or_redeploy: WorkerUpdateOrRedeployArgs,
+            #[command(flatten)]
+            plan: DeployPlanArgs,
+            /// Deploy to an environment defined in the application manifest, using its CLI profile, project and build profile
+            #[arg(long)]
+            env: Option<EnvironmentName>,
+        },
+        /// Watch all or selected components in the application, rebuild and redeploy them on changes
+        Watch {
+            #[command(flatten)]
+            component_name: AppOptionalComponentNames,
+            /// Try to automatically update existing workers after redeploying changed components
+            #[arg(long)]
+            update_workers: bool,
+            /// Time to wait for further changes before rebuilding in milliseconds, defaults to 500
+            #[arg(long, default_value = "500")]
+            debounce: u64,
         },
         /// Clean all components in the application or by selection
         Clean {
@@ -743,17 +835,35 @@ pub mod app {
             /// Update mode - auto or manual, defaults to "auto"
             #[arg(long, short, default_value = "auto")]
             update_mode: WorkerUpdateMode,
+            #[command(flatten)]
+            rollout: WorkerRolloutArgs,
         },
         /// Redeploy all workers of the application using the latest version
         RedeployWorkers {
             #[command(flatten)]
             component_name: AppOptionalComponentNames,
+            #[command(flatten)]
+            rollout: WorkerRolloutArgs,
         },
         /// Diagnose possible tooling problems
         Diagnose {
             #[command(flatten)]
             component_name: AppOptionalComponentNames,
         },
+        /// Show the component dependency graph of the application, use the global format flag for JSON or YAML output
+        Graph {
+            /// Graph format used for text output, defaults to "dot"
+            #[arg(long, default_value = "dot")]
+            graph_format: AppGraphFormat,
+        },
+        /// Print the JSON schema of the application manifest
+        Schema {
+            /// Write the schema to the given file instead of printing it
+            #[arg(long, short)]
+            output: Option<PathBuf>,
+        },
+        /// Validate the application manifest and the component WIT definitions without building anything
+        Validate,
         /// Run custom command
         #[clap(external_subcommand)]
         CustomCommand(Vec<String>),
@@ -762,11 +872,14 @@ pub mod app {
 
 pub mod component {
     use crate::command::component::plugin::ComponentPluginSubcommand;
+    use crate::command::parse_dependency_type;
     use crate::command::shared_args::{
         BuildArgs, ComponentOptionalComponentName, ComponentOptionalComponentNames,
-        ComponentTemplatePositionalArg, ForceBuildArg, WorkerUpdateOrRedeployArgs,
+        ComponentTemplatePositionalArg, ForceBuildArg, WorkerRolloutArgs,
+        WorkerUpdateOrRedeployArgs,
     };
-    use crate::model::WorkerUpdateMode;
+    use crate::model::app::DependencyType;
+    use crate::model::{ComponentName, WorkerUpdateMode};
     use clap::Subcommand;
     use golem_templates::model::PackageName;
 
@@ -824,11 +937,15 @@ pub mod component {
             /// Update mode - auto or manual, defaults to "auto"
             #[arg(long, short, default_value_t = WorkerUpdateMode::Automatic)]
             update_mode: WorkerUpdateMode,
+            #[command(flatten)]
+            rollout: WorkerRolloutArgs,
         },
         /// Redeploy all workers of the selected component using the latest version
         RedeployWorkers {
             #[command(flatten)]
             component_name: ComponentOptionalComponentName,
+            #[command(flatten)]
+            rollout: WorkerRolloutArgs,
         },
         /// Manage component plugin installations
         Plugin {
@@ -840,6 +957,28 @@ pub mod component {
             #[command(flatten)]
             component_name: ComponentOptionalComponentNames,
         },
+        /// Add a dependency to the selected component in the application manifest
+        AddDependency {
+            #[command(flatten)]
+            component_name: ComponentOptionalComponentName,
+            /// Name of the target component
+            #[arg(long)]
+            target_component_name: ComponentName,
+            /// Type of the dependency: wasm-rpc, static-wasm-rpc or wasm
+            #[arg(long, value_parser = parse_dependency_type, default_value = "wasm-rpc")]
+            dependency_type: DependencyType,
+        },
+        /// Remove a dependency of the selected component from the application manifest
+        RemoveDependency {
+            #[command(flatten)]
+            component_name: ComponentOptionalComponentName,
+            /// Name of the target component
+            #[arg(long)]
+            target_component_name: ComponentName,
+            /// Optional type of the dependency, if not specified, then the first dependency with the target is removed
+            #[arg(long, value_parser = parse_dependency_type)]
+            dependency_type: Option<DependencyType>,
+        },
     }
 
     pub mod plugin {
@@ -906,10 +1045,10 @@ pub mod worker {
     use crate::command::parse_cursor;
     use crate::command::parse_key_val;
     use crate::command::shared_args::{
-        ComponentOptionalComponentName, NewWorkerArgument, StreamArgs, WorkerFunctionArgument,
-        WorkerFunctionName, WorkerNameArg,
+        ComponentOptionalComponentName, NewWorkerArgument, StreamArgs, WorkerBulkArgs,
+        WorkerFunctionArgument, WorkerFunctionName, WorkerNameArg,
     };
-    use crate::model::{IdempotencyKey, WorkerUpdateMode};
+    use crate::model::{IdempotencyKey, PathBufOrStdin, WorkerUpdateMode};
     use clap::Subcommand;
     use golem_client::model::ScanCursor;
 
@@ -925,15 +1064,26 @@ pub mod worker {
             #[arg(short, long, value_parser = parse_key_val, value_name = "ENV=VAL")]
             env: Vec<(String, String)>,
         },
-        // TODO: json args
         /// Invoke (or enqueue invocation for) worker
         Invoke {
             #[command(flatten)]
             worker_name: WorkerNameArg,
             /// Worker function name to invoke
             function_name: WorkerFunctionName,
-            /// Worker function arguments in WAVE format
+            /// Worker function arguments in WAVE format, or in JSON format when using --json-args
+            ///
+            /// Resource handles are passed as the `<worker-uri>/<resource-id>` URIs shown in the
+            /// results of previous invocations on the same worker (e.g. the result of a
+            /// `[constructor]` call, then used as the first argument of `[method]` calls)
             arguments: Vec<WorkerFunctionArgument>,
+            /// Parse the arguments as JSON values instead of WAVE, either as plain JSON values
+            /// or as type annotated ones in the {"typ": ..., "value": ...} form
+            #[clap(long)]
+            json_args: bool,
+            /// Read the arguments from a file as a JSON array (using the same format as
+            /// --json-args), use "-" for reading from stdin
+            #[clap(long, conflicts_with_all = ["arguments", "json_args"])]
+            args_file: Option<PathBufOrStdin>,
             /// Enqueue invocation, and do not wait for it
             #[clap(long, short)]
             enqueue: bool,
@@ -946,6 +1096,8 @@ pub mod worker {
             stream: bool,
             #[command(flatten)]
             stream_args: StreamArgs,
+            #[command(flatten)]
+            bulk_args: WorkerBulkArgs,
         },
         /// Get worker metadata
         Get {
@@ -956,6 +1108,8 @@ pub mod worker {
         Delete {
             #[command(flatten)]
             worker_name: WorkerNameArg,
+            #[command(flatten)]
+            bulk_args: WorkerBulkArgs,
         },
         /// List worker metadata
         List {
@@ -997,16 +1151,26 @@ pub mod worker {
             mode: Option<WorkerUpdateMode>,
             /// The new version of the updated worker (default is the latest version)
             target_version: Option<u64>,
+            /// Wait until the worker is running the target version, and fail if the update failed
+            #[arg(long)]
+            wait: bool,
+            /// Maximum time to wait for the update in seconds, defaults to 60
+            #[arg(long, default_value = "60", requires = "wait")]
+            wait_timeout: u64,
         },
         /// Interrupts a running worker
         Interrupt {
             #[command(flatten)]
             worker_name: WorkerNameArg,
+            #[command(flatten)]
+            bulk_args: WorkerBulkArgs,
         },
         /// Resume an interrupted worker
         Resume {
             #[command(flatten)]
             worker_name: WorkerNameArg,
+            #[command(flatten)]
+            bulk_args: WorkerBulkArgs,
         },
         /// Simulates a crash on a worker for testing purposes.
         ///
@@ -1744,6 +1908,16 @@ fn parse_key_val(key_and_val: &str) -> anyhow::Result<(String, String)> {
     ))
 }
 
+fn parse_dependency_type(dependency_type: &str) -> anyhow::Result<DependencyType> {
+    DependencyType::from_str(dependency_type).map_err(|_| {
+        anyhow!(
+            "invalid dependency type: `{}`, accepted types: {}",
+            dependency_type.log_color_error_highlight(),
+            DependencyType::iter().map(|dt| dt.as_str()).join(", ")
+        )
+    })
+}
+
 // TODO: better error context and messages
 fn parse_cursor(cursor: &str) -> anyhow::Result<ScanCursor> {
     let parts = cursor.split('/').collect::<Vec<_>>();
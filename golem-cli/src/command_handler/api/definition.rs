@@ -17,18 +17,28 @@ use crate::command::shared_args::ProjectNameOptionalArg;
 use crate::command_handler::Handlers;
 use crate::context::{Context, GolemClients};
 use crate::error::service::AnyhowMapServiceError;
-use crate::log::{log_warn_action, LogColorize};
+use crate::log::{log_action, log_skipping_up_to_date, log_warn_action, LogColorize, LogIndent};
+use crate::model::http_api::{
+    bump_version, compare_versions, http_api_definition_matches, http_api_definition_request,
+    DEFAULT_HTTP_API_DEFINITION_VERSION,
+};
 use crate::model::text::api_definition::{
     ApiDefinitionGetView, ApiDefinitionNewView, ApiDefinitionUpdateView,
 };
-use crate::model::{ApiDefinitionId, ApiDefinitionVersion, PathBufOrStdin};
+use crate::model::{
+    ApiDefinitionId, ApiDefinitionIdWithVersion, ApiDefinitionVersion, PathBufOrStdin,
+    ProjectNameAndId,
+};
 use anyhow::Context as AnyhowContext;
 use golem_client::api::ApiDefinitionClient as ApiDefinitionClientOss;
 use golem_client::model::HttpApiDefinitionRequest as HttpApiDefinitionRequestOss;
 use golem_cloud_client::api::ApiDefinitionClient as ApiDefinitionClientCloud;
 use golem_cloud_client::model::HttpApiDefinitionRequest as HttpApiDefinitionRequestCloud;
 use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::cmp::Ordering;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub struct ApiDefinitionCommandHandler {
     ctx: Arc<Context>,
@@ -319,6 +329,214 @@ impl ApiDefinitionCommandHandler {
 
         Ok(())
     }
+
+    /// Creates or updates the HTTP API definitions of the application manifest, returns
+    /// the versions to be used for deployments.
+    ///
+    /// Unchanged definitions are reused, changed draft ones are updated in place, otherwise
+    /// a new version is created by bumping the latest one.
+    pub async fn deploy_app_definitions(
+        &self,
+        project: Option<&ProjectNameAndId>,
+    ) -> anyhow::Result<Vec<ApiDefinitionIdWithVersion>> {
+        let definitions = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            app_ctx
+                .some_or_err()?
+                .application
+                .http_api_definitions()
+                .clone()
+        };
+
+        if definitions.is_empty() {
+            return Ok(vec![]);
+        }
+
+        log_action("Deploying", "HTTP API definitions");
+        let _indent = LogIndent::new();
+
+        let mut deployed = Vec::with_capacity(definitions.len());
+        for (id, definition) in definitions {
+            let existing = self.definition_versions(project, &id).await?;
+            let latest = existing.iter().max_by(|left, right| {
+                compare_versions(
+                    left["version"].as_str().unwrap_or_default(),
+                    right["version"].as_str().unwrap_or_default(),
+                )
+            });
+
+            let version = match latest {
+                None => {
+                    let version = definition
+                        .value
+                        .version
+                        .clone()
+                        .unwrap_or_else(|| DEFAULT_HTTP_API_DEFINITION_VERSION.to_string());
+                    let request = http_api_definition_request(&id, &version, &definition.value);
+                    self.create_definition(project, &id, &version, request)
+                        .await?;
+                    version
+                }
+                Some(latest) => {
+                    let latest_version = latest["version"].as_str().unwrap_or_default();
+                    let request =
+                        http_api_definition_request(&id, latest_version, &definition.value);
+
+                    if http_api_definition_matches(&request, latest) {
+                        log_skipping_up_to_date(format!(
+                            "HTTP API definition {}/{}",
+                            id.log_color_highlight(),
+                            latest_version.log_color_highlight()
+                        ));
+                        latest_version.to_string()
+                    } else if latest["draft"].as_bool().unwrap_or_default() {
+                        self.update_definition(project, &id, latest_version, request)
+                            .await?;
+                        latest_version.to_string()
+                    } else {
+                        let version = match &definition.value.version {
+                            Some(version)
+                                if compare_versions(version, latest_version)
+                                    == Ordering::Greater =>
+                            {
+                                version.clone()
+                            }
+                            _ => bump_version(latest_version),
+                        };
+                        let request = http_api_definition_request(&id, &version, &definition.value);
+                        self.create_definition(project, &id, &version, request)
+                            .await?;
+                        version
+                    }
+                }
+            };
+
+            deployed.push(ApiDefinitionIdWithVersion {
+                id: ApiDefinitionId(id),
+                version: ApiDefinitionVersion(version),
+            });
+        }
+
+        Ok(deployed)
+    }
+
+    async fn definition_versions(
+        &self,
+        project: Option<&ProjectNameAndId>,
+        id: &str,
+    ) -> anyhow::Result<Vec<Value>> {
+        let definitions = match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => clients
+                .api_definition
+                .list_definitions(Some(id))
+                .await
+                .map_service_error_not_found_as_opt()?
+                .unwrap_or_default()
+                .into_iter()
+                .map(serde_json::to_value)
+                .collect::<Result<Vec<_>, _>>()?,
+            GolemClients::Cloud(clients) => clients
+                .api_definition
+                .list_definitions(&self.project_id(project).await?, Some(id))
+                .await
+                .map_service_error_not_found_as_opt()?
+                .unwrap_or_default()
+                .into_iter()
+                .map(serde_json::to_value)
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+
+        Ok(definitions)
+    }
+
+    async fn create_definition(
+        &self,
+        project: Option<&ProjectNameAndId>,
+        id: &str,
+        version: &str,
+        request: Value,
+    ) -> anyhow::Result<()> {
+        log_action(
+            "Creating",
+            format!(
+                "HTTP API definition {}/{}",
+                id.log_color_highlight(),
+                version.log_color_highlight()
+            ),
+        );
+
+        match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => {
+                let request: HttpApiDefinitionRequestOss = serde_json::from_value(request)?;
+                clients
+                    .api_definition
+                    .create_definition_json(&request)
+                    .await
+                    .map_service_error()?;
+            }
+            GolemClients::Cloud(clients) => {
+                let request: HttpApiDefinitionRequestCloud = serde_json::from_value(request)?;
+                clients
+                    .api_definition
+                    .create_definition_json(&self.project_id(project).await?, &request)
+                    .await
+                    .map_service_error()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn update_definition(
+        &self,
+        project: Option<&ProjectNameAndId>,
+        id: &str,
+        version: &str,
+        request: Value,
+    ) -> anyhow::Result<()> {
+        log_action(
+            "Updating",
+            format!(
+                "HTTP API definition {}/{}",
+                id.log_color_highlight(),
+                version.log_color_highlight()
+            ),
+        );
+
+        match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => {
+                let request: HttpApiDefinitionRequestOss = serde_json::from_value(request)?;
+                clients
+                    .api_definition
+                    .update_definition_json(id, version, &request)
+                    .await
+                    .map_service_error()?;
+            }
+            GolemClients::Cloud(clients) => {
+                let request: HttpApiDefinitionRequestCloud = serde_json::from_value(request)?;
+                clients
+                    .api_definition
+                    .update_definition_json(&self.project_id(project).await?, id, version, &request)
+                    .await
+                    .map_service_error()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn project_id(&self, project: Option<&ProjectNameAndId>) -> anyhow::Result<Uuid> {
+        match project {
+            Some(project) => Ok(project.project_id.0),
+            None => Ok(self
+                .ctx
+                .cloud_project_handler()
+                .selected_project_or_default(None)
+                .await?
+                .project_id
+                .0),
+        }
+    }
 }
 
 fn parse_api_definition<T: DeserializeOwned>(input: &str) -> anyhow::Result<T> {
@@ -18,9 +18,9 @@ use crate::command_handler::Handlers;
 use crate::context::{Context, GolemClients};
 use crate::error::service::AnyhowMapServiceError;
 use crate::error::NonSuccessfulExit;
-use crate::log::{log_warn_action, LogColorize};
+use crate::log::{log_action, log_warn_action, LogColorize, LogIndent};
 use crate::model::text::fmt::log_error;
-use crate::model::{ApiDefinitionId, ApiDefinitionIdWithVersion, ApiDeployment};
+use crate::model::{ApiDefinitionId, ApiDefinitionIdWithVersion, ApiDeployment, ProjectNameAndId};
 use anyhow::bail;
 use golem_client::api::ApiDeploymentClient as ApiDeploymentClientOss;
 use golem_client::model::{
@@ -32,6 +32,7 @@ use golem_cloud_client::model::{
     ApiDefinitionInfo as ApiDefinitionInfoCloud, ApiDeploymentRequest as ApiDeploymentRequestCloud,
     ApiSite as ApiSiteCloud,
 };
+use itertools::Itertools;
 use std::sync::Arc;
 
 pub struct ApiDeploymentCommandHandler {
@@ -73,6 +74,82 @@ impl ApiDeploymentCommandHandler {
             .opt_select_project(None /* TODO: account id */, project.project.as_ref())
             .await?;
 
+        let result = self
+            .deploy(project.as_ref(), &api_defs, host, subdomain)
+            .await?;
+
+        self.ctx.log_handler().log_view(&result);
+
+        Ok(())
+    }
+
+    /// Deploys the HTTP API definitions of the application manifest, using the deployments
+    /// defined for the selected CLI profile
+    pub async fn deploy_app_deployments(
+        &self,
+        project: Option<&ProjectNameAndId>,
+        api_defs: &[ApiDefinitionIdWithVersion],
+    ) -> anyhow::Result<()> {
+        let deployments = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            app_ctx
+                .some_or_err()?
+                .application
+                .http_api_deployments(&self.ctx.profile_name().0)
+                .to_vec()
+        };
+
+        if deployments.is_empty() {
+            return Ok(());
+        }
+
+        log_action("Deploying", "HTTP APIs");
+        let _indent = LogIndent::new();
+
+        for deployment in deployments {
+            let deployment = deployment.value;
+            let site = match &deployment.subdomain {
+                Some(subdomain) => format!("{}.{}", subdomain, deployment.host),
+                None => deployment.host.clone(),
+            };
+            let deployment_api_defs = deployment
+                .definitions
+                .iter()
+                .filter_map(|id| api_defs.iter().find(|api_def| api_def.id.0 == *id))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            log_action(
+                "Deploying",
+                format!(
+                    "{} to {}",
+                    deployment_api_defs
+                        .iter()
+                        .map(|api_def| api_def.to_string().log_color_highlight())
+                        .join(", "),
+                    site.log_color_highlight()
+                ),
+            );
+
+            self.deploy(
+                project,
+                &deployment_api_defs,
+                Some(deployment.host),
+                deployment.subdomain,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn deploy(
+        &self,
+        project: Option<&ProjectNameAndId>,
+        api_defs: &[ApiDefinitionIdWithVersion],
+        host: Option<String>,
+        subdomain: Option<String>,
+    ) -> anyhow::Result<ApiDeployment> {
         let result: ApiDeployment = match self.ctx.golem_clients().await? {
             GolemClients::Oss(clients) => {
                 let site = ApiSiteOss {
@@ -97,11 +174,16 @@ impl ApiDeploymentCommandHandler {
                     .into()
             }
             GolemClients::Cloud(clients) => {
-                let project = self
-                    .ctx
-                    .cloud_project_handler()
-                    .selected_project_or_default(project)
-                    .await?;
+                let project_id = match project {
+                    Some(project) => project.project_id,
+                    None => {
+                        self.ctx
+                            .cloud_project_handler()
+                            .selected_project_or_default(None)
+                            .await?
+                            .project_id
+                    }
+                };
 
                 let host = host.ok_or(anyhow::anyhow!(
                     "Host is required to work with cloud API deployments"
@@ -110,7 +192,7 @@ impl ApiDeploymentCommandHandler {
                 clients
                     .api_deployment
                     .deploy(&ApiDeploymentRequestCloud {
-                        project_id: project.project_id.0,
+                        project_id: project_id.0,
                         api_definitions: api_defs
                             .iter()
                             .map(|d| ApiDefinitionInfoCloud {
@@ -126,9 +208,7 @@ impl ApiDeploymentCommandHandler {
             }
         };
 
-        self.ctx.log_handler().log_view(&result);
-
-        Ok(())
+        Ok(result)
     }
 
     async fn cmd_get(&self, site: String) -> anyhow::Result<()> {
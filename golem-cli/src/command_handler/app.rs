@@ -12,11 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::app::error::CustomCommandError;
+use crate::app::context::{load_app_graph, validate_app};
+use crate::app::error::{format_errors, format_warns, CustomCommandError};
+use crate::app::watch::WatchedSources;
 use crate::command::app::AppSubcommand;
 use crate::command::builtin_app_subcommands;
 use crate::command::shared_args::{
-    AppOptionalComponentNames, BuildArgs, ForceBuildArg, WorkerUpdateOrRedeployArgs,
+    AppOptionalComponentNames, BuildArgs, DeployPlanArgs, ForceBuildArg, WorkerRolloutArgs,
+    WorkerUpdateOrRedeployArgs,
 };
 use crate::command_handler::Handlers;
 use crate::context::Context;
@@ -24,12 +27,16 @@ use crate::diagnose::diagnose;
 use crate::error::{HintError, NonSuccessfulExit};
 use crate::fs;
 use crate::fuzzy::{Error, FuzzySearch};
-use crate::log::{log_action, logln, LogColorize, LogIndent, LogOutput, Output};
-use crate::model::app::{ApplicationComponentSelectMode, DynamicHelpSections};
+use crate::log::{log_action, log_error_action, logln, LogColorize, LogIndent, LogOutput, Output};
+use crate::model::app::{
+    AppGraphFormat, ApplicationComponentSelectMode, BuildProfileName, DynamicHelpSections,
+    EnvironmentName,
+};
+use crate::model::app_raw;
 use crate::model::component::Component;
 use crate::model::text::fmt::{log_error, log_fuzzy_matches, log_text_view, log_warn};
 use crate::model::text::help::AvailableComponentNamesHelp;
-use crate::model::{ComponentName, WorkerUpdateMode};
+use crate::model::{ComponentName, Format, ProjectName, WorkerUpdateMode};
 use anyhow::{anyhow, bail};
 use colored::Colorize;
 use golem_templates::add_component_by_template;
@@ -39,6 +46,7 @@ use golem_templates::model::{
 use itertools::Itertools;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use strum::IntoEnumIterator;
 
 pub struct AppCommandHandler {
@@ -64,23 +72,40 @@ impl AppCommandHandler {
                 component_name,
                 force_build,
                 update_or_redeploy,
+                plan,
+                env,
+            } => {
+                self.cmd_deploy(component_name, force_build, update_or_redeploy, plan, env)
+                    .await
+            }
+            AppSubcommand::Watch {
+                component_name,
+                update_workers,
+                debounce,
             } => {
-                self.cmd_deploy(component_name, force_build, update_or_redeploy)
+                self.cmd_watch(component_name, update_workers, debounce)
                     .await
             }
             AppSubcommand::Clean { component_name } => self.cmd_clean(component_name).await,
             AppSubcommand::UpdateWorkers {
                 component_name,
                 update_mode,
+                rollout,
             } => {
-                self.cmd_update_workers(component_name.component_name, update_mode)
+                self.cmd_update_workers(component_name.component_name, update_mode, rollout)
                     .await
             }
-            AppSubcommand::RedeployWorkers { component_name } => {
-                self.cmd_redeploy_workers(component_name.component_name)
+            AppSubcommand::RedeployWorkers {
+                component_name,
+                rollout,
+            } => {
+                self.cmd_redeploy_workers(component_name.component_name, rollout)
                     .await
             }
             AppSubcommand::Diagnose { component_name } => self.cmd_diagnose(component_name).await,
+            AppSubcommand::Graph { graph_format } => self.cmd_graph(graph_format).await,
+            AppSubcommand::Schema { output } => self.cmd_schema(output).await,
+            AppSubcommand::Validate => self.cmd_validate().await,
             AppSubcommand::CustomCommand(command) => self.cmd_custom_command(command).await,
         }
     }
@@ -188,6 +213,7 @@ impl AppCommandHandler {
         component_name: AppOptionalComponentNames,
         build_args: BuildArgs,
     ) -> anyhow::Result<()> {
+        let _log_output = self.enable_build_events().await;
         self.build(
             component_name.component_name,
             Some(build_args),
@@ -209,23 +235,209 @@ impl AppCommandHandler {
         component_name: AppOptionalComponentNames,
         force_build: ForceBuildArg,
         update_or_redeploy: WorkerUpdateOrRedeployArgs,
+        plan: DeployPlanArgs,
+        environment_name: Option<EnvironmentName>,
     ) -> anyhow::Result<()> {
-        self.ctx
-            .component_handler()
+        let (ctx, project_name) = match environment_name {
+            Some(environment_name) => self.environment_context(&environment_name).await?,
+            None => (self.ctx.clone(), None),
+        };
+
+        let project = ctx
+            .cloud_project_handler()
+            .opt_select_project(None, project_name.as_ref())
+            .await?;
+
+        if plan.plan {
+            let plan_result = ctx
+                .component_handler()
+                .plan_deploy(
+                    project.as_ref(),
+                    component_name.component_name,
+                    Some(force_build),
+                    &ApplicationComponentSelectMode::All,
+                )
+                .await?;
+
+            ctx.log_handler().log_view(&plan_result);
+
+            if plan.fail_on_changes && plan_result.has_changes() {
+                bail!(NonSuccessfulExit);
+            }
+
+            return Ok(());
+        }
+
+        ctx.component_handler()
             .deploy(
-                self.ctx
-                    .cloud_project_handler()
-                    .opt_select_project(None, None)
-                    .await?
-                    .as_ref(),
+                project.as_ref(),
                 component_name.component_name,
                 Some(force_build),
                 &ApplicationComponentSelectMode::All,
                 update_or_redeploy,
             )
+            .await?;
+
+        ctx.worker_handler()
+            .deploy_app_workers(project.as_ref())
+            .await?;
+
+        let api_definitions = ctx
+            .api_definition_handler()
+            .deploy_app_definitions(project.as_ref())
+            .await?;
+
+        ctx.api_deployment_handler()
+            .deploy_app_deployments(project.as_ref(), &api_definitions)
             .await
     }
 
+    /// Creates the context for the environment defined in the application manifest, returns it
+    /// together with the project of the environment
+    async fn environment_context(
+        &self,
+        environment_name: &EnvironmentName,
+    ) -> anyhow::Result<(Arc<Context>, Option<ProjectName>)> {
+        // The manifest is loaded again by the environment context, using its build profile
+        self.ctx.silence_app_context_init().await;
+
+        let app_ctx = self.ctx.app_context_lock().await;
+        let app_ctx = app_ctx.some_or_err()?;
+
+        let Some(environment) = app_ctx.application.environment(environment_name) else {
+            log_error(format!(
+                "Environment {} is not defined in the application manifest",
+                environment_name.as_str().log_color_highlight()
+            ));
+            logln("");
+            logln(format!(
+                "Available environments: {}",
+                app_ctx
+                    .application
+                    .environment_names()
+                    .map(|name| name.as_str().log_color_highlight())
+                    .join(", ")
+            ));
+            bail!(NonSuccessfulExit);
+        };
+
+        let ctx = self.ctx.for_environment(environment_name, environment)?;
+
+        log_action(
+            "Selected",
+            format!(
+                "environment {}, profile: {}, project: {}, build profile: {}",
+                environment_name.as_str().log_color_highlight(),
+                environment.profile.log_color_highlight(),
+                environment
+                    .project
+                    .as_deref()
+                    .unwrap_or("<default>")
+                    .log_color_highlight(),
+                environment
+                    .build_profile
+                    .as_ref()
+                    .map(|build_profile| build_profile.as_str())
+                    .unwrap_or("<default>")
+                    .log_color_highlight()
+            ),
+        );
+
+        Ok((
+            Arc::new(ctx),
+            environment.project.clone().map(ProjectName::from),
+        ))
+    }
+
+    async fn cmd_watch(
+        &mut self,
+        component_name: AppOptionalComponentNames,
+        update_workers: bool,
+        debounce: u64,
+    ) -> anyhow::Result<()> {
+        let debounce = Duration::from_millis(debounce);
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(None, None)
+            .await?;
+
+        let mut watched_sources: Option<WatchedSources> = None;
+        let mut component_names_to_deploy = Some(component_name.component_name.clone());
+        loop {
+            self.ctx.unload_app_context().await;
+            match self
+                .watched_sources(component_name.component_name.clone())
+                .await
+            {
+                Ok(sources) => {
+                    watched_sources = Some(sources);
+                }
+                Err(error) => {
+                    if watched_sources.is_none() {
+                        return Err(error);
+                    }
+                    log_watch_error(error);
+                    log_warn("Using the previously loaded application manifests for watching");
+                }
+            }
+
+            // The baseline is taken before deploying, so changes made during the build are
+            // detected in the next round
+            let baseline = watched_sources.as_ref().unwrap().snapshot()?;
+
+            if let Some(component_names) = component_names_to_deploy.take() {
+                self.ctx.unload_app_context().await;
+                let result = self
+                    .ctx
+                    .component_handler()
+                    .deploy(
+                        project.as_ref(),
+                        component_names,
+                        None,
+                        &ApplicationComponentSelectMode::All,
+                        WorkerUpdateOrRedeployArgs {
+                            update_workers: update_workers.then_some(WorkerUpdateMode::Automatic),
+                            redeploy_workers: false,
+                            rollout: WorkerRolloutArgs::default(),
+                        },
+                    )
+                    .await;
+                if let Err(error) = result {
+                    log_watch_error(error);
+                }
+            }
+
+            logln("");
+            log_action("Watching", "for changes, press Ctrl+C to stop");
+            let changed_component_names = watched_sources
+                .as_ref()
+                .unwrap()
+                .wait_for_changes(&baseline, debounce)
+                .await?;
+            logln("");
+
+            if !changed_component_names.is_empty() {
+                component_names_to_deploy = Some(
+                    changed_component_names
+                        .iter()
+                        .map(|component_name| component_name.as_str().into())
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    async fn watched_sources(
+        &mut self,
+        component_names: Vec<ComponentName>,
+    ) -> anyhow::Result<WatchedSources> {
+        self.must_select_components(component_names, &ApplicationComponentSelectMode::All)
+            .await?;
+        let app_ctx = self.ctx.app_context_lock().await;
+        Ok(WatchedSources::new(app_ctx.some_or_err()?))
+    }
+
     async fn cmd_custom_command(&mut self, command: Vec<String>) -> anyhow::Result<()> {
         if command.len() != 1 {
             bail!(
@@ -291,6 +503,7 @@ impl AppCommandHandler {
         &mut self,
         component_names: Vec<ComponentName>,
         update_mode: WorkerUpdateMode,
+        rollout: WorkerRolloutArgs,
     ) -> anyhow::Result<()> {
         self.must_select_components(component_names, &ApplicationComponentSelectMode::All)
             .await?;
@@ -298,7 +511,7 @@ impl AppCommandHandler {
         let components = self.components_for_update_or_redeploy().await?;
         self.ctx
             .component_handler()
-            .update_workers_by_components(components, update_mode)
+            .update_workers_by_components(components, update_mode, &rollout)
             .await?;
 
         Ok(())
@@ -307,6 +520,7 @@ impl AppCommandHandler {
     async fn cmd_redeploy_workers(
         &mut self,
         component_names: Vec<ComponentName>,
+        rollout: WorkerRolloutArgs,
     ) -> anyhow::Result<()> {
         self.must_select_components(component_names, &ApplicationComponentSelectMode::All)
             .await?;
@@ -314,7 +528,7 @@ impl AppCommandHandler {
         let components = self.components_for_update_or_redeploy().await?;
         self.ctx
             .component_handler()
-            .redeploy_workers_by_components(components)
+            .redeploy_workers_by_components(components, &rollout)
             .await?;
 
         Ok(())
@@ -331,6 +545,104 @@ impl AppCommandHandler {
         .await
     }
 
+    async fn cmd_graph(&mut self, graph_format: AppGraphFormat) -> anyhow::Result<()> {
+        let graph = {
+            // Only the graph is written to the output, so it can be piped into other tools
+            let _log_output = LogOutput::new(Output::TracingDebug);
+            load_app_graph(&self.ctx.app_source_mode())
+        }?
+        .ok_or(anyhow!(HintError::NoApplicationManifestFound))?;
+
+        match (self.ctx.format(), graph_format) {
+            (Format::Text, AppGraphFormat::Dot) => logln(graph.to_dot()),
+            (Format::Text, AppGraphFormat::Mermaid) => logln(graph.to_mermaid()),
+            _ => self.ctx.log_handler().log_view(&graph),
+        }
+
+        Ok(())
+    }
+
+    async fn cmd_schema(&self, output: Option<PathBuf>) -> anyhow::Result<()> {
+        let schema = serde_json::to_string_pretty(&app_raw::Application::json_schema())?;
+
+        match output {
+            Some(output) => {
+                log_action(
+                    "Writing",
+                    format!(
+                        "application manifest schema to {}",
+                        output.log_color_highlight()
+                    ),
+                );
+                fs::write_str(&output, schema)?;
+            }
+            None => logln(schema),
+        }
+
+        Ok(())
+    }
+
+    async fn cmd_validate(&self) -> anyhow::Result<()> {
+        let profile: Option<BuildProfileName> =
+            self.ctx.build_profile().map(|p| p.to_string().into());
+
+        let result = {
+            // Warnings and errors are reported below with their locations, instead of the loading logs
+            let _log_output = LogOutput::new(Output::TracingDebug);
+            validate_app(&self.ctx.app_source_mode(), profile.as_ref())
+        }
+        .ok_or(anyhow!(HintError::NoApplicationManifestFound))?;
+
+        let (application, warns, errors) = result.into_product();
+
+        if !warns.is_empty() {
+            logln(format_warns(&warns));
+        }
+        if !errors.is_empty() {
+            logln(format_errors(&errors));
+        }
+
+        match application {
+            Some(application) if errors.is_empty() => {
+                log_action(
+                    "Validated",
+                    format!(
+                        "application manifest, found {} component(s) and {} warning(s)",
+                        application
+                            .component_names()
+                            .count()
+                            .to_string()
+                            .log_color_highlight(),
+                        warns.len().to_string().log_color_highlight()
+                    ),
+                );
+                Ok(())
+            }
+            _ => {
+                log_error_action(
+                    "Failed",
+                    format!(
+                        "to validate application manifest, found {} error(s)",
+                        errors.len().to_string().log_color_highlight()
+                    ),
+                );
+                bail!(NonSuccessfulExit)
+            }
+        }
+    }
+
+    /// With JSON output, build progress is written to stdout as NDJSON build events, while the
+    /// human-readable logs are moved to stderr. The returned guard restores the log output.
+    pub async fn enable_build_events(&self) -> Option<LogOutput> {
+        if self.ctx.format() != Format::Json {
+            return None;
+        }
+
+        colored::control::set_override(false);
+        self.ctx.set_build_events(true).await;
+        Some(LogOutput::new(Output::Stderr))
+    }
+
     pub async fn build(
         &mut self,
         component_names: Vec<ComponentName>,
@@ -344,6 +656,9 @@ impl AppCommandHandler {
             self.ctx
                 .set_skip_up_to_date_checks(build.force_build.force_build)
                 .await;
+            self.ctx.set_up_to_date_check(build.up_to_date_check).await;
+            self.ctx.set_build_jobs(build.jobs.get()).await;
+            self.ctx.set_build_cache(build.build_cache).await;
         }
         self.must_select_components(component_names, default_component_select_mode)
             .await?;
@@ -689,3 +1004,10 @@ impl AppCommandHandler {
         Ok(())
     }
 }
+
+fn log_watch_error(error: anyhow::Error) {
+    if error.downcast_ref::<NonSuccessfulExit>().is_none() {
+        logln("");
+        log_error(format!("{:#}", error));
+    }
+}
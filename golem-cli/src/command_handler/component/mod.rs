@@ -13,25 +13,33 @@
 // limitations under the License.
 
 use crate::app::context::ApplicationContext;
+use crate::app::manifest_edit;
 use crate::cloud::AccountId;
 use crate::command::builtin_app_subcommands;
 use crate::command::component::ComponentSubcommand;
 use crate::command::shared_args::{
     BuildArgs, ComponentOptionalComponentNames, ComponentTemplatePositionalArg, ForceBuildArg,
-    WorkerUpdateOrRedeployArgs,
+    WorkerRolloutArgs, WorkerUpdateOrRedeployArgs,
 };
 use crate::command_handler::component::ifs::IfsArchiveBuilder;
 use crate::command_handler::Handlers;
 use crate::context::{Context, GolemClients};
 use crate::error::service::AnyhowMapServiceError;
 use crate::error::NonSuccessfulExit;
-use crate::log::{log_action, logln, LogColorize, LogIndent};
+use crate::fs;
+use crate::log::{log_action, log_skipping_up_to_date, logln, LogColorize, LogIndent};
 use crate::model::app::{
-    AppComponentName, ApplicationComponentSelectMode, BuildProfileName, DynamicHelpSections,
+    AppBuildStep, AppComponentName, ApplicationComponentSelectMode, BuildProfileName,
+    DynamicHelpSections, EnvironmentName,
+};
+use crate::model::app::{DependencyType, InitialComponentFile, UpToDateCheckMode};
+use crate::model::app_raw;
+use crate::model::component::{AppComponentType, Component, ComponentSelection, ComponentView};
+use crate::model::deploy::{
+    ComponentDeployAction, ComponentDeployFingerprint, ComponentDeployPlan,
+    ComponentDeployPlanEntry, DeployedComponentFingerprint, WorkerRolloutOutcome,
+    WorkerRolloutResult,
 };
-use crate::model::app::{DependencyType, InitialComponentFile};
-use crate::model::component::{Component, ComponentSelection, ComponentView};
-use crate::model::deploy::TryUpdateAllWorkersResult;
 use crate::model::text::component::{ComponentCreateView, ComponentGetView, ComponentUpdateView};
 use crate::model::text::fmt::{log_error, log_text_view, log_warn};
 use crate::model::text::help::ComponentNameHelp;
@@ -40,6 +48,7 @@ use crate::model::{
     ComponentName, ComponentNameMatchKind, ComponentVersionSelection, ProjectNameAndId,
     SelectedComponents, WorkerUpdateMode,
 };
+use crate::wasm_rpc_stubgen::commands::metadata::add_processed_by;
 use anyhow::{anyhow, bail, Context as AnyhowContext};
 use golem_client::api::ComponentClient as ComponentClientOss;
 use golem_client::model::DynamicLinkedInstance as DynamicLinkedInstanceOss;
@@ -52,10 +61,12 @@ use golem_common::model::{ComponentId, ComponentType};
 use golem_templates::add_component_by_template;
 use golem_templates::model::{GuestLanguage, PackageName};
 use itertools::Itertools;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::fs::File;
+use walkdir::WalkDir;
 
 pub mod ifs;
 pub mod plugin;
@@ -103,12 +114,16 @@ impl ComponentCommandHandler {
             ComponentSubcommand::UpdateWorkers {
                 component_name,
                 update_mode,
+                rollout,
             } => {
-                self.cmd_update_workers(component_name.component_name, update_mode)
+                self.cmd_update_workers(component_name.component_name, update_mode, rollout)
                     .await
             }
-            ComponentSubcommand::RedeployWorkers { component_name } => {
-                self.cmd_redeploy_workers(component_name.component_name)
+            ComponentSubcommand::RedeployWorkers {
+                component_name,
+                rollout,
+            } => {
+                self.cmd_redeploy_workers(component_name.component_name, rollout)
                     .await
             }
             ComponentSubcommand::Plugin { subcommand } => {
@@ -120,6 +135,30 @@ impl ComponentCommandHandler {
             ComponentSubcommand::Diagnose { component_name } => {
                 self.cmd_diagnose(component_name).await
             }
+            ComponentSubcommand::AddDependency {
+                component_name,
+                target_component_name,
+                dependency_type,
+            } => {
+                self.cmd_add_dependency(
+                    component_name.component_name,
+                    target_component_name,
+                    dependency_type,
+                )
+                .await
+            }
+            ComponentSubcommand::RemoveDependency {
+                component_name,
+                target_component_name,
+                dependency_type,
+            } => {
+                self.cmd_remove_dependency(
+                    component_name.component_name,
+                    target_component_name,
+                    dependency_type,
+                )
+                .await
+            }
         }
     }
 
@@ -202,6 +241,7 @@ impl ComponentCommandHandler {
         component_name: ComponentOptionalComponentNames,
         build_args: BuildArgs,
     ) -> anyhow::Result<()> {
+        let _log_output = self.ctx.app_handler().enable_build_events().await;
         self.ctx
             .app_handler()
             .build(
@@ -528,11 +568,12 @@ impl ComponentCommandHandler {
         &self,
         component_name: Option<ComponentName>,
         update_mode: WorkerUpdateMode,
+        rollout: WorkerRolloutArgs,
     ) -> anyhow::Result<()> {
         let components = self
             .components_for_update_or_redeploy(component_name)
             .await?;
-        self.update_workers_by_components(components, update_mode)
+        self.update_workers_by_components(components, update_mode, &rollout)
             .await?;
 
         Ok(())
@@ -541,11 +582,13 @@ impl ComponentCommandHandler {
     async fn cmd_redeploy_workers(
         &self,
         component_name: Option<ComponentName>,
+        rollout: WorkerRolloutArgs,
     ) -> anyhow::Result<()> {
         let components = self
             .components_for_update_or_redeploy(component_name)
             .await?;
-        self.redeploy_workers_by_components(components).await?;
+        self.redeploy_workers_by_components(components, &rollout)
+            .await?;
 
         Ok(())
     }
@@ -563,6 +606,184 @@ impl ComponentCommandHandler {
             .await
     }
 
+    async fn cmd_add_dependency(
+        &mut self,
+        component_name: Option<ComponentName>,
+        target_component_name: ComponentName,
+        dependency_type: DependencyType,
+    ) -> anyhow::Result<()> {
+        let (component_name, target_component_name) = self
+            .select_dependency_components(component_name, target_component_name)
+            .await?;
+
+        let source = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+            app_ctx
+                .application
+                .component_source(&component_name)
+                .to_path_buf()
+        };
+
+        let manifest = fs::read_to_string(&source)?;
+        let manifest = manifest_edit::add_dependency(
+            &source,
+            &manifest,
+            component_name.as_str(),
+            dependency_type,
+            target_component_name.as_str(),
+        )?;
+        fs::write_str(&source, manifest)?;
+
+        log_action(
+            "Added",
+            format!(
+                "{} dependency {} - {} to {}",
+                dependency_type.as_str().log_color_highlight(),
+                component_name.as_str().log_color_highlight(),
+                target_component_name.as_str().log_color_highlight(),
+                source.log_color_highlight()
+            ),
+        );
+
+        self.update_dependency_wit(component_name).await
+    }
+
+    async fn cmd_remove_dependency(
+        &mut self,
+        component_name: Option<ComponentName>,
+        target_component_name: ComponentName,
+        dependency_type: Option<DependencyType>,
+    ) -> anyhow::Result<()> {
+        let (component_name, target_component_name) = self
+            .select_dependency_components(component_name, target_component_name)
+            .await?;
+
+        let sources = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+            app_ctx.application.sources().clone()
+        };
+
+        let mut removed_from = None;
+        for source in sources {
+            let manifest = fs::read_to_string(&source)?;
+            if let Some(manifest) = manifest_edit::remove_dependency(
+                &source,
+                &manifest,
+                component_name.as_str(),
+                dependency_type,
+                target_component_name.as_str(),
+            )? {
+                fs::write_str(&source, manifest)?;
+                removed_from = Some(source);
+                break;
+            }
+        }
+
+        let Some(source) = removed_from else {
+            bail!(
+                "Dependency {} - {} is not defined in the application manifest",
+                component_name.as_str().log_color_highlight(),
+                target_component_name.as_str().log_color_highlight()
+            );
+        };
+
+        log_action(
+            "Removed",
+            format!(
+                "dependency {} - {} from {}",
+                component_name.as_str().log_color_highlight(),
+                target_component_name.as_str().log_color_highlight(),
+                source.log_color_highlight()
+            ),
+        );
+
+        self.update_dependency_wit(component_name).await
+    }
+
+    /// Selects exactly one app component (based on the current dir or the given name) and
+    /// validates the dependency target component name
+    async fn select_dependency_components(
+        &mut self,
+        component_name: Option<ComponentName>,
+        target_component_name: ComponentName,
+    ) -> anyhow::Result<(AppComponentName, AppComponentName)> {
+        self.ctx
+            .app_handler()
+            .must_select_components(
+                component_name.into_iter().collect(),
+                &ApplicationComponentSelectMode::CurrentDir,
+            )
+            .await?;
+
+        let app_ctx = self.ctx.app_context_lock().await;
+        let app_ctx = app_ctx.some_or_err()?;
+
+        let selected_component_names = app_ctx.selected_component_names();
+        if selected_component_names.len() != 1 {
+            log_error(format!(
+                "Expected exactly one selected component, got: {}",
+                if selected_component_names.is_empty() {
+                    "none".to_string()
+                } else {
+                    selected_component_names
+                        .iter()
+                        .map(|name| name.as_str().log_color_highlight())
+                        .join(", ")
+                }
+            ));
+            logln("");
+            app_ctx.log_dynamic_help(&DynamicHelpSections {
+                components: true,
+                custom_commands: false,
+                builtin_commands: builtin_app_subcommands(),
+            })?;
+            bail!(NonSuccessfulExit)
+        }
+        let component_name = selected_component_names.iter().next().unwrap().clone();
+
+        let target_component_name = AppComponentName::from(target_component_name.0);
+        if !app_ctx
+            .application
+            .component_names()
+            .contains(&target_component_name)
+        {
+            log_error(format!(
+                "Target component {} not found",
+                target_component_name.as_str().log_color_highlight()
+            ));
+            logln("");
+            app_ctx.log_dynamic_help(&DynamicHelpSections {
+                components: true,
+                custom_commands: false,
+                builtin_commands: builtin_app_subcommands(),
+            })?;
+            bail!(NonSuccessfulExit)
+        }
+
+        Ok((component_name, target_component_name))
+    }
+
+    /// Reloads the edited manifest and regenerates the WIT dependencies of the component
+    async fn update_dependency_wit(
+        &mut self,
+        component_name: AppComponentName,
+    ) -> anyhow::Result<()> {
+        self.ctx.unload_app_context().await;
+        self.ctx
+            .set_steps_filter(HashSet::from([AppBuildStep::GenRpc]))
+            .await;
+        self.ctx
+            .app_handler()
+            .build(
+                vec![ComponentName::from(component_name.as_str())],
+                None,
+                &ApplicationComponentSelectMode::CurrentDir,
+            )
+            .await
+    }
+
     pub async fn deploy(
         &mut self,
         project: Option<&ProjectNameAndId>,
@@ -571,85 +792,216 @@ impl ComponentCommandHandler {
         default_component_select_mode: &ApplicationComponentSelectMode,
         update_or_redeploy: WorkerUpdateOrRedeployArgs,
     ) -> anyhow::Result<()> {
-        self.ctx
-            .app_handler()
-            .build(
-                component_names,
-                force_build.map(|force_build| BuildArgs {
-                    step: vec![],
-                    force_build,
-                }),
-                default_component_select_mode,
-            )
+        self.build_for_deploy(component_names, force_build, default_component_select_mode)
             .await?;
 
-        // TODO: hash <-> version check for skipping deploy
-
-        let selected_component_names = {
-            let app_ctx = self.ctx.app_context_lock().await;
-            app_ctx
-                .some_or_err()?
-                .selected_component_names()
-                .iter()
-                .cloned()
-                .collect::<Vec<_>>()
-        };
         let build_profile = self.ctx.build_profile().cloned();
+        let component_names = self
+            .deployable_component_names(build_profile.as_ref())
+            .await?;
 
         let components = {
             log_action("Deploying", "components");
             let _indent = LogIndent::new();
 
-            let mut components = Vec::with_capacity(selected_component_names.len());
-            for component_name in &selected_component_names {
-                let app_ctx = self.ctx.app_context_lock().await;
-                if app_ctx
-                    .some_or_err()?
-                    .application
-                    .component_properties(component_name, build_profile.as_ref())
-                    .is_deployable()
-                {
-                    drop(app_ctx);
-                    components.push(
-                        self.deploy_component(build_profile.as_ref(), project, component_name)
-                            .await?,
-                    );
-                }
+            let mut components = Vec::with_capacity(component_names.len());
+            for component_name in &component_names {
+                components.push(
+                    self.deploy_component(build_profile.as_ref(), project, component_name)
+                        .await?,
+                );
             }
 
             components
         };
 
         if let Some(update) = update_or_redeploy.update_workers {
-            self.update_workers_by_components(components, update)
+            self.update_workers_by_components(components, update, &update_or_redeploy.rollout)
                 .await?;
         } else if update_or_redeploy.redeploy_workers {
-            self.redeploy_workers_by_components(components).await?;
+            self.redeploy_workers_by_components(components, &update_or_redeploy.rollout)
+                .await?;
         }
 
         Ok(())
     }
 
-    async fn deploy_component(
+    /// Builds the selected components and compares them with the deployed ones, without
+    /// deploying anything
+    pub async fn plan_deploy(
+        &mut self,
+        project: Option<&ProjectNameAndId>,
+        component_names: Vec<ComponentName>,
+        force_build: Option<ForceBuildArg>,
+        default_component_select_mode: &ApplicationComponentSelectMode,
+    ) -> anyhow::Result<ComponentDeployPlan> {
+        self.build_for_deploy(component_names, force_build, default_component_select_mode)
+            .await?;
+
+        let build_profile = self.ctx.build_profile().cloned();
+        let mut plan = ComponentDeployPlan::default();
+        for component_name in self
+            .deployable_component_names(build_profile.as_ref())
+            .await?
+        {
+            let (_, _, plan_entry) = self
+                .component_deploy_plan(build_profile.as_ref(), project, &component_name)
+                .await?;
+            plan.components.push(plan_entry);
+        }
+
+        Ok(plan)
+    }
+
+    async fn build_for_deploy(
         &mut self,
+        component_names: Vec<ComponentName>,
+        force_build: Option<ForceBuildArg>,
+        default_component_select_mode: &ApplicationComponentSelectMode,
+    ) -> anyhow::Result<()> {
+        self.ctx
+            .app_handler()
+            .build(
+                component_names,
+                force_build.map(|force_build| BuildArgs {
+                    step: vec![],
+                    force_build,
+                    up_to_date_check: UpToDateCheckMode::default(),
+                    jobs: NonZeroUsize::MIN,
+                    build_cache: None,
+                }),
+                default_component_select_mode,
+            )
+            .await
+    }
+
+    async fn deployable_component_names(
+        &self,
+        build_profile: Option<&BuildProfileName>,
+    ) -> anyhow::Result<Vec<AppComponentName>> {
+        let app_ctx = self.ctx.app_context_lock().await;
+        let app_ctx = app_ctx.some_or_err()?;
+        Ok(app_ctx
+            .selected_component_names()
+            .iter()
+            .filter(|component_name| {
+                app_ctx
+                    .application
+                    .component_properties(component_name, build_profile)
+                    .is_deployable()
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// Compares the local component with the deployed one, using the hashes recorded by the last
+    /// deploy from the application
+    async fn component_deploy_plan(
+        &self,
         build_profile: Option<&BuildProfileName>,
         project: Option<&ProjectNameAndId>,
         component_name: &AppComponentName,
-    ) -> anyhow::Result<Component> {
-        let component_id = self
-            .component_id_by_name(project, &component_name.as_str().into())
+    ) -> anyhow::Result<(
+        ComponentDeployProperties,
+        Option<Component>,
+        ComponentDeployPlanEntry,
+    )> {
+        let deployed_component = self
+            .component(
+                project,
+                (&ComponentName::from(component_name.as_str())).into(),
+                None,
+            )
             .await?;
         let deploy_properties = {
             let mut app_ctx = self.ctx.app_context_lock_mut().await;
             let app_ctx = app_ctx.some_or_err_mut()?;
-            component_deploy_properties(app_ctx, component_name, build_profile)?
+            component_deploy_properties(
+                app_ctx,
+                component_name,
+                build_profile,
+                self.ctx.environment(),
+            )?
+        };
+        let plan_entry = deploy_properties.fingerprint.plan_entry(
+            component_name.as_str().into(),
+            deployed_component
+                .as_ref()
+                .map(DeployedComponentFingerprint::from)
+                .as_ref(),
+        );
+
+        Ok((deploy_properties, deployed_component, plan_entry))
+    }
+
+    async fn deploy_component(
+        &mut self,
+        build_profile: Option<&BuildProfileName>,
+        project: Option<&ProjectNameAndId>,
+        component_name: &AppComponentName,
+    ) -> anyhow::Result<Component> {
+        let (deploy_properties, deployed_component, plan_entry) = self
+            .component_deploy_plan(build_profile, project, component_name)
+            .await?;
+
+        let component = match deployed_component {
+            Some(component) if plan_entry.action == ComponentDeployAction::Unchanged => {
+                log_skipping_up_to_date(format!(
+                    "deploying component {}",
+                    component_name.as_str().log_color_highlight()
+                ));
+                component
+            }
+            deployed_component => {
+                self.upload_component(
+                    project,
+                    component_name,
+                    deployed_component.map(|component| {
+                        ComponentId(component.versioned_component_id.component_id)
+                    }),
+                    &deploy_properties,
+                )
+                .await?
+            }
         };
 
+        if let Some(plugins) = &deploy_properties.plugins {
+            let _indent = LogIndent::new();
+            let plugins_changed = self
+                .ctx
+                .component_plugin_handler()
+                .deploy_app_plugins(
+                    &component,
+                    plugins,
+                    deploy_properties.uninstall_extra_plugins,
+                )
+                .await?;
+            if plugins_changed {
+                // Plugin installations create new component versions
+                let component_name = ComponentName::from(component_name.as_str());
+                let component = self
+                    .component(project, (&component_name).into(), None)
+                    .await?
+                    .ok_or_else(|| anyhow!("Component {} not found", component_name))?;
+                return Ok(component);
+            }
+        }
+
+        Ok(component)
+    }
+
+    async fn upload_component(
+        &self,
+        project: Option<&ProjectNameAndId>,
+        component_name: &AppComponentName,
+        component_id: Option<ComponentId>,
+        deploy_properties: &ComponentDeployProperties,
+    ) -> anyhow::Result<Component> {
         let ifs_files = {
             if !deploy_properties.files.is_empty() {
                 Some(
                     IfsArchiveBuilder::new(self.ctx.file_download_client().await?)
-                        .build_files_archive(deploy_properties.files)
+                        .build_files_archive(deploy_properties.files.clone())
                         .await?,
                 )
             } else {
@@ -670,22 +1022,28 @@ impl ComponentCommandHandler {
             }
         };
 
-        let linked_wasm = File::open(&deploy_properties.linked_wasm_path)
+        // The content hashes are stored in the component, so later deploys can compare them
+        // with the local ones
+        add_processed_by(
+            &deploy_properties.linked_wasm_path,
+            deploy_properties.fingerprint.processed_by(),
+            &deploy_properties.deploy_wasm_path,
+        )?;
+        let linked_wasm = File::open(&deploy_properties.deploy_wasm_path)
             .await
             .with_context(|| {
                 anyhow!(
                     "Failed to open component linked WASM at {}",
                     deploy_properties
-                        .linked_wasm_path
+                        .deploy_wasm_path
                         .display()
                         .to_string()
                         .log_color_error_highlight()
                 )
             })?;
 
-        let component = match &component_id {
+        let component = match component_id {
             Some(component_id) => {
-                // TODO: use hashes for checking if component files has to be updated?
                 log_action(
                     "Updating",
                     format!(
@@ -721,6 +1079,7 @@ impl ComponentCommandHandler {
                                 ifs_archive,
                                 deploy_properties
                                     .dynamic_linking
+                                    .clone()
                                     .map(|dl| dl.to_cloud())
                                     .as_ref(),
                             )
@@ -773,6 +1132,7 @@ impl ComponentCommandHandler {
                                 ifs_archive,
                                 deploy_properties
                                     .dynamic_linking
+                                    .clone()
                                     .map(|dl| dl.to_cloud())
                                     .as_ref(),
                             )
@@ -826,6 +1186,7 @@ impl ComponentCommandHandler {
         &self,
         components: Vec<Component>,
         update: WorkerUpdateMode,
+        rollout: &WorkerRolloutArgs,
     ) -> anyhow::Result<()> {
         if components.is_empty() {
             return Ok(());
@@ -837,7 +1198,7 @@ impl ComponentCommandHandler {
         );
         let _indent = LogIndent::new();
 
-        let mut update_results = TryUpdateAllWorkersResult::default();
+        let mut rollout_results = WorkerRolloutResult::default();
         for component in &components {
             let result = self
                 .ctx
@@ -847,18 +1208,22 @@ impl ComponentCommandHandler {
                     component.versioned_component_id.component_id,
                     update,
                     component.versioned_component_id.version,
+                    rollout,
                 )
                 .await?;
-            update_results.extend(result);
+            rollout_results.extend(result);
+            if rollout_results.aborted.is_some() {
+                break;
+            }
         }
 
-        self.ctx.log_handler().log_view(&update_results);
-        Ok(())
+        self.log_rollout_results(rollout_results)
     }
 
     pub async fn redeploy_workers_by_components(
         &self,
         components: Vec<Component>,
+        rollout: &WorkerRolloutArgs,
     ) -> anyhow::Result<()> {
         if components.is_empty() {
             return Ok(());
@@ -867,19 +1232,39 @@ impl ComponentCommandHandler {
         log_action("Redeploying", "existing workers");
         let _indent = LogIndent::new();
 
+        let mut rollout_results = WorkerRolloutResult::default();
         for component in &components {
-            self.ctx
+            let result = self
+                .ctx
                 .worker_handler()
                 .redeploy_component_workers(
                     &component.component_name,
                     component.versioned_component_id.component_id,
+                    component.versioned_component_id.version,
+                    rollout,
                 )
                 .await?;
+            rollout_results.extend(result);
+            if rollout_results.aborted.is_some() {
+                break;
+            }
         }
 
-        // TODO: json / yaml output?
-        // TODO: unlike updating, redeploy is short-circuiting, should we normalize?
         // TODO: should we expose "delete-workers" too for development?
+        self.log_rollout_results(rollout_results)
+    }
+
+    /// Shows the results, and fails if the rollout was aborted, or if any worker failed to be
+    /// updated or redeployed, even when the rollout continued
+    fn log_rollout_results(&self, rollout_results: WorkerRolloutResult) -> anyhow::Result<()> {
+        self.ctx.log_handler().log_view(&rollout_results);
+
+        if rollout_results.aborted.is_some()
+            || rollout_results.count(WorkerRolloutOutcome::Failed) > 0
+        {
+            bail!(NonSuccessfulExit);
+        }
+
         Ok(())
     }
 
@@ -1235,12 +1620,17 @@ struct ComponentDeployProperties {
     linked_wasm_path: PathBuf,
     files: Vec<InitialComponentFile>,
     dynamic_linking: Option<DynamicLinkingOss>,
+    plugins: Option<Vec<app_raw::PluginInstallation>>,
+    uninstall_extra_plugins: bool,
+    fingerprint: ComponentDeployFingerprint,
+    deploy_wasm_path: PathBuf,
 }
 
 fn component_deploy_properties(
     app_ctx: &mut ApplicationContext,
     component_name: &AppComponentName,
     build_profile: Option<&BuildProfileName>,
+    environment_name: Option<&EnvironmentName>,
 ) -> anyhow::Result<ComponentDeployProperties> {
     let linked_wasm_path = app_ctx
         .application
@@ -1248,18 +1638,106 @@ fn component_deploy_properties(
     let component_properties = &app_ctx
         .application
         .component_properties(component_name, build_profile);
-    let component_type = component_properties
-        .component_type
+    let app_component_type = component_properties.component_type;
+    let component_type = app_component_type
         .as_deployable_component_type()
         .ok_or_else(|| anyhow!("Component {component_name} is not deployable"))?;
-    let files = component_properties.files.clone();
+    let files =
+        app_ctx
+            .application
+            .component_files(component_name, build_profile, environment_name);
+    let plugins = component_properties.plugins.clone();
+    let uninstall_extra_plugins = component_properties.uninstall_extra_plugins;
     let dynamic_linking = app_component_dynamic_linking(app_ctx, component_name)?;
+    let fingerprint = component_deploy_fingerprint(
+        app_component_type,
+        &linked_wasm_path,
+        &files,
+        dynamic_linking.as_ref(),
+    )?;
+    let deploy_wasm_path = app_ctx.application.component_deploy_wasm(component_name);
 
     Ok(ComponentDeployProperties {
         component_type,
         linked_wasm_path,
         files,
         dynamic_linking,
+        plugins,
+        uninstall_extra_plugins,
+        fingerprint,
+        deploy_wasm_path,
+    })
+}
+
+fn component_deploy_fingerprint(
+    component_type: AppComponentType,
+    linked_wasm_path: &Path,
+    files: &[InitialComponentFile],
+    dynamic_linking: Option<&DynamicLinkingOss>,
+) -> anyhow::Result<ComponentDeployFingerprint> {
+    let wasm = fs::read(linked_wasm_path)?;
+
+    let mut file_permissions = BTreeMap::new();
+    let mut files_hasher = blake3::Hasher::new();
+    for file in files {
+        let source = file.source.as_url();
+        match source.scheme() {
+            "file" | "" => {
+                let source_path = PathBuf::from(source.path());
+                for entry in WalkDir::new(&source_path).sort_by_file_name() {
+                    let entry = entry?;
+                    if entry.file_type().is_dir() {
+                        continue;
+                    }
+
+                    let mut target = file.target.clone();
+                    for path_component in entry.path().strip_prefix(&source_path)?.components() {
+                        target
+                            .extend_path(&path_component.as_os_str().to_string_lossy())
+                            .map_err(|err| anyhow!("Error extending path: {err}"))?;
+                    }
+
+                    files_hasher.update(target.path.to_string().as_bytes());
+                    files_hasher.update(blake3::hash(&fs::read(entry.path())?).as_bytes());
+                    file_permissions.insert(target.path.to_string(), target.permissions);
+                }
+            }
+            _ => {
+                // Remote files are not downloaded for planning, only their URLs are compared
+                files_hasher.update(file.target.path.to_string().as_bytes());
+                files_hasher.update(source.as_str().as_bytes());
+                file_permissions.insert(file.target.path.to_string(), file.target.permissions);
+            }
+        }
+    }
+
+    Ok(ComponentDeployFingerprint {
+        component_type,
+        wasm_hash: blake3::hash(&wasm).to_hex().to_string(),
+        files: file_permissions,
+        files_hash: files_hasher.finalize().to_hex().to_string(),
+        dynamic_linking: dynamic_linking
+            .map(|dynamic_linking| {
+                dynamic_linking
+                    .dynamic_linking
+                    .iter()
+                    .map(|(name, link)| {
+                        (
+                            name.clone(),
+                            match link {
+                                DynamicLinkedInstanceOss::WasmRpc(links) => links
+                                    .targets
+                                    .iter()
+                                    .map(|(resource, target)| {
+                                        (resource.clone(), target.interface_name.clone())
+                                    })
+                                    .collect(),
+                            },
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
     })
 }
 
@@ -17,7 +17,9 @@ use crate::command_handler::Handlers;
 use crate::context::{Context, GolemClients};
 use crate::error::service::AnyhowMapServiceError;
 use crate::error::NonSuccessfulExit;
-use crate::log::{log_action, log_error_action, log_warn_action, LogIndent};
+use crate::log::{log_action, log_error_action, log_warn_action, LogColorize, LogIndent};
+use crate::model::app_raw;
+use crate::model::component::Component;
 use crate::model::text::fmt::log_warn;
 use crate::model::ComponentName;
 use anyhow::bail;
@@ -28,6 +30,7 @@ use golem_client::model::{
 use golem_cloud_client::api::ComponentClient as ComponentClientCloud;
 use golem_common::base_model::PluginInstallationId;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub struct ComponentPluginCommandHandler {
     ctx: Arc<Context>,
@@ -390,4 +393,203 @@ impl ComponentPluginCommandHandler {
 
         Ok(())
     }
+
+    /// Reconciles the installed plugins of a deployed component with the plugins defined in the
+    /// application manifest, returns true if any installation was changed
+    pub async fn deploy_app_plugins(
+        &self,
+        component: &Component,
+        plugins: &[app_raw::PluginInstallation],
+        uninstall_extra_plugins: bool,
+    ) -> anyhow::Result<bool> {
+        let installations = self.installed_plugins(component).await?;
+        let mut changed = false;
+
+        for plugin in plugins {
+            match installations
+                .iter()
+                .find(|installation| installation.name == plugin.name)
+            {
+                Some(installation) if installation.version == plugin.version => {
+                    if installation.priority == plugin.priority
+                        && installation.parameters.len() == plugin.parameters.len()
+                        && plugin
+                            .parameters
+                            .iter()
+                            .all(|(key, value)| installation.parameters.get(key) == Some(value))
+                    {
+                        continue;
+                    }
+
+                    log_action(
+                        "Updating",
+                        format!(
+                            "plugin {} for component {}",
+                            plugin.name.log_color_highlight(),
+                            component.component_name.0.log_color_highlight()
+                        ),
+                    );
+                    self.update_plugin(
+                        component,
+                        &installation.id,
+                        &PluginInstallationUpdate {
+                            priority: plugin.priority,
+                            parameters: plugin.parameters.clone().into_iter().collect(),
+                        },
+                    )
+                    .await?;
+                }
+                Some(installation) => {
+                    log_action(
+                        "Reinstalling",
+                        format!(
+                            "plugin {} for component {}, version {} -> {}",
+                            plugin.name.log_color_highlight(),
+                            component.component_name.0.log_color_highlight(),
+                            installation.version.log_color_highlight(),
+                            plugin.version.log_color_highlight()
+                        ),
+                    );
+                    self.uninstall_plugin(component, &installation.id).await?;
+                    self.install_plugin(component, plugin).await?;
+                }
+                None => {
+                    log_action(
+                        "Installing",
+                        format!(
+                            "plugin {} version {} for component {}",
+                            plugin.name.log_color_highlight(),
+                            plugin.version.log_color_highlight(),
+                            component.component_name.0.log_color_highlight()
+                        ),
+                    );
+                    self.install_plugin(component, plugin).await?;
+                }
+            }
+            changed = true;
+        }
+
+        for installation in &installations {
+            if plugins
+                .iter()
+                .any(|plugin| plugin.name == installation.name)
+            {
+                continue;
+            }
+
+            if uninstall_extra_plugins {
+                log_warn_action(
+                    "Uninstalling",
+                    format!(
+                        "plugin {} from component {}, as it is not defined in the application manifest",
+                        installation.name.log_color_highlight(),
+                        component.component_name.0.log_color_highlight()
+                    ),
+                );
+                self.uninstall_plugin(component, &installation.id).await?;
+                changed = true;
+            } else {
+                log_warn(format!(
+                    "Plugin {} is installed for component {}, but it is not defined in the application manifest",
+                    installation.name.log_color_highlight(),
+                    component.component_name.0.log_color_highlight()
+                ));
+            }
+        }
+
+        Ok(changed)
+    }
+
+    async fn installed_plugins(
+        &self,
+        component: &Component,
+    ) -> anyhow::Result<Vec<PluginInstallation>> {
+        let component_id = &component.versioned_component_id.component_id;
+        let version = component.versioned_component_id.version.to_string();
+        match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => clients
+                .component
+                .get_installed_plugins(component_id, &version)
+                .await
+                .map_service_error(),
+            GolemClients::Cloud(clients) => clients
+                .component
+                .get_installed_plugins(component_id, &version)
+                .await
+                .map_service_error(),
+        }
+    }
+
+    async fn install_plugin(
+        &self,
+        component: &Component,
+        plugin: &app_raw::PluginInstallation,
+    ) -> anyhow::Result<()> {
+        let component_id = &component.versioned_component_id.component_id;
+        let installation = PluginInstallationCreation {
+            name: plugin.name.clone(),
+            version: plugin.version.clone(),
+            priority: plugin.priority,
+            parameters: plugin.parameters.clone().into_iter().collect(),
+        };
+        match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => clients
+                .component
+                .install_plugin(component_id, &installation)
+                .await
+                .map(|_| ())
+                .map_service_error(),
+            GolemClients::Cloud(clients) => clients
+                .component
+                .install_plugin(component_id, &installation)
+                .await
+                .map(|_| ())
+                .map_service_error(),
+        }
+    }
+
+    async fn update_plugin(
+        &self,
+        component: &Component,
+        installation_id: &Uuid,
+        update: &PluginInstallationUpdate,
+    ) -> anyhow::Result<()> {
+        let component_id = &component.versioned_component_id.component_id;
+        match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => clients
+                .component
+                .update_installed_plugin(component_id, installation_id, update)
+                .await
+                .map(|_| ())
+                .map_service_error(),
+            GolemClients::Cloud(clients) => clients
+                .component
+                .update_installed_plugin(component_id, installation_id, update)
+                .await
+                .map(|_| ())
+                .map_service_error(),
+        }
+    }
+
+    async fn uninstall_plugin(
+        &self,
+        component: &Component,
+        installation_id: &Uuid,
+    ) -> anyhow::Result<()> {
+        let component_id = &component.versioned_component_id.component_id;
+        match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => clients
+                .component
+                .uninstall_plugin(component_id, installation_id)
+                .await
+                .map(|_| ())
+                .map_service_error(),
+            GolemClients::Cloud(clients) => clients
+                .component
+                .uninstall_plugin(component_id, installation_id)
+                .await
+                .map(|_| ())
+                .map_service_error(),
+        }
+    }
 }
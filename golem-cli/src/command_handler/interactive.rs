@@ -66,6 +66,24 @@ impl InteractiveHandler {
         )
     }
 
+    pub fn confirm_bulk_worker_operation(
+        &self,
+        operation: &str,
+        component_name: &ComponentName,
+        number_of_workers: usize,
+        default_confirm: bool,
+    ) -> anyhow::Result<bool> {
+        self.confirm(
+            default_confirm,
+            format!(
+                "The filters matched {} worker(s) of component {}, do you want to {} all of them?",
+                number_of_workers.to_string().log_color_highlight(),
+                component_name.0.log_color_highlight(),
+                operation.log_color_warn()
+            ),
+        )
+    }
+
     pub fn confirm_update_to_latest(
         &self,
         component_name: &ComponentName,
@@ -17,7 +17,8 @@ mod stream_output;
 
 use crate::cloud::AccountId;
 use crate::command::shared_args::{
-    NewWorkerArgument, StreamArgs, WorkerFunctionArgument, WorkerFunctionName, WorkerNameArg,
+    NewWorkerArgument, StreamArgs, WorkerBulkArgs, WorkerFunctionArgument, WorkerFunctionName,
+    WorkerNameArg, WorkerRolloutArgs,
 };
 use crate::command::worker::WorkerSubcommand;
 use crate::command_handler::worker::stream::WorkerConnection;
@@ -26,12 +27,19 @@ use crate::context::{Context, GolemClients};
 use crate::error::service::{AnyhowMapServiceError, ServiceError};
 use crate::error::NonSuccessfulExit;
 use crate::fuzzy::{Error, FuzzySearch};
-use crate::log::{log_action, log_error_action, log_warn_action, logln, LogColorize, LogIndent};
+use crate::log::{
+    log_action, log_error_action, log_skipping_up_to_date, log_warn_action, logln, LogColorize,
+    LogIndent,
+};
 use crate::model::app::ApplicationComponentSelectMode;
+use crate::model::app_raw;
 use crate::model::component::{
-    function_params_types, show_exported_functions, AppComponentType, Component,
+    function_params_types, render_type, show_exported_functions, AppComponentType, Component,
+};
+use crate::model::deploy::{
+    WorkerRollout, WorkerRolloutAttempt, WorkerRolloutHealth, WorkerRolloutOutcome,
+    WorkerRolloutResult,
 };
-use crate::model::deploy::{TryUpdateAllWorkersResult, WorkerUpdateAttempt};
 use crate::model::invoke_result_view::InvokeResultView;
 use crate::model::text::fmt::{
     format_export, format_worker_name_match, log_error, log_fuzzy_match, log_text_view, log_warn,
@@ -42,13 +50,16 @@ use crate::model::text::help::{
 };
 use crate::model::text::worker::{WorkerCreateView, WorkerGetView};
 use crate::model::to_oss::ToOss;
+use crate::model::wave::type_wave_compatible;
 use crate::model::worker::fuzzy_match_function_name;
 use crate::model::{
-    ComponentName, ComponentNameMatchKind, IdempotencyKey, ProjectName, WorkerMetadata,
-    WorkerMetadataView, WorkerName, WorkerNameMatch, WorkerUpdateMode, WorkersMetadataResponseView,
+    ComponentName, ComponentNameMatchKind, IdempotencyKey, PathBufOrStdin, ProjectName,
+    ProjectNameAndId, WorkerBulkOperationResult, WorkerMetadata, WorkerMetadataView, WorkerName,
+    WorkerNameMatch, WorkerOperationAttempt, WorkerUpdateMode, WorkersMetadataResponseView,
 };
-use anyhow::{anyhow, bail};
+use anyhow::{anyhow, bail, Context as AnyhowContext};
 use colored::Colorize;
+use futures_util::{stream, StreamExt};
 use golem_client::api::{ComponentClient as ComponentClientOss, WorkerClient as WorkerClientOss};
 use golem_client::model::{
     InvokeParameters as InvokeParametersOss, InvokeResult, PublicOplogEntry,
@@ -67,12 +78,16 @@ use golem_cloud_client::model::{
     WorkerCreationRequest as WorkerCreationRequestCloud,
 };
 use golem_common::model::public_oplog::OplogCursor;
-use golem_wasm_rpc::json::OptionallyTypeAnnotatedValueJson;
+use golem_wasm_ast::analysis::AnalysedType;
+use golem_wasm_rpc::json::{OptionallyTypeAnnotatedValueJson, TypeAnnotatedValueJsonExtensions};
 use golem_wasm_rpc::parse_type_annotated_value;
+use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
 use itertools::{EitherOrBoth, Itertools};
 use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::time::timeout;
 use uuid::Uuid;
 
@@ -96,24 +111,33 @@ impl WorkerCommandHandler {
                 worker_name,
                 function_name,
                 arguments,
+                json_args,
+                args_file,
                 enqueue,
                 idempotency_key,
                 stream,
                 stream_args,
+                bulk_args,
             } => {
                 self.cmd_invoke(
                     worker_name,
                     &function_name,
                     arguments,
+                    json_args,
+                    args_file,
                     enqueue,
                     idempotency_key,
                     stream,
                     stream_args,
+                    bulk_args,
                 )
                 .await
             }
             WorkerSubcommand::Get { worker_name } => self.cmd_get(worker_name).await,
-            WorkerSubcommand::Delete { worker_name } => self.cmd_delete(worker_name).await,
+            WorkerSubcommand::Delete {
+                worker_name,
+                bulk_args,
+            } => self.cmd_delete(worker_name, bulk_args).await,
             WorkerSubcommand::List {
                 component_name,
                 filter: filters,
@@ -134,20 +158,29 @@ impl WorkerCommandHandler {
                 worker_name,
                 stream_args,
             } => self.cmd_stream(worker_name, stream_args).await,
-            WorkerSubcommand::Interrupt { worker_name } => self.cmd_interrupt(worker_name).await,
+            WorkerSubcommand::Interrupt {
+                worker_name,
+                bulk_args,
+            } => self.cmd_interrupt(worker_name, bulk_args).await,
             WorkerSubcommand::Update {
                 worker_name,
                 mode,
                 target_version,
+                wait,
+                wait_timeout,
             } => {
                 self.cmd_update(
                     worker_name,
                     mode.unwrap_or(WorkerUpdateMode::Automatic),
                     target_version,
+                    wait.then(|| Duration::from_secs(wait_timeout)),
                 )
                 .await
             }
-            WorkerSubcommand::Resume { worker_name } => self.cmd_resume(worker_name).await,
+            WorkerSubcommand::Resume {
+                worker_name,
+                bulk_args,
+            } => self.cmd_resume(worker_name, bulk_args).await,
             WorkerSubcommand::SimulateCrash { worker_name } => {
                 self.cmd_simulate_crash(worker_name).await
             }
@@ -240,10 +273,13 @@ impl WorkerCommandHandler {
         worker_name: WorkerNameArg,
         function_name: &WorkerFunctionName,
         arguments: Vec<WorkerFunctionArgument>,
+        json_args: bool,
+        args_file: Option<PathBufOrStdin>,
         enqueue: bool,
         idempotency_key: Option<IdempotencyKey>,
         stream: bool,
         stream_args: StreamArgs,
+        bulk_args: WorkerBulkArgs,
     ) -> anyhow::Result<()> {
         self.ctx.silence_app_context_init().await;
 
@@ -272,6 +308,11 @@ impl WorkerCommandHandler {
         };
 
         let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
+        let is_bulk = is_bulk_selection(&worker_name_match, &bulk_args)?;
+        if is_bulk && stream {
+            log_error("Streaming is not supported when invoking multiple workers");
+            bail!(NonSuccessfulExit);
+        }
 
         let component = self
             .ctx
@@ -337,6 +378,44 @@ impl WorkerCommandHandler {
             }
         };
 
+        let arguments = invoke_args(&component, &function_name, arguments, json_args, args_file)?;
+
+        if is_bulk {
+            let handler = &*self;
+            let component = &component;
+            let function_name = &function_name;
+            let arguments = &arguments;
+            let idempotency_key = &idempotency_key;
+            let (operation, action) = if enqueue {
+                ("enqueue an invocation for", "Enqueueing invocation for")
+            } else {
+                ("invoke", "Invoking")
+            };
+            return handler
+                .bulk_worker_operation(
+                    component,
+                    &bulk_args,
+                    operation,
+                    action,
+                    true,
+                    move |worker_name| async move {
+                        handler
+                            .invoke_worker(
+                                component,
+                                Some(&worker_name),
+                                function_name,
+                                arguments.clone(),
+                                idempotency_key.clone(),
+                                enqueue,
+                                None,
+                            )
+                            .await
+                            .map(|_| ())
+                    },
+                )
+                .await;
+        }
+
         if enqueue {
             log_action(
                 "Enqueueing",
@@ -357,8 +436,6 @@ impl WorkerCommandHandler {
             );
         }
 
-        let arguments = wave_args_to_invoke_args(&component, &function_name, arguments)?;
-
         let result = self
             .invoke_worker(
                 &component,
@@ -749,13 +826,36 @@ impl WorkerCommandHandler {
         Ok(())
     }
 
-    async fn cmd_interrupt(&mut self, worker_name: WorkerNameArg) -> anyhow::Result<()> {
+    async fn cmd_interrupt(
+        &mut self,
+        worker_name: WorkerNameArg,
+        bulk_args: WorkerBulkArgs,
+    ) -> anyhow::Result<()> {
         self.ctx.silence_app_context_init().await;
         let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
         let (component, worker_name) = self
             .component_by_worker_name_match(&worker_name_match)
             .await?;
 
+        if is_bulk_selection(&worker_name_match, &bulk_args)? {
+            let handler = &*self;
+            let component = &component;
+            return handler
+                .bulk_worker_operation(
+                    component,
+                    &bulk_args,
+                    "interrupt",
+                    "Interrupting",
+                    false,
+                    move |worker_name| async move {
+                        handler
+                            .interrupt_worker(component, &worker_name, false)
+                            .await
+                    },
+                )
+                .await;
+        }
+
         log_action(
             "Interrupting",
             format!("worker {}", format_worker_name_match(&worker_name_match)),
@@ -772,13 +872,34 @@ impl WorkerCommandHandler {
         Ok(())
     }
 
-    async fn cmd_resume(&mut self, worker_name: WorkerNameArg) -> anyhow::Result<()> {
+    async fn cmd_resume(
+        &mut self,
+        worker_name: WorkerNameArg,
+        bulk_args: WorkerBulkArgs,
+    ) -> anyhow::Result<()> {
         self.ctx.silence_app_context_init().await;
         let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
         let (component, worker_name) = self
             .component_by_worker_name_match(&worker_name_match)
             .await?;
 
+        if is_bulk_selection(&worker_name_match, &bulk_args)? {
+            let handler = &*self;
+            let component = &component;
+            return handler
+                .bulk_worker_operation(
+                    component,
+                    &bulk_args,
+                    "resume",
+                    "Resuming",
+                    true,
+                    move |worker_name| async move {
+                        handler.resume_worker(component, &worker_name).await
+                    },
+                )
+                .await;
+        }
+
         log_action(
             "Resuming",
             format!("worker {}", format_worker_name_match(&worker_name_match)),
@@ -799,6 +920,7 @@ impl WorkerCommandHandler {
         worker_name: WorkerNameArg,
         mode: WorkerUpdateMode,
         target_version: Option<u64>,
+        wait_timeout: Option<Duration>,
     ) -> anyhow::Result<()> {
         self.ctx.silence_app_context_init().await;
         let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
@@ -851,6 +973,40 @@ impl WorkerCommandHandler {
         )
         .await?;
 
+        if let Some(wait_timeout) = wait_timeout {
+            let result = self
+                .wait_for_healthy_worker(
+                    &component.component_name,
+                    component.versioned_component_id.component_id,
+                    &worker_name,
+                    target_version,
+                    wait_timeout,
+                )
+                .await;
+
+            let (outcome, error) = match result {
+                Ok(()) => (WorkerRolloutOutcome::Succeeded, None),
+                Err(error) => (WorkerRolloutOutcome::Failed, Some(error.to_string())),
+            };
+            let result = WorkerRolloutResult {
+                attempts: vec![WorkerRolloutAttempt {
+                    component_name: component.component_name.clone(),
+                    worker_name,
+                    target_version,
+                    canary: false,
+                    outcome,
+                    error,
+                }],
+                aborted: None,
+            };
+
+            self.ctx.log_handler().log_view(&result);
+
+            if outcome == WorkerRolloutOutcome::Failed {
+                bail!(NonSuccessfulExit);
+            }
+        }
+
         Ok(())
     }
 
@@ -895,13 +1051,34 @@ impl WorkerCommandHandler {
         Ok(())
     }
 
-    async fn cmd_delete(&mut self, worker_name: WorkerNameArg) -> anyhow::Result<()> {
+    async fn cmd_delete(
+        &mut self,
+        worker_name: WorkerNameArg,
+        bulk_args: WorkerBulkArgs,
+    ) -> anyhow::Result<()> {
         self.ctx.silence_app_context_init().await;
         let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
         let (component, worker_name) = self
             .component_by_worker_name_match(&worker_name_match)
             .await?;
 
+        if is_bulk_selection(&worker_name_match, &bulk_args)? {
+            let handler = &*self;
+            let component_id = component.versioned_component_id.component_id;
+            return handler
+                .bulk_worker_operation(
+                    &component,
+                    &bulk_args,
+                    "delete",
+                    "Deleting",
+                    false,
+                    move |worker_name| async move {
+                        handler.delete(component_id, &worker_name.0).await
+                    },
+                )
+                .await;
+        }
+
         log_warn_action(
             "Deleting",
             format!("worker {}", format_worker_name_match(&worker_name_match)),
@@ -958,8 +1135,115 @@ impl WorkerCommandHandler {
         }
     }
 
+    /// Creates the workers defined in the application manifest for the selected components if they
+    /// do not exist yet, existing workers are only checked for differences in args and env
+    pub async fn deploy_app_workers(
+        &self,
+        project: Option<&ProjectNameAndId>,
+    ) -> anyhow::Result<()> {
+        let component_workers = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+            app_ctx
+                .selected_component_names()
+                .iter()
+                .filter_map(|component_name| {
+                    let properties = app_ctx
+                        .application
+                        .component_properties(component_name, self.ctx.build_profile());
+                    (properties.is_deployable() && !properties.workers.is_empty()).then(|| {
+                        (
+                            ComponentName::from(component_name.as_str()),
+                            app_ctx.application.component_workers(
+                                component_name,
+                                self.ctx.build_profile(),
+                                self.ctx.environment(),
+                            ),
+                        )
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+
+        if component_workers.is_empty() {
+            return Ok(());
+        }
+
+        log_action("Deploying", "workers");
+        let _indent = LogIndent::new();
+
+        for (component_name, workers) in component_workers {
+            let Some(component) = self
+                .ctx
+                .component_handler()
+                .component(project, (&component_name).into(), None)
+                .await?
+            else {
+                log_warn(format!(
+                    "Component {} is not deployed, skipping its workers",
+                    component_name.0.log_color_highlight()
+                ));
+                continue;
+            };
+
+            if component.component_type == AppComponentType::Ephemeral {
+                log_warn(format!(
+                    "Component {} is ephemeral, skipping its named workers",
+                    component_name.0.log_color_highlight()
+                ));
+                continue;
+            }
+
+            let component_id = component.versioned_component_id.component_id;
+            for worker in workers {
+                let worker_name = WorkerName::from(worker.name.as_str());
+                let worker_subject = format!(
+                    "worker {}/{}",
+                    component_name.0.log_color_highlight(),
+                    worker.name.log_color_highlight()
+                );
+
+                match self
+                    .opt_worker_metadata(component_id, &component_name, &worker_name)
+                    .await?
+                {
+                    Some(metadata) => {
+                        let drift = worker_drift(&worker, &metadata);
+                        if drift.is_empty() {
+                            log_skipping_up_to_date(worker_subject);
+                        } else {
+                            log_warn_action(
+                                "Found",
+                                format!(
+                                    "differences for existing {}, recreate the worker to apply them:",
+                                    worker_subject
+                                ),
+                            );
+                            let _indent = LogIndent::new();
+                            for line in drift {
+                                logln(format!("- {}", line));
+                            }
+                        }
+                    }
+                    None => {
+                        log_action("Creating", worker_subject);
+                        self.new_worker(
+                            component_id,
+                            worker.name.clone(),
+                            worker.args.clone(),
+                            worker.env.clone().into_iter().collect(),
+                        )
+                        .await?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     pub async fn invoke_worker(
-        &mut self,
+        &self,
         component: &Component,
         worker_name: Option<&WorkerName>,
         function_name: &str,
@@ -1168,6 +1452,30 @@ impl WorkerCommandHandler {
         Ok(result)
     }
 
+    async fn opt_worker_metadata(
+        &self,
+        component_id: Uuid,
+        component_name: &ComponentName,
+        worker_name: &WorkerName,
+    ) -> anyhow::Result<Option<WorkerMetadata>> {
+        let result = match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => clients
+                .worker
+                .get_worker_metadata(&component_id, &worker_name.0)
+                .await
+                .map_service_error_not_found_as_opt()?
+                .map(|result| WorkerMetadata::from_oss(component_name.clone(), result)),
+            GolemClients::Cloud(clients) => clients
+                .worker
+                .get_worker_metadata(&component_id, &worker_name.0)
+                .await
+                .map_service_error_not_found_as_opt()?
+                .map(|result| WorkerMetadata::from_cloud(component_name.clone(), result)),
+        };
+
+        Ok(result)
+    }
+
     async fn delete(&self, component_id: Uuid, worker_name: &str) -> anyhow::Result<()> {
         match self.ctx.golem_clients().await? {
             GolemClients::Oss(clients) => clients
@@ -1191,7 +1499,8 @@ impl WorkerCommandHandler {
         component_id: Uuid,
         update_mode: WorkerUpdateMode,
         target_version: u64,
-    ) -> anyhow::Result<TryUpdateAllWorkersResult> {
+        rollout: &WorkerRolloutArgs,
+    ) -> anyhow::Result<WorkerRolloutResult> {
         let (workers, _) = self
             .list_component_workers(component_name, component_id, None, None, None, false)
             .await?;
@@ -1204,7 +1513,7 @@ impl WorkerCommandHandler {
                     component_name
                 ),
             );
-            return Ok(TryUpdateAllWorkersResult::default());
+            return Ok(WorkerRolloutResult::default());
         }
 
         log_action(
@@ -1218,39 +1527,122 @@ impl WorkerCommandHandler {
         );
         let _indent = LogIndent::new();
 
-        let mut update_results = TryUpdateAllWorkersResult::default();
-        for worker in workers {
-            let result = self
-                .update_worker(
+        self.rollout_workers(
+            component_name,
+            component_id,
+            workers,
+            target_version,
+            rollout,
+            move |worker| async move {
+                self.update_worker(
                     component_name,
-                    worker.worker_id.component_id.0,
+                    component_id,
                     &worker.worker_id.worker_name,
                     update_mode,
                     target_version,
                 )
-                .await;
+                .await
+            },
+        )
+        .await
+    }
 
-            match result {
-                Ok(_) => {
-                    update_results.triggered.push(WorkerUpdateAttempt {
-                        component_name: component_name.clone(),
-                        target_version,
-                        worker_name: worker.worker_id.worker_name.as_str().into(),
-                        error: None,
-                    });
-                }
-                Err(error) => {
-                    update_results.triggered.push(WorkerUpdateAttempt {
-                        component_name: component_name.clone(),
-                        target_version,
-                        worker_name: worker.worker_id.worker_name.as_str().into(),
-                        error: Some(error.to_string()),
-                    });
-                }
+    /// Executes the operation on the workers of the component matching the filters, with
+    /// limited concurrency, then shows the per-worker results. The confirmation of destructive
+    /// operations defaults to no.
+    async fn bulk_worker_operation<F, Fut>(
+        &self,
+        component: &Component,
+        bulk_args: &WorkerBulkArgs,
+        operation: &str,
+        action: &str,
+        default_confirm: bool,
+        operation_fn: F,
+    ) -> anyhow::Result<()>
+    where
+        F: Fn(WorkerName) -> Fut,
+        Fut: Future<Output = anyhow::Result<()>>,
+    {
+        let component_name = &component.component_name;
+        let (workers, _) = self
+            .list_component_workers(
+                component_name,
+                component.versioned_component_id.component_id,
+                (!bulk_args.filter.is_empty()).then_some(bulk_args.filter.as_slice()),
+                None,
+                None,
+                false,
+            )
+            .await?;
+
+        if workers.is_empty() {
+            log_warn_action(
+                "Skipping",
+                format!(
+                    "component {}, no workers matched the filters",
+                    component_name.0.log_color_highlight()
+                ),
+            );
+            return Ok(());
+        }
+
+        if !self
+            .ctx
+            .interactive_handler()
+            .confirm_bulk_worker_operation(
+                operation,
+                component_name,
+                workers.len(),
+                default_confirm,
+            )?
+        {
+            bail!(NonSuccessfulExit);
+        }
+
+        log_action(
+            action,
+            format!(
+                "{} worker(s) of component {}",
+                workers.len().to_string().log_color_highlight(),
+                component_name.0.log_color_highlight()
+            ),
+        );
+
+        let mut attempts = stream::iter(workers)
+            .map(|worker| {
+                let worker_name: WorkerName = worker.worker_id.worker_name.as_str().into();
+                let result = operation_fn(worker_name.clone());
+                async move { (worker_name, result.await) }
+            })
+            .buffer_unordered(bulk_args.concurrency.get())
+            .collect::<Vec<_>>()
+            .await;
+        attempts.sort_by(|(left, _), (right, _)| left.0.cmp(&right.0));
+
+        let mut result = WorkerBulkOperationResult::default();
+        for (worker_name, attempt) in attempts {
+            match attempt {
+                Ok(()) => result.succeeded.push(WorkerOperationAttempt {
+                    component_name: component_name.clone(),
+                    worker_name,
+                    error: None,
+                }),
+                Err(error) => result.failed.push(WorkerOperationAttempt {
+                    component_name: component_name.clone(),
+                    worker_name,
+                    error: Some(error.to_string()),
+                }),
             }
         }
 
-        Ok(update_results)
+        logln("");
+        self.ctx.log_handler().log_view(&result);
+
+        if !result.failed.is_empty() {
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
     }
 
     async fn update_worker(
@@ -1333,7 +1725,9 @@ impl WorkerCommandHandler {
         &self,
         component_name: &ComponentName,
         component_id: Uuid,
-    ) -> anyhow::Result<()> {
+        target_version: u64,
+        rollout: &WorkerRolloutArgs,
+    ) -> anyhow::Result<WorkerRolloutResult> {
         let (workers, _) = self
             .list_component_workers(component_name, component_id, None, None, None, false)
             .await?;
@@ -1346,7 +1740,7 @@ impl WorkerCommandHandler {
                     component_name
                 ),
             );
-            return Ok(());
+            return Ok(WorkerRolloutResult::default());
         }
 
         log_action(
@@ -1367,11 +1761,134 @@ impl WorkerCommandHandler {
             bail!(NonSuccessfulExit);
         }
 
-        for worker in workers {
-            self.redeploy_worker(component_name, worker).await?;
+        self.rollout_workers(
+            component_name,
+            component_id,
+            workers,
+            target_version,
+            rollout,
+            move |worker| async move { self.redeploy_worker(component_name, worker).await },
+        )
+        .await
+    }
+
+    /// Updates or redeploys the workers concurrently. Canary workers are rolled out first, and
+    /// the rest of the workers are only rolled out if all the canaries became healthy. The
+    /// rollout is aborted when the failures exceed the abort threshold.
+    async fn rollout_workers<F, Fut>(
+        &self,
+        component_name: &ComponentName,
+        component_id: Uuid,
+        mut workers: Vec<WorkerMetadata>,
+        target_version: u64,
+        rollout: &WorkerRolloutArgs,
+        rollout_worker: F,
+    ) -> anyhow::Result<WorkerRolloutResult>
+    where
+        F: Fn(WorkerMetadata) -> Fut,
+        Fut: Future<Output = anyhow::Result<()>>,
+    {
+        let mut rollout =
+            WorkerRollout::new(component_name, target_version, rollout, workers.len());
+        let canary_count = rollout.canary_count();
+        let non_canaries = workers.split_off(canary_count);
+        let canaries = workers;
+
+        let aborted = AtomicBool::new(false);
+
+        for (canary, workers) in [(true, canaries), (false, non_canaries)] {
+            if workers.is_empty() {
+                continue;
+            }
+            if canary {
+                log_action(
+                    "Rolling out",
+                    format!(
+                        "{} canary worker(s) first",
+                        canary_count.to_string().log_color_highlight()
+                    ),
+                );
+            } else if canary_count > 0 && !rollout.is_aborted() {
+                log_action("Rolling out", "the remaining workers");
+            }
+
+            let health_timeout = rollout.health_timeout(canary);
+
+            let mut attempts = stream::iter(workers)
+                .map(|worker| {
+                    let worker_name: WorkerName = worker.worker_id.worker_name.as_str().into();
+                    let aborted = &aborted;
+                    let rollout_worker = &rollout_worker;
+                    async move {
+                        if aborted.load(Ordering::Relaxed) {
+                            return (worker_name, None);
+                        }
+
+                        let outcome = async {
+                            rollout_worker(worker).await?;
+                            if let Some(health_timeout) = health_timeout {
+                                self.wait_for_healthy_worker(
+                                    component_name,
+                                    component_id,
+                                    &worker_name,
+                                    target_version,
+                                    health_timeout,
+                                )
+                                .await?;
+                            }
+                            Ok::<_, anyhow::Error>(())
+                        }
+                        .await;
+
+                        (worker_name, Some(outcome))
+                    }
+                })
+                .buffer_unordered(rollout.concurrency());
+
+            while let Some((worker_name, outcome)) = attempts.next().await {
+                let outcome = outcome.map(|outcome| outcome.map_err(|error| error.to_string()));
+                if let Some(reason) = rollout.record(worker_name, canary, outcome) {
+                    log_error_action("Aborting", format!("rollout, {}", reason));
+                    aborted.store(true, Ordering::Relaxed);
+                }
+            }
         }
 
-        Ok(())
+        Ok(rollout.finish())
+    }
+
+    /// Polls the worker metadata until the worker is running the target version, fails if the
+    /// worker or the update failed, or the worker did not become healthy within the timeout
+    async fn wait_for_healthy_worker(
+        &self,
+        component_name: &ComponentName,
+        component_id: Uuid,
+        worker_name: &WorkerName,
+        target_version: u64,
+        health_timeout: Duration,
+    ) -> anyhow::Result<()> {
+        let started_at = Instant::now();
+        loop {
+            let metadata = self
+                .worker_metadata(component_id, component_name, worker_name)
+                .await?;
+
+            match WorkerRolloutHealth::of(&metadata, target_version) {
+                WorkerRolloutHealth::Healthy => return Ok(()),
+                WorkerRolloutHealth::Failed(reason) => bail!(reason),
+                WorkerRolloutHealth::Pending => {}
+            }
+            if started_at.elapsed() >= health_timeout {
+                bail!(
+                    "Worker did not become healthy in {} seconds, status: {}, component version: {}",
+                    health_timeout.as_secs(),
+                    metadata.status,
+                    metadata.component_version
+                );
+            }
+
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
     }
 
     async fn redeploy_worker(
@@ -1542,7 +2059,7 @@ impl WorkerCommandHandler {
     }
 
     async fn resume_worker(
-        &mut self,
+        &self,
         component: &Component,
         worker_name: &WorkerName,
     ) -> anyhow::Result<()> {
@@ -1572,7 +2089,7 @@ impl WorkerCommandHandler {
     }
 
     async fn interrupt_worker(
-        &mut self,
+        &self,
         component: &Component,
         worker_name: &WorkerName,
         recover_immediately: bool,
@@ -1826,66 +2343,194 @@ impl WorkerCommandHandler {
     }
 }
 
+fn invoke_args(
+    component: &Component,
+    function_name: &str,
+    arguments: Vec<WorkerFunctionArgument>,
+    json_args: bool,
+    args_file: Option<PathBufOrStdin>,
+) -> anyhow::Result<Vec<OptionallyTypeAnnotatedValueJson>> {
+    match args_file {
+        Some(args_file) => {
+            json_args_to_invoke_args(component, function_name, read_json_args(&args_file)?)
+        }
+        None if json_args => {
+            let json_args = arguments
+                .iter()
+                .map(|argument| {
+                    serde_json::from_str::<serde_json::Value>(argument).with_context(|| {
+                        anyhow!(
+                            "Failed to parse argument as JSON: {}",
+                            argument.log_color_error_highlight()
+                        )
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            json_args_to_invoke_args(component, function_name, json_args)
+        }
+        None => wave_args_to_invoke_args(component, function_name, arguments),
+    }
+}
+
+/// Reads the arguments from a file or from STDIN as a JSON array
+fn read_json_args(args_file: &PathBufOrStdin) -> anyhow::Result<Vec<serde_json::Value>> {
+    serde_json::from_str::<Vec<serde_json::Value>>(&args_file.read_to_string()?).with_context(
+        || {
+            anyhow!(
+                "Failed to parse the arguments from {}, expected a JSON array",
+                match args_file {
+                    PathBufOrStdin::Path(path) => path.log_color_highlight(),
+                    PathBufOrStdin::Stdin => "STDIN".log_color_highlight(),
+                }
+            )
+        },
+    )
+}
+
 fn wave_args_to_invoke_args(
     component: &Component,
     function_name: &str,
     wave_args: Vec<String>,
 ) -> anyhow::Result<Vec<OptionallyTypeAnnotatedValueJson>> {
     let types = function_params_types(component, function_name)?;
+    check_argument_count(&types, &wave_args)?;
 
-    if types.len() != wave_args.len() {
-        logln("");
-        log_error(format!(
-            "Wrong number of parameters: expected {}, got {}",
-            types.len(),
-            wave_args.len()
-        ));
-        logln("");
-        log_text_view(&ParameterErrorTableView(
-            types
-                .into_iter()
-                .zip_longest(wave_args)
-                .map(|zipped| match zipped {
-                    EitherOrBoth::Both(typ, value) => ArgumentError {
-                        type_: Some(typ.clone()),
-                        value: Some(value),
-                        error: None,
-                    },
-                    EitherOrBoth::Left(typ) => ArgumentError {
-                        type_: Some(typ.clone()),
-                        value: None,
-                        error: Some("missing argument".log_color_error().to_string()),
-                    },
-                    EitherOrBoth::Right(value) => ArgumentError {
-                        type_: None,
-                        value: Some(value),
-                        error: Some("extra argument".log_color_error().to_string()),
-                    },
-                })
-                .collect::<Vec<_>>(),
-        ));
-        logln("");
-        bail!(NonSuccessfulExit);
+    let type_annotated_values = wave_args
+        .iter()
+        .zip(types.iter())
+        .map(|(wave, typ)| parse_wave_arg(typ, wave))
+        .collect::<Vec<_>>();
+
+    to_invoke_args("WAVE", types, wave_args, type_annotated_values)
+}
+
+/// WAVE does not support resource handles, so handle parameters are accepted as the opaque
+/// `<worker-uri>/<resource-id>` URIs rendered in the results of previous invocations
+fn parse_wave_arg(typ: &AnalysedType, wave: &str) -> Result<TypeAnnotatedValue, String> {
+    match typ {
+        AnalysedType::Handle(_) => {
+            let uri = wave.trim().trim_matches('"');
+            TypeAnnotatedValue::parse_with_type(&serde_json::Value::String(uri.to_string()), typ)
+                .map_err(|errors| errors.join("\n"))
+        }
+        typ if !type_wave_compatible(typ) => Err(
+            "Resource handles nested in other values are not supported in WAVE arguments, use --json-args and pass the handles as JSON strings"
+                .to_string(),
+        ),
+        typ => parse_type_annotated_value(typ, wave),
     }
+}
 
-    let type_annotated_values = wave_args
+fn json_args_to_invoke_args(
+    component: &Component,
+    function_name: &str,
+    json_args: Vec<serde_json::Value>,
+) -> anyhow::Result<Vec<OptionallyTypeAnnotatedValueJson>> {
+    parse_json_args(function_params_types(component, function_name)?, json_args)
+}
+
+fn parse_json_args(
+    types: Vec<&AnalysedType>,
+    json_args: Vec<serde_json::Value>,
+) -> anyhow::Result<Vec<OptionallyTypeAnnotatedValueJson>> {
+    let rendered_args = json_args
+        .iter()
+        .map(|json| json.to_string())
+        .collect::<Vec<_>>();
+    check_argument_count(&types, &rendered_args)?;
+
+    let type_annotated_values = json_args
         .iter()
         .zip(types.iter())
-        .map(|(wave, typ)| parse_type_annotated_value(typ, wave))
+        .map(|(json, typ)| parse_json_arg(typ, json))
         .collect::<Vec<_>>();
 
+    to_invoke_args("JSON", types, rendered_args, type_annotated_values)
+}
+
+/// Parses a plain JSON argument, or a type annotated one in the `{"typ": ..., "value": ...}`
+/// form, where the annotated type has to match the parameter type
+fn parse_json_arg(
+    typ: &AnalysedType,
+    json: &serde_json::Value,
+) -> Result<TypeAnnotatedValue, String> {
+    let value = match json.as_object() {
+        Some(object)
+            if object.len() == 2 && object.contains_key("typ") && object.contains_key("value") =>
+        {
+            let annotated_type = serde_json::from_value::<AnalysedType>(object["typ"].clone())
+                .map_err(|err| format!("Invalid type annotation: {}", err))?;
+            if &annotated_type != typ {
+                return Err(format!(
+                    "Type annotation does not match the parameter type, got: {}",
+                    render_type(&annotated_type)
+                ));
+            }
+            &object["value"]
+        }
+        _ => json,
+    };
+
+    TypeAnnotatedValue::parse_with_type(value, typ).map_err(|errors| errors.join("\n"))
+}
+
+fn check_argument_count(types: &[&AnalysedType], args: &[String]) -> anyhow::Result<()> {
+    if types.len() == args.len() {
+        return Ok(());
+    }
+
+    logln("");
+    log_error(format!(
+        "Wrong number of parameters: expected {}, got {}",
+        types.len(),
+        args.len()
+    ));
+    logln("");
+    log_text_view(&ParameterErrorTableView(
+        types
+            .iter()
+            .zip_longest(args)
+            .map(|zipped| match zipped {
+                EitherOrBoth::Both(typ, value) => ArgumentError {
+                    type_: Some((*typ).clone()),
+                    value: Some(value.clone()),
+                    error: None,
+                },
+                EitherOrBoth::Left(typ) => ArgumentError {
+                    type_: Some((*typ).clone()),
+                    value: None,
+                    error: Some("missing argument".log_color_error().to_string()),
+                },
+                EitherOrBoth::Right(value) => ArgumentError {
+                    type_: None,
+                    value: Some(value.clone()),
+                    error: Some("extra argument".log_color_error().to_string()),
+                },
+            })
+            .collect::<Vec<_>>(),
+    ));
+    logln("");
+    bail!(NonSuccessfulExit);
+}
+
+fn to_invoke_args(
+    args_format: &str,
+    types: Vec<&AnalysedType>,
+    args: Vec<String>,
+    type_annotated_values: Vec<Result<TypeAnnotatedValue, String>>,
+) -> anyhow::Result<Vec<OptionallyTypeAnnotatedValueJson>> {
     if type_annotated_values
         .iter()
         .any(|parse_result| parse_result.is_err())
     {
         logln("");
-        log_error("Argument WAVE parse error(s)!");
+        log_error(format!("Argument {} parse error(s)!", args_format));
         logln("");
         log_text_view(&ParameterErrorTableView(
             type_annotated_values
                 .into_iter()
                 .zip(types)
-                .zip(wave_args)
+                .zip(args)
                 .map(|((parsed, typ), value)| (parsed, typ, value))
                 .map(|(parsed, typ, value)| ArgumentError {
                     type_: Some(typ.clone()),
@@ -1910,6 +2555,30 @@ fn wave_args_to_invoke_args(
         .map_err(|err| anyhow!("Failed to convert type annotated value: {err}"))
 }
 
+/// Multiple workers are selected by using `*` as the worker name, optionally with filters
+fn is_bulk_selection(
+    worker_name_match: &WorkerNameMatch,
+    bulk_args: &WorkerBulkArgs,
+) -> anyhow::Result<bool> {
+    let is_bulk = worker_name_match
+        .worker_name
+        .as_ref()
+        .is_some_and(|worker_name| worker_name.0 == "*");
+
+    if !is_bulk && !bulk_args.filter.is_empty() {
+        log_error(format!(
+            "Worker filters can only be used with {} as the worker name, e.g. {}",
+            "*".log_color_highlight(),
+            "<COMPONENT>/*".log_color_highlight()
+        ));
+        logln("");
+        log_text_view(&WorkerNameHelp);
+        bail!(NonSuccessfulExit);
+    }
+
+    Ok(is_bulk)
+}
+
 fn scan_cursor_to_string(cursor: &ScanCursor) -> String {
     format!("{}/{}", cursor.layer, cursor.cursor)
 }
@@ -1960,3 +2629,169 @@ fn parse_worker_error(status: u16, body: Vec<u8>) -> ServiceError {
         }
     }
 }
+
+/// Describes the differences between a worker defined in the application manifest and an
+/// existing worker, environment variables provided by Golem are ignored
+fn worker_drift(worker: &app_raw::Worker, metadata: &WorkerMetadata) -> Vec<String> {
+    let mut drift = Vec::new();
+
+    if worker.args != metadata.args {
+        drift.push(format!(
+            "args: [{}], manifest: [{}]",
+            metadata.args.join(", "),
+            worker.args.join(", ")
+        ));
+    }
+
+    for (key, value) in &worker.env {
+        match metadata.env.get(key) {
+            Some(existing_value) if existing_value == value => {}
+            Some(existing_value) => drift.push(format!(
+                "env {}: {}, manifest: {}",
+                key, existing_value, value
+            )),
+            None => drift.push(format!("env {}: missing, manifest: {}", key, value)),
+        }
+    }
+
+    for key in metadata
+        .env
+        .keys()
+        .filter(|key| !worker.env.contains_key(*key) && !key.starts_with("GOLEM_"))
+        .sorted()
+    {
+        drift.push(format!("env {}: not defined in the manifest", key));
+    }
+
+    drift
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command_handler::worker::{
+        parse_json_arg, parse_json_args, parse_wave_arg, read_json_args,
+    };
+    use crate::fs;
+    use crate::model::wave::render_value_with_handles;
+    use crate::model::PathBufOrStdin;
+    use assert2::check;
+    use golem_wasm_ast::analysis::analysed_type::{field, handle, list, record, str, u32};
+    use golem_wasm_ast::analysis::{AnalysedResourceId, AnalysedResourceMode, AnalysedType};
+    use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+    use golem_wasm_rpc::{Uri, Value};
+    use serde_json::json;
+    use test_r::test;
+
+    fn annotated(typ: &AnalysedType, value: serde_json::Value) -> serde_json::Value {
+        json!({
+            "typ": serde_json::to_value(typ).unwrap(),
+            "value": value
+        })
+    }
+
+    #[test]
+    fn parse_plain_and_annotated_json_args() {
+        let typ = record(vec![field("name", str()), field("ids", list(u32()))]);
+        let value = json!({"name": "cart", "ids": [1, 2]});
+
+        let plain = parse_json_arg(&typ, &value).unwrap();
+        let annotated = parse_json_arg(&typ, &annotated(&typ, value)).unwrap();
+        check!(plain == annotated);
+
+        check!(parse_json_arg(&typ, &json!({"name": "cart"})).is_err());
+    }
+
+    #[test]
+    fn parse_json_arg_with_mismatched_annotation() {
+        let err = parse_json_arg(&u32(), &annotated(&str(), json!("1"))).unwrap_err();
+        check!(err.starts_with("Type annotation does not match the parameter type"));
+
+        let err = parse_json_arg(&u32(), &json!({"typ": "unknown", "value": 1})).unwrap_err();
+        check!(err.starts_with("Invalid type annotation"));
+    }
+
+    #[test]
+    fn records_with_typ_and_value_fields_have_to_be_annotated() {
+        let typ = record(vec![field("typ", str()), field("value", u32())]);
+
+        check!(parse_json_arg(&typ, &json!({"typ": "a", "value": 1})).is_err());
+        check!(parse_json_arg(&typ, &annotated(&typ, json!({"typ": "a", "value": 1}))).is_ok());
+    }
+
+    #[test]
+    fn parse_json_args_from_args_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let args_file = dir.path().join("args.json");
+        let types = [str(), u32()];
+        let types = types.iter().collect::<Vec<_>>();
+
+        fs::write_str(
+            &args_file,
+            serde_json::to_string(&json!(["cart", annotated(&u32(), json!(1))])).unwrap(),
+        )
+        .unwrap();
+        let annotated_args = parse_json_args(
+            types.clone(),
+            read_json_args(&PathBufOrStdin::Path(args_file.clone())).unwrap(),
+        )
+        .unwrap();
+        let plain_args = parse_json_args(types.clone(), vec![json!("cart"), json!(1)]).unwrap();
+        check!(
+            serde_json::to_value(&annotated_args).unwrap()
+                == serde_json::to_value(&plain_args).unwrap()
+        );
+
+        check!(parse_json_args(types.clone(), vec![json!("cart")]).is_err());
+        check!(parse_json_args(types.clone(), vec![json!("cart"), json!(1), json!(2)]).is_err());
+        check!(parse_json_args(types, vec![json!("cart"), json!("1")]).is_err());
+
+        fs::write_str(&args_file, r#"{"args": ["cart", 1]}"#).unwrap();
+        check!(read_json_args(&PathBufOrStdin::Path(args_file)).is_err());
+    }
+
+    #[test]
+    fn parse_rendered_handle_wave_arg() {
+        let typ = handle(AnalysedResourceId(0), AnalysedResourceMode::Borrowed);
+        let worker_uri = "urn:worker:4b4e9c9a-2f5b-4b0e-9d2a-6f1c0e6c8a10/counter-worker";
+        let rendered = render_value_with_handles(
+            &Value::Handle {
+                uri: Uri {
+                    value: worker_uri.to_string(),
+                },
+                resource_id: 3,
+            },
+            &typ,
+        )
+        .unwrap();
+        check!(rendered == format!("{}/3", worker_uri));
+
+        for wave in [rendered.clone(), format!(" \"{}\" ", rendered)] {
+            let parsed = match parse_wave_arg(&typ, &wave).unwrap() {
+                TypeAnnotatedValue::Handle(parsed) => parsed,
+                other => panic!("Expected a handle, got: {:?}", other),
+            };
+            check!(parsed.uri == worker_uri);
+            check!(parsed.resource_id == 3);
+        }
+
+        check!(parse_wave_arg(&typ, worker_uri).is_err());
+    }
+
+    #[test]
+    fn parse_nested_handle_wave_arg() {
+        let typ = list(handle(AnalysedResourceId(0), AnalysedResourceMode::Owned));
+
+        let err = parse_wave_arg(&typ, "[urn:worker:component/counter-worker/3]").unwrap_err();
+        check!(err.contains("--json-args"));
+    }
+
+    #[test]
+    fn parse_wave_arg_without_handles() {
+        let typ = record(vec![field("name", str()), field("ids", list(u32()))]);
+
+        check!(
+            parse_wave_arg(&typ, r#"{name: "cart", ids: [1, 2]}"#).unwrap()
+                == parse_json_arg(&typ, &json!({"name": "cart", "ids": [1, 2]})).unwrap()
+        );
+    }
+}
@@ -17,15 +17,20 @@ use crate::auth::{Auth, CloudAuthentication};
 use crate::cloud::{AccountId, CloudAuthenticationConfig};
 use crate::command::GolemCliGlobalFlags;
 use crate::config::{
-    ClientConfig, HttpClientConfig, NamedProfile, Profile, ProfileKind, ProfileName,
+    BuildProfileName, ClientConfig, Config, HttpClientConfig, NamedProfile, Profile, ProfileKind,
+    ProfileName,
 };
 use crate::error::HintError;
-use crate::log::{set_log_output, LogOutput, Output};
-use crate::model::app::{AppBuildStep, ApplicationSourceMode};
-use crate::model::app::{ApplicationConfig, BuildProfileName as AppBuildProfileName};
+use crate::log::{set_log_output, LogColorize, LogOutput, Output};
+use crate::model::app::{
+    AppBuildStep, ApplicationSourceMode, BuildCacheLocation, UpToDateCheckMode,
+};
+use crate::model::app::{
+    ApplicationConfig, BuildProfileName as AppBuildProfileName, Environment, EnvironmentName,
+};
 use crate::model::{Format, HasFormatConfig};
 use crate::wasm_rpc_stubgen::stub::RustDependencyOverride;
-use anyhow::anyhow;
+use anyhow::{anyhow, bail, Context as AnyhowContext};
 use golem_client::api::ApiDefinitionClientLive as ApiDefinitionClientOss;
 use golem_client::api::ApiDeploymentClientLive as ApiDeploymentClientOss;
 use golem_client::api::ApiSecurityClientLive as ApiSecurityClientOss;
@@ -65,6 +70,8 @@ use uuid::Uuid;
 // but NOT responsible for producing CLI output, those should be part of the CommandHandler(s)
 pub struct Context {
     // Readonly
+    global_flags: GolemCliGlobalFlags,
+    environment: Option<EnvironmentName>,
     config_dir: PathBuf,
     format: Format,
     profile_name: ProfileName,
@@ -102,6 +109,8 @@ impl Context {
         let client_config = ClientConfig::from(&profile.profile);
 
         Self {
+            global_flags: global_flags.clone(),
+            environment: None,
             config_dir: global_flags.config_dir(),
             format: global_flags.format.unwrap_or(Format::Text),
             profile_name: profile.name,
@@ -165,10 +174,80 @@ impl Context {
         self.profile_kind
     }
 
+    pub fn profile_name(&self) -> &ProfileName {
+        &self.profile_name
+    }
+
     pub fn build_profile(&self) -> Option<&AppBuildProfileName> {
         self.app_context_config.build_profile.as_ref()
     }
 
+    /// Environment of the application manifest selected for deploy
+    pub fn environment(&self) -> Option<&EnvironmentName> {
+        self.environment.as_ref()
+    }
+
+    /// Creates a new context for the environment, using its CLI profile and build profile.
+    /// Explicitly selected profiles must match the ones of the environment.
+    pub fn for_environment(
+        &self,
+        environment_name: &EnvironmentName,
+        environment: &Environment,
+    ) -> anyhow::Result<Self> {
+        let profile_name = ProfileName::from(environment.profile.clone());
+        let profile_was_selected = self.global_flags.profile.is_some()
+            || self.global_flags.local
+            || self.global_flags.cloud;
+        if profile_was_selected && self.profile_name != profile_name {
+            bail!(
+                "The selected profile {} does not match the profile {} of environment {}",
+                self.profile_name.0.log_color_error_highlight(),
+                profile_name.0.log_color_highlight(),
+                environment_name.as_str().log_color_highlight()
+            );
+        }
+
+        if let Some(build_profile) = self.build_profile() {
+            if Some(build_profile) != environment.build_profile.as_ref() {
+                bail!(
+                    "The selected build profile {} does not match the build profile {} of environment {}",
+                    build_profile.as_str().log_color_error_highlight(),
+                    environment
+                        .build_profile
+                        .as_ref()
+                        .map(|build_profile| build_profile.as_str())
+                        .unwrap_or("<default>")
+                        .log_color_highlight(),
+                    environment_name.as_str().log_color_highlight()
+                );
+            }
+        }
+
+        let global_flags = GolemCliGlobalFlags {
+            profile: Some(profile_name.clone()),
+            local: false,
+            cloud: false,
+            build_profile: environment
+                .build_profile
+                .as_ref()
+                .map(|build_profile| BuildProfileName::from(build_profile.as_str())),
+            ..self.global_flags.clone()
+        };
+
+        let profile = Config::get_active_profile(&self.config_dir, Some(profile_name.clone()))
+            .with_context(|| {
+                anyhow!(
+                    "Failed to select profile {} for environment {}",
+                    profile_name.0.log_color_highlight(),
+                    environment_name.as_str().log_color_highlight()
+                )
+            })?;
+
+        let mut ctx = Self::new(&global_flags, profile);
+        ctx.environment = Some(environment_name.clone());
+        Ok(ctx)
+    }
+
     pub fn http_batch_size(&self) -> u64 {
         self.http_batch_size
     }
@@ -246,6 +325,10 @@ impl Context {
         state
     }
 
+    pub fn app_source_mode(&self) -> ApplicationSourceMode {
+        self.app_context_config.app_source_mode()
+    }
+
     pub async fn unload_app_context(&self) {
         let mut state = self.app_context_state.write().await;
         *state = ApplicationContextState::default();
@@ -279,6 +362,46 @@ impl Context {
         .await
     }
 
+    pub async fn set_up_to_date_check(&self, mode: UpToDateCheckMode) {
+        self.set_app_ctx_init_config(
+            "up_to_date_check",
+            |ctx| &mut ctx.up_to_date_check,
+            |ctx| &mut ctx.up_to_date_check_was_set,
+            mode,
+        )
+        .await
+    }
+
+    pub async fn set_build_jobs(&self, jobs: usize) {
+        self.set_app_ctx_init_config(
+            "build_jobs",
+            |ctx| &mut ctx.build_jobs,
+            |ctx| &mut ctx.build_jobs_was_set,
+            jobs,
+        )
+        .await
+    }
+
+    pub async fn set_build_cache(&self, build_cache: Option<BuildCacheLocation>) {
+        self.set_app_ctx_init_config(
+            "build_cache",
+            |ctx| &mut ctx.build_cache,
+            |ctx| &mut ctx.build_cache_was_set,
+            build_cache,
+        )
+        .await
+    }
+
+    pub async fn set_build_events(&self, build_events: bool) {
+        self.set_app_ctx_init_config(
+            "build_events",
+            |ctx| &mut ctx.build_events,
+            |ctx| &mut ctx.build_events_was_set,
+            build_events,
+        )
+        .await
+    }
+
     pub async fn set_steps_filter(&self, steps_filter: HashSet<AppBuildStep>) {
         self.set_app_ctx_init_config(
             "steps_filter",
@@ -537,13 +660,36 @@ struct ApplicationContextConfig {
     wasm_rpc_client_build_offline: bool,
 }
 
+impl ApplicationContextConfig {
+    fn app_source_mode(&self) -> ApplicationSourceMode {
+        match &self.app_manifest_path {
+            Some(path) => ApplicationSourceMode::Explicit(path.clone()),
+            None => {
+                if self.disable_app_manifest_discovery {
+                    ApplicationSourceMode::None
+                } else {
+                    ApplicationSourceMode::Automatic
+                }
+            }
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct ApplicationContextState {
     pub silent_init: bool,
     pub skip_up_to_date_checks: bool,
     skip_up_to_date_checks_was_set: bool,
+    pub up_to_date_check: UpToDateCheckMode,
+    up_to_date_check_was_set: bool,
     pub build_steps_filter: HashSet<AppBuildStep>,
     build_steps_filter_was_set: bool,
+    pub build_jobs: usize,
+    build_jobs_was_set: bool,
+    pub build_cache: Option<BuildCacheLocation>,
+    build_cache_was_set: bool,
+    pub build_events: bool,
+    build_events_was_set: bool,
 
     app_context: Option<Result<Option<ApplicationContext>, Arc<anyhow::Error>>>,
 }
@@ -559,19 +705,12 @@ impl ApplicationContextState {
             .then(|| LogOutput::new(Output::TracingDebug));
 
         let config = ApplicationConfig {
-            app_source_mode: {
-                match &config.app_manifest_path {
-                    Some(path) => ApplicationSourceMode::Explicit(path.clone()),
-                    None => {
-                        if config.disable_app_manifest_discovery {
-                            ApplicationSourceMode::None
-                        } else {
-                            ApplicationSourceMode::Automatic
-                        }
-                    }
-                }
-            },
+            app_source_mode: config.app_source_mode(),
             skip_up_to_date_checks: self.skip_up_to_date_checks,
+            up_to_date_check: self.up_to_date_check,
+            build_jobs: self.build_jobs.max(1),
+            build_cache: self.build_cache.clone(),
+            build_events: self.build_events,
             profile: config.build_profile.as_ref().map(|p| p.to_string().into()),
             offline: config.wasm_rpc_client_build_offline,
             steps_filter: self.build_steps_filter.clone(),
@@ -25,6 +25,8 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
 use version_compare::{Cmp, Version};
 use walkdir::DirEntry;
 use wax::{Glob, LinkBehavior, WalkBehavior};
@@ -309,7 +311,7 @@ struct ToolMetadata {
     pub instructions: &'static str,
 }
 
-#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, EnumIter)]
 enum Tool {
     Cargo,
     CargoComponent,
@@ -746,6 +748,20 @@ impl DetectedTool {
     }
 }
 
+/// Checks if a tool is available in the given directory. Known tools (using their short name)
+/// are checked the same way as for diagnose, other tools are checked by trying to execute them
+/// with --version.
+pub fn is_tool_available(dir: &Path, name: &str) -> bool {
+    match Tool::iter().find(|tool| tool.metadata().short_name == name) {
+        Some(tool) => tool.get_version(dir).is_ok(),
+        None => Command::new(name)
+            .current_dir(dir)
+            .arg("--version")
+            .output()
+            .is_ok(),
+    }
+}
+
 pub fn diagnose(dir: &Path, language: Option<GuestLanguage>) {
     let selected_language = match &language {
         Some(language) => SelectedLanguage::from_flag(dir, *language),
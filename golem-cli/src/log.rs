@@ -15,6 +15,7 @@
 use crate::fs::{OverwriteSafeAction, OverwriteSafeActionPlan, PathExtra};
 use colored::{ColoredString, Colorize};
 use std::borrow::Cow;
+use std::cell::RefCell;
 use std::path::{Path, PathBuf};
 use std::sync::{LazyLock, OnceLock, RwLock};
 use terminal_size::terminal_size;
@@ -25,6 +26,10 @@ static LOG_STATE: LazyLock<RwLock<LogState>> = LazyLock::new(RwLock::default);
 static TERMINAL_WIDTH: OnceLock<Option<usize>> = OnceLock::new();
 static WRAP_PADDING: usize = 2;
 
+thread_local! {
+    static LOG_CAPTURE: RefCell<Option<LogCaptureState>> = const { RefCell::new(None) };
+}
+
 fn terminal_width() -> Option<usize> {
     *TERMINAL_WIDTH.get_or_init(|| terminal_size().map(|(width, _)| width.0 as usize))
 }
@@ -84,16 +89,55 @@ impl Default for LogState {
     }
 }
 
+#[derive(Default)]
+struct LogCaptureState {
+    indents: Vec<Option<String>>,
+    lines: Vec<String>,
+}
+
+impl LogCaptureState {
+    fn inc_indent(&mut self, custom_prefix: Option<&str>) {
+        self.indents.push(custom_prefix.map(|p| p.to_string()));
+    }
+
+    fn dec_indent(&mut self) {
+        self.indents.pop();
+    }
+
+    fn push_line(&mut self, message: &str) {
+        let mut line = String::new();
+        for indent in &self.indents {
+            line.push_str(indent.as_ref().map(|s| s.as_str()).unwrap_or("  "))
+        }
+        line.push_str(message);
+        self.lines.push(line);
+    }
+}
+
+fn with_log_capture<F: FnOnce(&mut LogCaptureState)>(f: F) -> bool {
+    LOG_CAPTURE.with_borrow_mut(|capture| match capture {
+        Some(capture) => {
+            f(capture);
+            true
+        }
+        None => false,
+    })
+}
+
 pub struct LogIndent;
 
 impl LogIndent {
     pub fn new() -> Self {
-        LOG_STATE.write().unwrap().inc_indent(None);
+        if !with_log_capture(|capture| capture.inc_indent(None)) {
+            LOG_STATE.write().unwrap().inc_indent(None);
+        }
         Self
     }
 
     pub fn prefix<S: AsRef<str>>(prefix: S) -> Self {
-        LOG_STATE.write().unwrap().inc_indent(Some(prefix.as_ref()));
+        if !with_log_capture(|capture| capture.inc_indent(Some(prefix.as_ref()))) {
+            LOG_STATE.write().unwrap().inc_indent(Some(prefix.as_ref()));
+        }
         Self
     }
 }
@@ -106,8 +150,52 @@ impl Default for LogIndent {
 
 impl Drop for LogIndent {
     fn drop(&mut self) {
-        let mut state = LOG_STATE.write().unwrap();
-        state.dec_indent();
+        if !with_log_capture(|capture| capture.dec_indent()) {
+            let mut state = LOG_STATE.write().unwrap();
+            state.dec_indent();
+        }
+    }
+}
+
+/// Captures all log lines (and indents) of the current thread instead of writing them out,
+/// so output of tasks running in parallel can be logged as one block with [log_captured_lines].
+pub struct LogCapture;
+
+impl LogCapture {
+    pub fn new() -> Self {
+        LOG_CAPTURE.set(Some(LogCaptureState::default()));
+        Self
+    }
+
+    pub fn finish(self) -> Vec<String> {
+        LOG_CAPTURE.with_borrow_mut(|capture| {
+            capture
+                .as_mut()
+                .map(|capture| std::mem::take(&mut capture.lines))
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl Default for LogCapture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for LogCapture {
+    fn drop(&mut self) {
+        LOG_CAPTURE.set(None);
+    }
+}
+
+pub fn is_log_captured() -> bool {
+    LOG_CAPTURE.with_borrow(|capture| capture.is_some())
+}
+
+pub fn log_captured_lines(lines: Vec<String>) {
+    for line in lines {
+        logln_internal(&line);
     }
 }
 
@@ -159,6 +247,10 @@ pub fn logln<T: AsRef<str>>(message: T) {
 }
 
 pub fn logln_internal(message: &str) {
+    if with_log_capture(|capture| capture.push_line(message)) {
+        return;
+    }
+
     let state = LOG_STATE.read().unwrap();
 
     let lines = match state.max_width {
@@ -203,6 +295,17 @@ pub fn log_skipping_up_to_date<T: AsRef<str>>(subject: T) {
     );
 }
 
+pub fn log_skipping_restored_from_build_cache<T: AsRef<str>>(subject: T) {
+    log_warn_action(
+        "Skipping",
+        format!(
+            "{}, {}",
+            subject.as_ref(),
+            "RESTORED-FROM-CACHE".log_color_ok_highlight()
+        ),
+    );
+}
+
 pub fn log_action_plan(action: &OverwriteSafeAction, plan: OverwriteSafeActionPlan) {
     match plan {
         OverwriteSafeActionPlan::Create => match action {
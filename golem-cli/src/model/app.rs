@@ -9,6 +9,10 @@ use crate::wasm_rpc_stubgen::naming;
 use crate::wasm_rpc_stubgen::naming::wit::package_dep_dir_name_from_parser;
 use crate::wasm_rpc_stubgen::stub::RustDependencyOverride;
 use golem_common::model::{ComponentFilePathWithPermissions, ComponentFilePermissions};
+use itertools::Itertools;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
@@ -17,6 +21,8 @@ use std::fmt::{Debug, Display};
 use std::hash::Hash;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
 use url::Url;
 use wit_parser::PackageName;
 
@@ -26,6 +32,10 @@ pub const DEFAULT_CONFIG_FILE_NAME: &str = "golem.yaml";
 pub struct ApplicationConfig {
     pub app_source_mode: ApplicationSourceMode,
     pub skip_up_to_date_checks: bool,
+    pub up_to_date_check: UpToDateCheckMode,
+    pub build_jobs: usize,
+    pub build_cache: Option<BuildCacheLocation>,
+    pub build_events: bool,
     pub profile: Option<BuildProfileName>,
     pub offline: bool,
     pub steps_filter: HashSet<AppBuildStep>,
@@ -89,15 +99,69 @@ pub struct ComponentStubInterfaces {
     pub exported_interfaces_per_stub_resource: BTreeMap<String, String>,
 }
 
-#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 #[clap(rename_all = "kebab_case")]
+#[serde(rename_all = "kebab-case")]
 pub enum AppBuildStep {
     GenRpc,
     Componentize,
     Link,
+    PostLink,
     AddMetadata,
 }
 
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[clap(rename_all = "kebab_case")]
+pub enum UpToDateCheckMode {
+    /// Compare the modification times of sources and targets
+    #[default]
+    Timestamp,
+    /// Compare the content hash of sources with the one saved on the last successful run
+    ContentHash,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[clap(rename_all = "kebab_case")]
+pub enum AppGraphFormat {
+    /// Graphviz DOT
+    #[default]
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
+/// Location of the shared build cache, either a local directory or an HTTP(S) base URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildCacheLocation {
+    LocalDir(PathBuf),
+    Http(Url),
+}
+
+impl FromStr for BuildCacheLocation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("http://") || s.starts_with("https://") {
+            Url::parse(s)
+                .map(BuildCacheLocation::Http)
+                .map_err(|err| format!("Invalid build cache URL: {}", err))
+        } else if s.is_empty() {
+            Err("Empty build cache location".to_string())
+        } else {
+            Ok(BuildCacheLocation::LocalDir(PathBuf::from(s)))
+        }
+    }
+}
+
+impl Display for BuildCacheLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildCacheLocation::LocalDir(dir) => write!(f, "{}", dir.display()),
+            BuildCacheLocation::Http(url) => write!(f, "{}", url),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct AppComponentName(String);
 
@@ -152,6 +216,33 @@ impl From<&str> for BuildProfileName {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EnvironmentName(String);
+
+impl EnvironmentName {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for EnvironmentName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for EnvironmentName {
+    fn from(value: String) -> Self {
+        EnvironmentName(value)
+    }
+}
+
+impl From<&str> for EnvironmentName {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TemplateName(String);
 
@@ -193,6 +284,19 @@ pub fn includes_from_yaml_file(source: &Path) -> Vec<String> {
         .unwrap_or_default()
 }
 
+/// Temp dir defined by the main manifest, used before the whole application is loaded
+pub fn temp_dir_from_yaml_file(source: &Path) -> PathBuf {
+    let source_dir = source
+        .parent()
+        .expect("Failed to get parent dir of the main source");
+    let temp_dir = fs::read_to_string(source)
+        .ok()
+        .and_then(|source| app_raw::Application::from_yaml_str(source.as_str()).ok())
+        .and_then(|app| app.temp_dir)
+        .unwrap_or_else(|| "golem-temp".to_string());
+    source_dir.join(temp_dir)
+}
+
 #[derive(Clone, Debug)]
 pub enum ResolvedComponentProperties {
     Properties {
@@ -236,7 +340,7 @@ impl<T: Default> Default for WithSource<T> {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, EnumIter)]
 pub enum DependencyType {
     /// Dynamic ("stubless") wasm-rpc
     DynamicWasmRpc,
@@ -288,6 +392,70 @@ impl FromStr for DependencyType {
     }
 }
 
+/// Manifest dependency types are plain strings, the schema lists the known ones
+impl JsonSchema for DependencyType {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "DependencyType".to_string()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            enum_values: Some(
+                DependencyType::iter()
+                    .map(|dependency_type| dependency_type.as_str().into())
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+/// Route methods of HTTP API definitions, using the names expected by the API definition service
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, EnumIter)]
+pub enum HttpApiRouteMethod {
+    Get,
+    Connect,
+    Post,
+    Delete,
+    Put,
+    Patch,
+    Options,
+    Trace,
+    Head,
+}
+
+impl HttpApiRouteMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpApiRouteMethod::Get => "Get",
+            HttpApiRouteMethod::Connect => "Connect",
+            HttpApiRouteMethod::Post => "Post",
+            HttpApiRouteMethod::Delete => "Delete",
+            HttpApiRouteMethod::Put => "Put",
+            HttpApiRouteMethod::Patch => "Patch",
+            HttpApiRouteMethod::Options => "Options",
+            HttpApiRouteMethod::Trace => "Trace",
+            HttpApiRouteMethod::Head => "Head",
+        }
+    }
+}
+
+impl FromStr for HttpApiRouteMethod {
+    type Err = ();
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        Self::iter()
+            .find(|method| method.as_str().eq_ignore_ascii_case(str))
+            .ok_or(())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct DependentComponent {
     pub name: AppComponentName,
@@ -308,6 +476,7 @@ impl Ord for DependentComponent {
 
 #[derive(Clone, Debug)]
 pub struct Application {
+    sources: BTreeSet<PathBuf>,
     temp_dir: Option<WithSource<String>>,
     wit_deps: WithSource<Vec<String>>,
     components: BTreeMap<AppComponentName, Component>,
@@ -315,6 +484,9 @@ pub struct Application {
     no_dependencies: BTreeSet<DependentComponent>,
     custom_commands: HashMap<String, WithSource<Vec<app_raw::ExternalCommand>>>,
     clean: Vec<WithSource<String>>,
+    http_api_definitions: BTreeMap<String, WithSource<app_raw::HttpApiDefinition>>,
+    http_api_deployments: BTreeMap<String, Vec<WithSource<app_raw::HttpApiDeployment>>>,
+    environments: BTreeMap<EnvironmentName, Environment>,
 }
 
 impl Application {
@@ -322,6 +494,10 @@ impl Application {
         build_application(apps)
     }
 
+    pub fn sources(&self) -> &BTreeSet<PathBuf> {
+        &self.sources
+    }
+
     pub fn component_names(&self) -> impl Iterator<Item = &AppComponentName> {
         self.components.keys()
     }
@@ -348,6 +524,83 @@ impl Application {
         &self.wit_deps
     }
 
+    pub fn http_api_definitions(
+        &self,
+    ) -> &BTreeMap<String, WithSource<app_raw::HttpApiDefinition>> {
+        &self.http_api_definitions
+    }
+
+    /// HTTP API deployments for the selected CLI profile
+    pub fn http_api_deployments(
+        &self,
+        profile_name: &str,
+    ) -> &[WithSource<app_raw::HttpApiDeployment>] {
+        self.http_api_deployments
+            .get(profile_name)
+            .map(|deployments| deployments.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn environment_names(&self) -> impl Iterator<Item = &EnvironmentName> {
+        self.environments.keys()
+    }
+
+    pub fn environment(&self, environment_name: &EnvironmentName) -> Option<&Environment> {
+        self.environments.get(environment_name)
+    }
+
+    /// Files of the component, with the overrides of the environment applied
+    pub fn component_files(
+        &self,
+        component_name: &AppComponentName,
+        profile: Option<&BuildProfileName>,
+        environment_name: Option<&EnvironmentName>,
+    ) -> Vec<InitialComponentFile> {
+        let mut files = self
+            .component_properties(component_name, profile)
+            .files
+            .clone();
+        if let Some(overrides) = self.environment_component(environment_name, component_name) {
+            files.retain(|file| {
+                !overrides
+                    .files
+                    .iter()
+                    .any(|override_file| override_file.target.path == file.target.path)
+            });
+            files.extend(overrides.files.iter().cloned());
+        }
+        files
+    }
+
+    /// Workers of the component, with the env overrides of the environment applied
+    pub fn component_workers(
+        &self,
+        component_name: &AppComponentName,
+        profile: Option<&BuildProfileName>,
+        environment_name: Option<&EnvironmentName>,
+    ) -> Vec<app_raw::Worker> {
+        let mut workers = self
+            .component_properties(component_name, profile)
+            .workers
+            .clone();
+        if let Some(overrides) = self.environment_component(environment_name, component_name) {
+            for worker in &mut workers {
+                worker.env.extend(overrides.env.clone());
+            }
+        }
+        workers
+    }
+
+    fn environment_component(
+        &self,
+        environment_name: Option<&EnvironmentName>,
+        component_name: &AppComponentName,
+    ) -> Option<&EnvironmentComponent> {
+        environment_name
+            .and_then(|environment_name| self.environments.get(environment_name))
+            .and_then(|environment| environment.components.get(component_name))
+    }
+
     pub fn all_dependencies(&self) -> BTreeSet<DependentComponent> {
         self.dependencies.values().flatten().cloned().collect()
     }
@@ -421,6 +674,13 @@ impl Application {
         self.temp_dir().join("task-results")
     }
 
+    /// Final linked WASM extended with the deploy fingerprint, which is uploaded during deploy
+    pub fn component_deploy_wasm(&self, component_name: &AppComponentName) -> PathBuf {
+        self.temp_dir()
+            .join("deploy-wasm")
+            .join(format!("{}.wasm", component_name.as_str()))
+    }
+
     pub fn rib_repl_history_file(&self) -> PathBuf {
         self.temp_dir().join(".rib_repl_history")
     }
@@ -624,6 +884,27 @@ impl Application {
         )
     }
 
+    /// Output of the post-link step, or the linked WASM if no post-link step is enabled
+    pub fn component_post_linked_wasm(
+        &self,
+        component_name: &AppComponentName,
+        profile: Option<&BuildProfileName>,
+    ) -> PathBuf {
+        if self
+            .component_properties(component_name, profile)
+            .post_link
+            .is_enabled()
+        {
+            self.component_source_dir(component_name).join(
+                self.temp_dir()
+                    .join("post-linked-wasm")
+                    .join(format!("{}.wasm", component_name.as_str())),
+            )
+        } else {
+            self.component_linked_wasm(component_name)
+        }
+    }
+
     fn client_build_dir(&self) -> PathBuf {
         self.temp_dir().join("client")
     }
@@ -669,6 +950,22 @@ impl Component {
     }
 }
 
+/// Deploy target environment, see [app_raw::Environment]
+#[derive(Clone, Debug)]
+pub struct Environment {
+    pub source: PathBuf,
+    pub profile: String,
+    pub project: Option<String>,
+    pub build_profile: Option<BuildProfileName>,
+    pub components: BTreeMap<AppComponentName, EnvironmentComponent>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct EnvironmentComponent {
+    pub files: Vec<InitialComponentFile>,
+    pub env: BTreeMap<String, String>,
+}
+
 #[derive(Clone, Debug)]
 pub struct ComponentProperties {
     pub source_wit: String,
@@ -680,6 +977,10 @@ pub struct ComponentProperties {
     pub clean: Vec<String>,
     pub component_type: AppComponentType,
     pub files: Vec<InitialComponentFile>,
+    pub post_link: PostLinkProperties,
+    pub plugins: Option<Vec<app_raw::PluginInstallation>>,
+    pub uninstall_extra_plugins: bool,
+    pub workers: Vec<app_raw::Worker>,
 }
 
 impl ComponentProperties {
@@ -690,6 +991,10 @@ impl ComponentProperties {
     ) -> Option<Self> {
         let files = InitialComponentFile::from_raw_vec(validation, source, raw.files)?;
 
+        Self::validate_external_commands(validation, &raw);
+        Self::validate_plugins(validation, &raw);
+        Self::validate_workers(validation, &raw);
+
         Some(Self {
             source_wit: raw.source_wit.unwrap_or_default(),
             generated_wit: raw.generated_wit.unwrap_or_default(),
@@ -700,9 +1005,83 @@ impl ComponentProperties {
             clean: raw.clean,
             component_type: raw.component_type.unwrap_or_default(),
             files,
+            post_link: PostLinkProperties::default().merge_with_overrides(raw.post_link),
+            plugins: raw.plugins,
+            uninstall_extra_plugins: raw.uninstall_extra_plugins.unwrap_or_default(),
+            workers: raw.workers,
         })
     }
 
+    fn validate_workers(validation: &mut ValidationBuilder, raw: &app_raw::ComponentProperties) {
+        let duplicated_names = raw
+            .workers
+            .iter()
+            .map(|worker| worker.name.as_str())
+            .counts()
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(name, _)| name)
+            .sorted()
+            .collect::<Vec<_>>();
+
+        for name in duplicated_names {
+            validation.add_error(format!(
+                "Worker {} is defined more than once in {}",
+                name.log_color_error_highlight(),
+                "workers".log_color_highlight()
+            ));
+        }
+
+        for worker in &raw.workers {
+            if worker.name.is_empty() || worker.name == "-" {
+                validation.add_error(format!(
+                    "Invalid worker name {} in {}, workers defined in the manifest must be named",
+                    worker.name.log_color_error_highlight(),
+                    "workers".log_color_highlight()
+                ));
+            }
+        }
+    }
+
+    fn validate_plugins(validation: &mut ValidationBuilder, raw: &app_raw::ComponentProperties) {
+        let Some(plugins) = &raw.plugins else {
+            return;
+        };
+
+        let duplicated_names = plugins
+            .iter()
+            .map(|plugin| plugin.name.as_str())
+            .counts()
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(name, _)| name)
+            .sorted()
+            .collect::<Vec<_>>();
+
+        for name in duplicated_names {
+            validation.add_error(format!(
+                "Plugin {} is defined more than once in {}",
+                name.log_color_error_highlight(),
+                "plugins".log_color_highlight()
+            ));
+        }
+    }
+
+    fn validate_external_commands(
+        validation: &mut ValidationBuilder,
+        raw: &app_raw::ComponentProperties,
+    ) {
+        for command in raw
+            .build
+            .iter()
+            .chain(raw.custom_commands.values().flatten())
+        {
+            if let Err(err) = command.timeout_duration() {
+                validation.add_error(err.to_string());
+            }
+        }
+    }
+
     fn from_raw_template<C: Serialize>(
         validation: &mut ValidationBuilder,
         source: &Path,
@@ -726,6 +1105,10 @@ impl ComponentProperties {
         let mut any_overrides = false;
         let mut any_errors = false;
 
+        Self::validate_external_commands(validation, &overrides);
+        Self::validate_plugins(validation, &overrides);
+        Self::validate_workers(validation, &overrides);
+
         if let Some(source_wit) = overrides.source_wit {
             self.source_wit = source_wit;
             any_overrides = true;
@@ -761,6 +1144,26 @@ impl ComponentProperties {
             any_overrides = true;
         }
 
+        if overrides.post_link.is_some() {
+            self.post_link = self.post_link.merge_with_overrides(overrides.post_link);
+            any_overrides = true;
+        }
+
+        if overrides.plugins.is_some() {
+            self.plugins = overrides.plugins;
+            any_overrides = true;
+        }
+
+        if let Some(uninstall_extra_plugins) = overrides.uninstall_extra_plugins {
+            self.uninstall_extra_plugins = uninstall_extra_plugins;
+            any_overrides = true;
+        }
+
+        if !overrides.workers.is_empty() {
+            self.workers = overrides.workers;
+            any_overrides = true;
+        }
+
         if !overrides.files.is_empty() {
             any_overrides = true;
             match InitialComponentFile::from_raw_vec(validation, source, overrides.files) {
@@ -789,6 +1192,36 @@ impl ComponentProperties {
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PostLinkProperties {
+    pub strip_debug_info: bool,
+    pub strip_custom_sections: bool,
+    pub validate: bool,
+}
+
+impl PostLinkProperties {
+    fn merge_with_overrides(self, overrides: Option<app_raw::PostLinkProperties>) -> Self {
+        let Some(overrides) = overrides else {
+            return self;
+        };
+        Self {
+            strip_debug_info: overrides.strip_debug_info.unwrap_or(self.strip_debug_info),
+            strip_custom_sections: overrides
+                .strip_custom_sections
+                .unwrap_or(self.strip_custom_sections),
+            validate: overrides.validate.unwrap_or(self.validate),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.strip_debug_info || self.strip_custom_sections || self.validate
+    }
+
+    pub fn is_stripping(&self) -> bool {
+        self.strip_debug_info || self.strip_custom_sections
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct InitialComponentFile {
     pub source: InitialComponentFileSource,
@@ -890,19 +1323,22 @@ mod app_builder {
     use crate::log::LogColorize;
     use crate::model::app::{
         AppComponentName, Application, BuildProfileName, Component, ComponentProperties,
-        DependencyType, DependentComponent, ResolvedComponentProperties, TemplateName, WithSource,
+        DependencyType, DependentComponent, Environment, EnvironmentComponent, EnvironmentName,
+        HttpApiRouteMethod, InitialComponentFile, ResolvedComponentProperties, TemplateName,
+        WithSource,
     };
     use crate::model::app_raw;
+    use crate::model::template::Template;
     use crate::validation::{ValidatedResult, ValidationBuilder};
     use heck::{
         ToKebabCase, ToLowerCamelCase, ToPascalCase, ToShoutyKebabCase, ToShoutySnakeCase,
         ToSnakeCase, ToTitleCase, ToTrainCase, ToUpperCamelCase,
     };
     use itertools::Itertools;
-    use serde::Serialize;
     use std::collections::{BTreeMap, BTreeSet, HashMap};
     use std::path::{Path, PathBuf};
     use std::str::FromStr;
+    use strum::IntoEnumIterator;
 
     pub fn build_application(
         apps: Vec<app_raw::ApplicationWithSource>,
@@ -910,15 +1346,24 @@ mod app_builder {
         AppBuilder::build(apps)
     }
 
+    /// Variables provided for every component, cannot be used as manifest variable or
+    /// template parameter names
+    const TEMPLATE_BUILTIN_VARS: [&str; 2] = ["componentName", "component_name"];
+
+    type TemplateContext = BTreeMap<String, serde_yaml::Value>;
+
     #[derive(Debug, PartialEq, Eq, Hash)]
     enum UniqueSourceCheckedEntityKey {
         Include,
         TempDir,
         WitDeps,
+        Var(String),
         CustomCommand(String),
+        HttpApiDefinition(String),
         Template(TemplateName),
         WasmRpcDependency((AppComponentName, DependentComponent)),
         Component(AppComponentName),
+        Environment(EnvironmentName),
     }
 
     impl UniqueSourceCheckedEntityKey {
@@ -928,10 +1373,13 @@ mod app_builder {
                 UniqueSourceCheckedEntityKey::Include => property,
                 UniqueSourceCheckedEntityKey::TempDir => property,
                 UniqueSourceCheckedEntityKey::WitDeps => property,
+                UniqueSourceCheckedEntityKey::Var(_) => "Variable",
                 UniqueSourceCheckedEntityKey::CustomCommand(_) => "Custom command",
+                UniqueSourceCheckedEntityKey::HttpApiDefinition(_) => "HTTP API definition",
                 UniqueSourceCheckedEntityKey::Template(_) => "Template",
                 UniqueSourceCheckedEntityKey::WasmRpcDependency(_) => "WASM RPC dependency",
                 UniqueSourceCheckedEntityKey::Component(_) => "Component",
+                UniqueSourceCheckedEntityKey::Environment(_) => "Environment",
             }
         }
 
@@ -946,9 +1394,15 @@ mod app_builder {
                 UniqueSourceCheckedEntityKey::WitDeps => {
                     "witDeps".log_color_highlight().to_string()
                 }
+                UniqueSourceCheckedEntityKey::Var(var_name) => {
+                    var_name.log_color_highlight().to_string()
+                }
                 UniqueSourceCheckedEntityKey::CustomCommand(command_name) => {
                     command_name.log_color_highlight().to_string()
                 }
+                UniqueSourceCheckedEntityKey::HttpApiDefinition(definition_name) => {
+                    definition_name.log_color_highlight().to_string()
+                }
                 UniqueSourceCheckedEntityKey::Template(template_name) => {
                     template_name.as_str().log_color_highlight().to_string()
                 }
@@ -966,19 +1420,27 @@ mod app_builder {
                 UniqueSourceCheckedEntityKey::Component(component_name) => {
                     component_name.as_str().log_color_highlight().to_string()
                 }
+                UniqueSourceCheckedEntityKey::Environment(environment_name) => {
+                    environment_name.as_str().log_color_highlight().to_string()
+                }
             }
         }
     }
 
     #[derive(Default)]
     struct AppBuilder {
+        sources: BTreeSet<PathBuf>,
         include: Vec<String>,
         temp_dir: Option<WithSource<String>>,
         wit_deps: WithSource<Vec<String>>,
+        vars: BTreeMap<String, WithSource<serde_yaml::Value>>,
         templates: HashMap<TemplateName, app_raw::ComponentTemplate>,
         dependencies: BTreeMap<AppComponentName, BTreeSet<DependentComponent>>,
         custom_commands: HashMap<String, WithSource<Vec<app_raw::ExternalCommand>>>,
         clean: Vec<WithSource<String>>,
+        http_api_definitions: BTreeMap<String, WithSource<app_raw::HttpApiDefinition>>,
+        http_api_deployments: BTreeMap<String, Vec<WithSource<app_raw::HttpApiDeployment>>>,
+        environments: BTreeMap<EnvironmentName, Environment>,
         raw_components: HashMap<AppComponentName, (PathBuf, app_raw::Component)>,
         resolved_components: BTreeMap<AppComponentName, Component>,
 
@@ -992,10 +1454,13 @@ mod app_builder {
 
             builder.add_raw_apps(&mut validation, apps);
             builder.validate_dependency_targets(&mut validation);
+            builder.validate_http_api(&mut validation);
             builder.validate_unique_sources(&mut validation);
             builder.resolve_components(&mut validation);
+            builder.validate_environments(&mut validation);
 
             validation.build(Application {
+                sources: builder.sources,
                 temp_dir: builder.temp_dir,
                 wit_deps: builder.wit_deps,
                 components: builder.resolved_components,
@@ -1003,6 +1468,9 @@ mod app_builder {
                 no_dependencies: BTreeSet::new(),
                 custom_commands: builder.custom_commands,
                 clean: builder.clean,
+                http_api_definitions: builder.http_api_definitions,
+                http_api_deployments: builder.http_api_deployments,
+                environments: builder.environments,
             })
         }
 
@@ -1023,14 +1491,96 @@ mod app_builder {
             }
         }
 
+        /// Unknown conditions of external commands are only reported as warnings (at the location
+        /// of the condition), so manifests using conditions of newer versions can still be used.
+        /// Conditions are checked on the raw manifest, so each one is only reported once, even
+        /// if the defining template is used by multiple components.
+        fn validate_external_command_conditions(
+            validation: &mut ValidationBuilder,
+            source: &Path,
+            application: &app_raw::Application,
+        ) {
+            let mut properties = Vec::<(Vec<String>, &app_raw::ComponentProperties)>::new();
+            for (template_name, template) in &application.templates {
+                let path = vec!["templates".to_string(), template_name.clone()];
+                properties.push((path.clone(), &template.component_properties));
+                for (profile_name, profile) in &template.profiles {
+                    let mut path = path.clone();
+                    path.extend(["profiles".to_string(), profile_name.clone()]);
+                    properties.push((path, profile));
+                }
+            }
+            for (component_name, component) in &application.components {
+                let path = vec!["components".to_string(), component_name.clone()];
+                properties.push((path.clone(), &component.component_properties));
+                for (profile_name, profile) in &component.profiles {
+                    let mut path = path.clone();
+                    path.extend(["profiles".to_string(), profile_name.clone()]);
+                    properties.push((path, profile));
+                }
+            }
+
+            let mut command_lists = Vec::<(Vec<String>, &Vec<app_raw::ExternalCommand>)>::new();
+            for (command_name, commands) in &application.custom_commands {
+                command_lists.push((
+                    vec!["customCommands".to_string(), command_name.clone()],
+                    commands,
+                ));
+            }
+            for (path, properties) in properties {
+                let mut build_path = path.clone();
+                build_path.push("build".to_string());
+                command_lists.push((build_path, &properties.build));
+                for (command_name, commands) in &properties.custom_commands {
+                    let mut path = path.clone();
+                    path.extend(["customCommands".to_string(), command_name.clone()]);
+                    command_lists.push((path, commands));
+                }
+            }
+
+            for (path, commands) in command_lists {
+                for (index, command) in commands.iter().enumerate() {
+                    let Some(condition) = &command.when else {
+                        continue;
+                    };
+                    let index = index.to_string();
+                    for key in condition.unknown.keys() {
+                        let key_path = path
+                            .iter()
+                            .map(|segment| segment.as_str())
+                            .chain([index.as_str(), "when", key.as_str()])
+                            .collect::<Vec<_>>();
+                        validation.add_warn_at(
+                            validation.key_span(source, &key_path),
+                            format!(
+                                "Unknown condition {} in {} for command {}, the condition is ignored",
+                                key.log_color_error_highlight(),
+                                "when".log_color_highlight(),
+                                command.command.log_color_highlight()
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
         fn add_raw_app(
             &mut self,
             validation: &mut ValidationBuilder,
             app: app_raw::ApplicationWithSource,
         ) {
+            validation.add_source_map(app.source_map);
             validation.with_context(
                 vec![("source", app.source.to_string_lossy().to_string())],
                 |validation| {
+                    self.sources.insert(app.source.clone());
+
+                    Self::validate_external_command_conditions(
+                        validation,
+                        &app.source,
+                        &app.application,
+                    );
+
                     let app_source = PathExtra::new(&app.source);
                     let app_source_dir = app_source.parent().unwrap();
 
@@ -1058,6 +1608,24 @@ mod app_builder {
                             WithSource::new(app_source_dir.to_path_buf(), app.application.wit_deps);
                     }
 
+                    for (var_name, value) in app.application.vars {
+                        if TEMPLATE_BUILTIN_VARS.contains(&var_name.as_str()) {
+                            validation.add_error_at(
+                                validation.key_span(&app.source, &["vars", var_name.as_str()]),
+                                format!(
+                                    "Variable {} is reserved, as it is always provided for templates",
+                                    var_name.log_color_error_highlight()
+                                ),
+                            );
+                        } else if self.add_entity_source(
+                            UniqueSourceCheckedEntityKey::Var(var_name.clone()),
+                            &app.source,
+                        ) {
+                            self.vars
+                                .insert(var_name, WithSource::new(app.source.clone(), value));
+                        }
+                    }
+
                     for (template_name, template) in app.application.templates {
                         self.add_raw_template(validation, &app.source, template_name, template);
                     }
@@ -1099,6 +1667,85 @@ mod app_builder {
                             .into_iter()
                             .map(|path| WithSource::new(app.source.to_path_buf(), path)),
                     );
+
+                    if let Some(http_api) = app.application.http_api {
+                        for (definition_name, definition) in http_api.definitions {
+                            if self.add_entity_source(
+                                UniqueSourceCheckedEntityKey::HttpApiDefinition(
+                                    definition_name.clone(),
+                                ),
+                                &app.source,
+                            ) {
+                                self.http_api_definitions.insert(
+                                    definition_name,
+                                    WithSource::new(app.source.to_path_buf(), definition),
+                                );
+                            }
+                        }
+
+                        for (profile_name, deployments) in http_api.deployments {
+                            self.http_api_deployments
+                                .entry(profile_name)
+                                .or_default()
+                                .extend(deployments.into_iter().map(|deployment| {
+                                    WithSource::new(app.source.to_path_buf(), deployment)
+                                }));
+                        }
+                    }
+
+                    for (environment_name, environment) in app.application.environments {
+                        let environment_name = EnvironmentName::from(environment_name);
+                        if self.add_entity_source(
+                            UniqueSourceCheckedEntityKey::Environment(environment_name.clone()),
+                            &app.source,
+                        ) {
+                            self.add_raw_environment(
+                                validation,
+                                &app.source,
+                                environment_name,
+                                environment,
+                            );
+                        }
+                    }
+                },
+            );
+        }
+
+        fn add_raw_environment(
+            &mut self,
+            validation: &mut ValidationBuilder,
+            source: &Path,
+            environment_name: EnvironmentName,
+            environment: app_raw::Environment,
+        ) {
+            validation.with_context(
+                vec![("environment", environment_name.to_string())],
+                |validation| {
+                    let mut components = BTreeMap::new();
+                    for (component_name, component) in environment.components {
+                        if let Some(files) =
+                            InitialComponentFile::from_raw_vec(validation, source, component.files)
+                        {
+                            components.insert(
+                                AppComponentName::from(component_name),
+                                EnvironmentComponent {
+                                    files,
+                                    env: component.env,
+                                },
+                            );
+                        }
+                    }
+
+                    self.environments.insert(
+                        environment_name,
+                        Environment {
+                            source: source.to_path_buf(),
+                            profile: environment.profile,
+                            project: environment.project,
+                            build_profile: environment.build_profile.map(BuildProfileName::from),
+                            components,
+                        },
+                    );
                 },
             );
         }
@@ -1112,33 +1759,66 @@ mod app_builder {
         ) {
             let valid =
                 validation.with_context(vec![("template", template_name.clone())], |validation| {
+                    for param_name in template.params.keys() {
+                        if TEMPLATE_BUILTIN_VARS.contains(&param_name.as_str()) {
+                            validation.add_error_at(
+                                validation.key_span(
+                                    source,
+                                    &["templates", template_name.as_str(), "params", param_name.as_str()],
+                                ),
+                                format!(
+                                    "Template parameter {} is reserved, as it is always provided for templates",
+                                    param_name.log_color_error_highlight()
+                                ),
+                            );
+                        }
+                    }
+
                     if template.profiles.is_empty() {
                         if template.default_profile.is_some() {
-                            validation.add_error(format!(
-                                "When {} is not defined then {} should not be defined",
-                                "profiles".log_color_highlight(),
-                                "defaultProfile".log_color_highlight()
-                            ));
+                            validation.add_error_at(
+                                validation.key_span(
+                                    source,
+                                    &["templates", template_name.as_str(), "defaultProfile"],
+                                ),
+                                format!(
+                                    "When {} is not defined then {} should not be defined",
+                                    "profiles".log_color_highlight(),
+                                    "defaultProfile".log_color_highlight()
+                                ),
+                            );
                         }
                     } else {
                         let defined_property_names =
                             template.component_properties.defined_property_names();
                         if !defined_property_names.is_empty() {
                             for property_name in defined_property_names {
-                                validation.add_error(format!(
-                                    "When {} is defined then {} should not be defined",
-                                    "profiles".log_color_highlight(),
-                                    property_name.log_color_highlight()
-                                ));
+                                validation.add_error_at(
+                                    validation.key_span(
+                                        source,
+                                        &["templates", template_name.as_str(), property_name],
+                                    ),
+                                    format!(
+                                        "When {} is defined then {} should not be defined",
+                                        "profiles".log_color_highlight(),
+                                        property_name.log_color_highlight()
+                                    ),
+                                );
                             }
                         }
 
                         if template.default_profile.is_none() {
-                            validation.add_error(format!(
-                                "When {} is defined then {} is mandatory",
-                                "profiles".log_color_highlight(),
-                                "defaultProfile".log_color_highlight()
-                            ));
+                            validation.add_error_at(
+                                validation.key_span(
+                                    source,
+                                    &["templates", template_name.as_str(), "profiles"],
+                                ),
+                                format!(
+                                    "When {} is defined then {} is mandatory",
+                                    "profiles".log_color_highlight(),
+                                    "defaultProfile".log_color_highlight()
+                                ),
+                            );
                         }
                     }
                 });
@@ -1161,7 +1841,8 @@ mod app_builder {
             component_dependencies: Vec<app_raw::Dependency>,
         ) {
             validation.with_context(vec![("component", component_name.clone())], |validation| {
-                for dependency in component_dependencies {
+                for (index, dependency) in component_dependencies.into_iter().enumerate() {
+                    let index = index.to_string();
                     let dep_type = DependencyType::from_str(&dependency.type_);
                     if let Ok(dep_type) = dep_type {
                         match dependency.target {
@@ -1182,16 +1863,33 @@ mod app_builder {
                                         .insert(dependent_component);
                                 }
                             }
-                            None => validation.add_error(format!(
-                                "Missing {} field for component wasm-rpc dependency",
-                                "target".log_color_error_highlight()
-                            )),
+                            None => validation.add_error_at(
+                                validation.key_span(
+                                    source,
+                                    &["dependencies", component_name.as_str(), index.as_str()],
+                                ),
+                                format!(
+                                    "Missing {} field for component wasm-rpc dependency",
+                                    "target".log_color_error_highlight()
+                                ),
+                            ),
                         }
                     } else {
-                        validation.add_error(format!(
-                            "Unknown component dependency type: {}",
-                            dependency.type_.log_color_error_highlight()
-                        ));
+                        validation.add_error_at(
+                            validation.value_span(
+                                source,
+                                &[
+                                    "dependencies",
+                                    component_name.as_str(),
+                                    index.as_str(),
+                                    "type",
+                                ],
+                            ),
+                            format!(
+                                "Unknown component dependency type: {}",
+                                dependency.type_.log_color_error_highlight()
+                            ),
+                        );
                     }
                 }
             });
@@ -1235,21 +1933,29 @@ mod app_builder {
                         validation.with_context(
                             vec![("source", source.to_string_lossy().to_string())],
                             |validation| {
+                                let span = validation
+                                    .key_span(source, &["dependencies", component.as_str()]);
                                 if invalid_source {
-                                    validation.add_error(format!(
-                                        "{} {} - {} references unknown component",
-                                        target.dep_type.describe(),
-                                        component.as_str().log_color_error_highlight(),
-                                        target.name.as_str().log_color_highlight()
-                                    ))
+                                    validation.add_error_at(
+                                        span.clone(),
+                                        format!(
+                                            "{} {} - {} references unknown component",
+                                            target.dep_type.describe(),
+                                            component.as_str().log_color_error_highlight(),
+                                            target.name.as_str().log_color_highlight()
+                                        ),
+                                    )
                                 }
                                 if invalid_target {
-                                    validation.add_error(format!(
-                                        "{} {} - {} references unknown target component",
-                                        target.dep_type.describe(),
-                                        component.as_str().log_color_highlight(),
-                                        target.name.as_str().log_color_error_highlight()
-                                    ))
+                                    validation.add_error_at(
+                                        span,
+                                        format!(
+                                            "{} {} - {} references unknown target component",
+                                            target.dep_type.describe(),
+                                            component.as_str().log_color_highlight(),
+                                            target.name.as_str().log_color_error_highlight()
+                                        ),
+                                    )
                                 }
                             },
                         );
@@ -1258,6 +1964,159 @@ mod app_builder {
             }
         }
 
+        fn validate_http_api(&self, validation: &mut ValidationBuilder) {
+            for (definition_name, definition) in &self.http_api_definitions {
+                validation.with_context(
+                    vec![
+                        ("source", definition.source.to_string_lossy().to_string()),
+                        ("HTTP API definition", definition_name.clone()),
+                    ],
+                    |validation| {
+                        for (index, route) in definition.value.routes.iter().enumerate() {
+                            let index = index.to_string();
+
+                            if HttpApiRouteMethod::from_str(&route.method).is_err() {
+                                validation.add_error_at(
+                                    validation.value_span(
+                                        &definition.source,
+                                        &[
+                                            "httpApi",
+                                            "definitions",
+                                            definition_name.as_str(),
+                                            "routes",
+                                            index.as_str(),
+                                            "method",
+                                        ],
+                                    ),
+                                    format!(
+                                        "Invalid method {} for route {}, accepted methods: {}",
+                                        route.method.log_color_error_highlight(),
+                                        route.path.log_color_highlight(),
+                                        HttpApiRouteMethod::iter()
+                                            .map(|method| method.as_str().log_color_highlight())
+                                            .join(", ")
+                                    ),
+                                );
+                            }
+
+                            if let Some(component_name) = &route.binding.component_name {
+                                if !self
+                                    .raw_components
+                                    .contains_key(&AppComponentName::from(component_name.clone()))
+                                {
+                                    validation.add_error_at(
+                                        validation.value_span(
+                                            &definition.source,
+                                            &[
+                                                "httpApi",
+                                                "definitions",
+                                                definition_name.as_str(),
+                                                "routes",
+                                                index.as_str(),
+                                                "binding",
+                                                "componentName",
+                                            ],
+                                        ),
+                                        format!(
+                                            "Route {} {} references unknown component {}",
+                                            route.method.log_color_highlight(),
+                                            route.path.log_color_highlight(),
+                                            component_name.log_color_error_highlight()
+                                        ),
+                                    );
+                                }
+                            }
+                        }
+                    },
+                );
+            }
+
+            for (profile_name, deployments) in &self.http_api_deployments {
+                for deployment in deployments {
+                    validation.with_context(
+                        vec![
+                            ("source", deployment.source.to_string_lossy().to_string()),
+                            ("HTTP API deployment", profile_name.clone()),
+                        ],
+                        |validation| {
+                            for definition_name in &deployment.value.definitions {
+                                if !self.http_api_definitions.contains_key(definition_name) {
+                                    validation.add_error(format!(
+                                        "Deployment for host {} references unknown HTTP API definition {}",
+                                        deployment.value.host.log_color_highlight(),
+                                        definition_name.log_color_error_highlight()
+                                    ));
+                                }
+                            }
+                        },
+                    );
+                }
+            }
+        }
+
+        fn validate_environments(&self, validation: &mut ValidationBuilder) {
+            let build_profiles = self
+                .resolved_components
+                .values()
+                .flat_map(|component| match &component.properties {
+                    ResolvedComponentProperties::Properties { .. } => vec![],
+                    ResolvedComponentProperties::Profiles { profiles, .. } => {
+                        profiles.keys().cloned().collect::<Vec<_>>()
+                    }
+                })
+                .collect::<BTreeSet<_>>();
+
+            for (environment_name, environment) in &self.environments {
+                validation.with_context(
+                    vec![
+                        ("source", environment.source.to_string_lossy().to_string()),
+                        ("environment", environment_name.to_string()),
+                    ],
+                    |validation| {
+                        if let Some(build_profile) = &environment.build_profile {
+                            if !build_profiles.contains(build_profile) {
+                                validation.add_error_at(
+                                    validation.value_span(
+                                        &environment.source,
+                                        &[
+                                            "environments",
+                                            environment_name.as_str(),
+                                            "buildProfile",
+                                        ],
+                                    ),
+                                    format!(
+                                        "Build profile {} is not defined by any component",
+                                        build_profile.as_str().log_color_error_highlight(),
+                                    ),
+                                );
+                            }
+                        }
+
+                        for component_name in environment.components.keys() {
+                            if !self.raw_components.contains_key(component_name) {
+                                validation.add_error_at(
+                                    validation.key_span(
+                                        &environment.source,
+                                        &[
+                                            "environments",
+                                            environment_name.as_str(),
+                                            "components",
+                                            component_name.as_str(),
+                                        ],
+                                    ),
+                                    format!(
+                                        "Environment {} references unknown component {}",
+                                        environment_name.as_str().log_color_highlight(),
+                                        component_name.as_str().log_color_error_highlight()
+                                    ),
+                                );
+                            }
+                        }
+                    },
+                );
+            }
+        }
+
         fn template_env<'a>() -> minijinja::Environment<'a> {
             let mut env = minijinja::Environment::new();
 
@@ -1280,15 +2139,86 @@ mod app_builder {
             env
         }
 
-        fn template_context(component_name: &AppComponentName) -> impl Serialize {
-            minijinja::context! {
-                componentName => component_name.as_str(),
-                component_name => component_name.as_str()
+        /// Manifest variables, overridden by template parameters, then by the builtin variables
+        fn template_context(
+            &self,
+            component_name: &AppComponentName,
+            params: BTreeMap<String, serde_yaml::Value>,
+        ) -> TemplateContext {
+            let mut ctx = self
+                .vars
+                .iter()
+                .map(|(name, value)| (name.clone(), value.value.clone()))
+                .collect::<TemplateContext>();
+            ctx.extend(params);
+            for name in TEMPLATE_BUILTIN_VARS {
+                ctx.insert(
+                    name.to_string(),
+                    serde_yaml::Value::String(component_name.to_string()),
+                );
             }
+            ctx
+        }
+
+        /// Template defaults overridden by the component parameters, returns None if
+        /// a parameter is unknown or a required one is missing
+        fn template_params(
+            validation: &mut ValidationBuilder,
+            source: &Path,
+            component_name: &AppComponentName,
+            template_name: &TemplateName,
+            template: &app_raw::ComponentTemplate,
+            component_params: BTreeMap<String, serde_yaml::Value>,
+        ) -> Option<BTreeMap<String, serde_yaml::Value>> {
+            let (params, valid) = validation.with_context_returning(vec![], |validation| {
+                for param_name in component_params.keys() {
+                    if !template.params.contains_key(param_name) {
+                        validation.add_error_at(
+                            validation.key_span(
+                                source,
+                                &["components", component_name.as_str(), "params", param_name.as_str()],
+                            ),
+                            format!(
+                                "Parameter {} cannot be used, as template {} has the following parameters: {}",
+                                param_name.log_color_error_highlight(),
+                                template_name.as_str().log_color_highlight(),
+                                template.params.keys().map(|s| s.log_color_highlight()).join(", ")
+                            ),
+                        );
+                    }
+                }
+
+                let mut params = template.params.clone();
+                params.extend(component_params);
+
+                for (param_name, value) in &params {
+                    if value.is_null() {
+                        validation.add_error_at(
+                            validation.key_span(
+                                source,
+                                &["components", component_name.as_str(), "template"],
+                            ),
+                            format!(
+                                "Missing required parameter {} for template {}",
+                                param_name.log_color_error_highlight(),
+                                template_name.as_str().log_color_highlight(),
+                            ),
+                        );
+                    }
+                }
+
+                params
+            });
+
+            valid.then_some(params)
         }
 
         fn resolve_components(&mut self, validation: &mut ValidationBuilder) {
             let template_env = Self::template_env();
+            // Referencing undefined variables or parameters is most likely a typo in the manifest,
+            // so it is only allowed for applications not using vars and template parameters
+            let mut strict_template_env = Self::template_env();
+            strict_template_env.set_undefined_behavior(minijinja::UndefinedBehavior::Strict);
 
             let components = std::mem::take(&mut self.raw_components);
 
@@ -1296,6 +2226,7 @@ mod app_builder {
                 self.resolve_component(
                     validation,
                     &template_env,
+                    &strict_template_env,
                     source,
                     component_name,
                     component,
@@ -1307,6 +2238,7 @@ mod app_builder {
             &mut self,
             validation: &mut ValidationBuilder,
             template_env: &minijinja::Environment,
+            strict_template_env: &minijinja::Environment,
             source: PathBuf,
             component_name: AppComponentName,
             component: app_raw::Component,
@@ -1320,28 +2252,80 @@ mod app_builder {
                     let properties = match &component.template {
                         Some(template_name) => {
                             let template_name = TemplateName::from(template_name.clone());
-                            match self.templates.get_mut(&template_name) {
-                                Some(template) => Self::resolve_templated_component_properties(
+                            match self.templates.get(&template_name) {
+                                Some(template) => Self::template_params(
                                     validation,
                                     &source,
-                                    template_env,
-                                    template_name,
+                                    &component_name,
+                                    &template_name,
                                     template,
-                                    component_name.clone(),
-                                    component,
-                                ),
+                                    component.params.clone(),
+                                )
+                                .and_then(|params| {
+                                    // Overrides are only rendered when the application uses vars
+                                    // or template parameters, so existing manifests using
+                                    // template-like syntax in overrides keep working
+                                    let uses_vars =
+                                        !self.vars.is_empty() || !template.params.is_empty();
+                                    Self::resolve_templated_component_properties(
+                                        validation,
+                                        &source,
+                                        &component_name,
+                                        if uses_vars {
+                                            strict_template_env
+                                        } else {
+                                            template_env
+                                        },
+                                        &self.template_context(&component_name, params),
+                                        uses_vars,
+                                        template_name,
+                                        template,
+                                        component,
+                                    )
+                                }),
                                 None => {
-                                    validation.add_error(format!(
-                                        "Component references unknown template: {}",
-                                        template_name.as_str().log_color_error_highlight()
-                                    ));
+                                    validation.add_error_at(
+                                        validation.value_span(
+                                            &source,
+                                            &["components", component_name.as_str(), "template"],
+                                        ),
+                                        format!(
+                                            "Component references unknown template: {}",
+                                            template_name.as_str().log_color_error_highlight()
+                                        ),
+                                    );
                                     None
                                 }
                             }
                         }
-                        None => Self::resolve_directly_defined_component_properties(
-                            validation, &source, component,
-                        ),
+                        None => {
+                            if !component.params.is_empty() {
+                                validation.add_error_at(
+                                    validation.key_span(
+                                        &source,
+                                        &["components", component_name.as_str(), "params"],
+                                    ),
+                                    format!(
+                                        "Property {} cannot be used without {}",
+                                        "params".log_color_highlight(),
+                                        "template".log_color_highlight()
+                                    ),
+                                );
+                            }
+                            // Components without templates are only rendered when the
+                            // application uses vars, so existing manifests using template-like
+                            // syntax in commands (e.g. {{.Id}} for docker) keep working
+                            let template_ctx = (!self.vars.is_empty())
+                                .then(|| self.template_context(&component_name, BTreeMap::new()));
+                            Self::resolve_directly_defined_component_properties(
+                                validation,
+                                &source,
+                                &component_name,
+                                strict_template_env,
+                                template_ctx.as_ref(),
+                                component,
+                            )
+                        }
                     };
                     if let Some(properties) = properties {
                         self.resolved_components
@@ -1354,10 +2338,12 @@ mod app_builder {
         fn resolve_templated_component_properties(
             validation: &mut ValidationBuilder,
             source: &Path,
+            component_name: &AppComponentName,
             template_env: &minijinja::Environment,
+            template_ctx: &TemplateContext,
+            render_overrides: bool,
             template_name: TemplateName,
-            template: &mut app_raw::ComponentTemplate,
-            component_name: AppComponentName,
+            template: &app_raw::ComponentTemplate,
             component: app_raw::Component,
         ) -> Option<ResolvedComponentProperties> {
             let (properties, _) = validation.with_context_returning(
@@ -1368,18 +2354,26 @@ mod app_builder {
 
                         if !template.profiles.is_empty() && !defined_property_names.is_empty() {
                             for property_name in defined_property_names {
-                                validation.add_error(
+                                validation.add_error_at(
+                                    validation.key_span(
+                                        source,
+                                        &["components", component_name.as_str(), property_name],
+                                    ),
                                     format!(
                                         "Property {} cannot be used, as the component uses a template with profiles",
                                         property_name.log_color_highlight()
-                                    )
+                                    ),
                                 );
                             }
                         }
 
                         for profile_name in component.profiles.keys() {
                             if !template.profiles.contains_key(profile_name) {
-                                validation.add_error(
+                                validation.add_error_at(
+                                    validation.key_span(
+                                        source,
+                                        &["components", component_name.as_str(), "profiles", profile_name.as_str()],
+                                    ),
                                     format!(
                                         "Profile {} cannot be used, as the component uses template {} with the following profiles: {}",
                                         profile_name.log_color_highlight(),
@@ -1392,7 +2386,11 @@ mod app_builder {
 
                         if let Some(default_profile) = &component.default_profile {
                             if !template.profiles.contains_key(default_profile) {
-                                validation.add_error(
+                                validation.add_error_at(
+                                    validation.value_span(
+                                        source,
+                                        &["components", component_name.as_str(), "defaultProfile"],
+                                    ),
                                     format!(
                                         "Default profile override {} cannot be used, as the component uses template {} with the following profiles: {}",
                                         default_profile.log_color_highlight(),
@@ -1410,9 +2408,10 @@ mod app_builder {
                                 validation,
                                 source,
                                 template_env,
+                                template_ctx,
+                                render_overrides,
                                 template_name,
                                 template,
-                                component_name,
                                 component.component_properties,
                             )
                         } else {
@@ -1420,9 +2419,10 @@ mod app_builder {
                                 validation,
                                 source,
                                 template_env,
+                                template_ctx,
+                                render_overrides,
                                 template_name,
                                 template,
-                                component_name,
                                 component.profiles,
                                 component.default_profile,
                             )
@@ -1438,18 +2438,20 @@ mod app_builder {
             validation: &mut ValidationBuilder,
             source: &Path,
             template_env: &minijinja::Environment,
+            template_ctx: &TemplateContext,
+            render_overrides: bool,
             template_name: TemplateName,
             template: &app_raw::ComponentTemplate,
-            component_name: AppComponentName,
             component_properties: app_raw::ComponentProperties,
         ) -> Option<ResolvedComponentProperties> {
             Self::convert_and_validate_templated_component_properties(
                 validation,
                 source,
                 template_env,
+                template_ctx,
+                render_overrides,
                 &template_name,
                 &template.component_properties,
-                &component_name,
                 Some(component_properties),
             )
             .map(|(properties, any_template_overrides)| {
@@ -1465,9 +2467,10 @@ mod app_builder {
             validation: &mut ValidationBuilder,
             source: &Path,
             template_env: &minijinja::Environment,
+            template_ctx: &TemplateContext,
+            render_overrides: bool,
             template_name: TemplateName,
             template: &app_raw::ComponentTemplate,
-            component_name: AppComponentName,
             mut profiles: HashMap<String, app_raw::ComponentProperties>,
             default_profile: Option<String>,
         ) -> Option<ResolvedComponentProperties> {
@@ -1486,9 +2489,10 @@ mod app_builder {
                                     validation,
                                     source,
                                     template_env,
+                                    template_ctx,
+                                    render_overrides,
                                     &template_name,
                                     template_component_properties,
-                                    &component_name,
                                     component_properties,
                                 )
                                 .into_iter()
@@ -1526,15 +2530,28 @@ mod app_builder {
         fn resolve_directly_defined_component_properties(
             validation: &mut ValidationBuilder,
             source: &Path,
+            component_name: &AppComponentName,
+            template_env: &minijinja::Environment,
+            template_ctx: Option<&TemplateContext>,
             component: app_raw::Component,
         ) -> Option<ResolvedComponentProperties> {
             if component.profiles.is_empty() {
                 Self::resolve_directly_defined_non_profiled_component_properties(
-                    validation, source, component,
+                    validation,
+                    source,
+                    component_name,
+                    template_env,
+                    template_ctx,
+                    component,
                 )
             } else {
                 Self::resolve_directly_defined_profiled_component_properties(
-                    validation, source, component,
+                    validation,
+                    source,
+                    component_name,
+                    template_env,
+                    template_ctx,
+                    component,
                 )
             }
         }
@@ -1542,29 +2559,44 @@ mod app_builder {
         fn resolve_directly_defined_profiled_component_properties(
             validation: &mut ValidationBuilder,
             source: &Path,
+            component_name: &AppComponentName,
+            template_env: &minijinja::Environment,
+            template_ctx: Option<&TemplateContext>,
             component: app_raw::Component,
         ) -> Option<ResolvedComponentProperties> {
             let valid =
                 validation.with_context(vec![], |validation| match &component.default_profile {
                     Some(default_profile) => {
                         if !component.profiles.contains_key(default_profile) {
-                            validation.add_error(format!(
-                                "Default profile {} not found in available profiles: {}",
-                                default_profile.log_color_highlight(),
-                                component
-                                    .profiles
-                                    .keys()
-                                    .map(|s| s.log_color_highlight())
-                                    .join(", ")
-                            ));
+                            validation.add_error_at(
+                                validation.value_span(
+                                    source,
+                                    &["components", component_name.as_str(), "defaultProfile"],
+                                ),
+                                format!(
+                                    "Default profile {} not found in available profiles: {}",
+                                    default_profile.log_color_highlight(),
+                                    component
+                                        .profiles
+                                        .keys()
+                                        .map(|s| s.log_color_highlight())
+                                        .join(", ")
+                                ),
+                            );
                         }
                     }
                     None => {
-                        validation.add_error(format!(
-                            "When {} is defined then {} is mandatory",
-                            "profiles".log_color_highlight(),
-                            "defaultProfile".log_color_highlight()
-                        ));
+                        validation.add_error_at(
+                            validation.key_span(
+                                source,
+                                &["components", component_name.as_str(), "profiles"],
+                            ),
+                            format!(
+                                "When {} is defined then {} is mandatory",
+                                "profiles".log_color_highlight(),
+                                "defaultProfile".log_color_highlight()
+                            ),
+                        );
                     }
                 });
 
@@ -1584,7 +2616,11 @@ mod app_builder {
                                 vec![("profile", profile_name.to_string())],
                                 |validation| {
                                     Self::convert_and_validate_component_properties(
-                                        validation, source, properties,
+                                        validation,
+                                        source,
+                                        template_env,
+                                        template_ctx,
+                                        properties,
                                     )
                                 },
                             );
@@ -1600,15 +2636,24 @@ mod app_builder {
         fn resolve_directly_defined_non_profiled_component_properties(
             validation: &mut ValidationBuilder,
             source: &Path,
+            component_name: &AppComponentName,
+            template_env: &minijinja::Environment,
+            template_ctx: Option<&TemplateContext>,
             component: app_raw::Component,
         ) -> Option<ResolvedComponentProperties> {
             let valid = validation.with_context(vec![], |validation| {
                 if component.default_profile.is_some() {
-                    validation.add_error(format!(
-                        "When {} is not defined then {} should not be defined",
-                        "profiles".log_color_highlight(),
-                        "defaultProfile".log_color_highlight()
-                    ));
+                    validation.add_error_at(
+                        validation.key_span(
+                            source,
+                            &["components", component_name.as_str(), "defaultProfile"],
+                        ),
+                        format!(
+                            "When {} is not defined then {} should not be defined",
+                            "profiles".log_color_highlight(),
+                            "defaultProfile".log_color_highlight()
+                        ),
+                    );
                 }
             });
 
@@ -1617,6 +2662,8 @@ mod app_builder {
                     Self::convert_and_validate_component_properties(
                         validation,
                         source,
+                        template_env,
+                        template_ctx,
                         component.component_properties,
                     )
                 })
@@ -1628,20 +2675,43 @@ mod app_builder {
                 })
         }
 
+        /// Template properties are always rendered, while the component overrides are only
+        /// rendered with render_overrides, otherwise they are used as is
         fn convert_and_validate_templated_component_properties(
             validation: &mut ValidationBuilder,
             source: &Path,
             template_env: &minijinja::Environment,
+            template_ctx: &TemplateContext,
+            render_overrides: bool,
             template_name: &TemplateName,
             template_properties: &app_raw::ComponentProperties,
-            component_name: &AppComponentName,
             component_properties: Option<app_raw::ComponentProperties>,
         ) -> Option<(ComponentProperties, bool)> {
+            let component_properties = match component_properties
+                .map(|properties| {
+                    if render_overrides {
+                        properties.render(template_env, template_ctx)
+                    } else {
+                        Ok(properties)
+                    }
+                })
+                .transpose()
+            {
+                Ok(component_properties) => component_properties,
+                Err(err) => {
+                    validation.add_error(format!(
+                        "Failed to render component properties, error: {}",
+                        err.to_string().log_color_error_highlight()
+                    ));
+                    return None;
+                }
+            };
+
             ComponentProperties::from_raw_template(
                 validation,
                 source,
                 template_env,
-                &Self::template_context(component_name),
+                template_ctx,
                 template_properties,
             )
             .inspect_err(|err| {
@@ -1676,14 +2746,36 @@ mod app_builder {
             })
         }
 
+        /// Directly defined component properties are only rendered if there is a template
+        /// context, otherwise they are used as is
         fn convert_and_validate_component_properties(
             validation: &mut ValidationBuilder,
             source: &Path,
+            template_env: &minijinja::Environment,
+            template_ctx: Option<&TemplateContext>,
             component_properties: app_raw::ComponentProperties,
         ) -> Option<ComponentProperties> {
-            ComponentProperties::from_raw(validation, source, component_properties).inspect(
-                |properties| Self::validate_resolved_component_properties(validation, properties),
-            )
+            match template_ctx {
+                Some(template_ctx) => ComponentProperties::from_raw_template(
+                    validation,
+                    source,
+                    template_env,
+                    template_ctx,
+                    &component_properties,
+                )
+                .inspect_err(|err| {
+                    validation.add_error(format!(
+                        "Failed to render component properties, error: {}",
+                        err.to_string().log_color_error_highlight()
+                    ))
+                })
+                .ok()
+                .flatten(),
+                None => ComponentProperties::from_raw(validation, source, component_properties),
+            }
+            .inspect(|properties| {
+                Self::validate_resolved_component_properties(validation, properties)
+            })
         }
 
         fn validate_resolved_component_properties(
@@ -1705,3 +2797,460 @@ mod app_builder {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::model::app::{AppComponentName, Application, EnvironmentName, InitialComponentFile};
+    use crate::model::app_raw;
+    use assert2::check;
+    use indoc::indoc;
+    use itertools::Itertools;
+    use std::path::PathBuf;
+    use test_r::test;
+
+    fn application(yaml: &str) -> (Option<Application>, Vec<String>) {
+        let app = app_raw::ApplicationWithSource::from_yaml_string(
+            PathBuf::from("golem.yaml"),
+            yaml.to_string(),
+        )
+        .unwrap();
+        let (app, _, errors) = Application::from_raw_apps(vec![app]).into_product();
+        (app, errors)
+    }
+
+    #[test]
+    fn template_params_and_vars() {
+        let (app, errors) = application(indoc! {"
+            vars:
+              profile: release
+            templates:
+              rust:
+                params:
+                  feature: null
+                  target: wasm32-wasip1
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: target/{{ target }}/{{ profile }}/{{ component_name | to_snake_case }}.wasm
+                build:
+                  - command: cargo component build --{{ profile }} --features {{ feature }}
+            components:
+              app:a:
+                template: rust
+                params:
+                  feature: a
+              app:b:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: target/{{ profile }}/{{ componentName }}.wasm
+        "});
+
+        check!(errors.is_empty());
+        let app = app.unwrap();
+
+        let a = app.component_properties(&AppComponentName::from("app:a"), None);
+        check!(a.component_wasm == "target/wasm32-wasip1/release/app_a.wasm");
+        check!(a.build[0].command == "cargo component build --release --features a");
+
+        let b = app.component_properties(&AppComponentName::from("app:b"), None);
+        check!(b.component_wasm == "target/release/app:b.wasm");
+    }
+
+    #[test]
+    fn components_without_templates_and_vars_are_not_rendered() {
+        let (app, errors) = application(indoc! {r#"
+            components:
+              app:a:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: target/app_a.wasm
+                build:
+                  - command: docker inspect --format '{{.Id}}' app-a
+                  - command: echo ${#HOME} {# not a comment #}
+                    shell: true
+        "#});
+
+        check!(errors.is_empty());
+        let app = app.unwrap();
+
+        let a = app.component_properties(&AppComponentName::from("app:a"), None);
+        check!(a.build[0].command == "docker inspect --format '{{.Id}}' app-a");
+        check!(a.build[1].command == "echo ${#HOME} {# not a comment #}");
+    }
+
+    #[test]
+    fn templated_component_overrides_without_vars_are_not_rendered() {
+        let (app, errors) = application(indoc! {r#"
+            templates:
+              rust:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: target/{{ component_name | to_snake_case }}.wasm
+                build:
+                  - command: cargo component build
+            components:
+              app:a:
+                template: rust
+                build:
+                  - command: docker inspect --format '{{.Id}}' app-a
+                customCommands:
+                  deploy:
+                    - command: echo ${{ github.ref }}
+        "#});
+
+        check!(errors.is_empty());
+        let app = app.unwrap();
+
+        let a = app.component_properties(&AppComponentName::from("app:a"), None);
+        check!(a.component_wasm == "target/app_a.wasm");
+        check!(a.build[0].command == "docker inspect --format '{{.Id}}' app-a");
+        check!(a.custom_commands["deploy"][0].command == "echo ${{ github.ref }}");
+    }
+
+    #[test]
+    fn rendered_components_can_escape_template_syntax() {
+        let (app, errors) = application(indoc! {r#"
+            vars:
+              image: app-a
+            components:
+              app:a:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: target/app_a.wasm
+                build:
+                  - command: docker inspect --format '{% raw %}{{.Id}}{% endraw %}' {{ image }}
+        "#});
+
+        check!(errors.is_empty());
+        let app = app.unwrap();
+
+        let a = app.component_properties(&AppComponentName::from("app:a"), None);
+        check!(a.build[0].command == "docker inspect --format '{{.Id}}' app-a");
+    }
+
+    #[test]
+    fn template_params_errors() {
+        let (app, errors) = application(indoc! {"
+            templates:
+              rust:
+                params:
+                  feature: null
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: '{{ feature }}-{{ unknown }}.wasm'
+            components:
+              app:a:
+                template: rust
+                params:
+                  featur: a
+              app:b:
+                template: rust
+                params:
+                  feature: b
+        "});
+
+        check!(app.is_none());
+        check!(errors.len() == 3);
+        check!(errors.iter().any(|error| error.contains("featur")));
+        check!(errors
+            .iter()
+            .any(|error| error.contains("Missing required parameter")));
+        check!(errors
+            .iter()
+            .any(|error| error.contains("Failed to render template")));
+    }
+
+    #[test]
+    fn plugins_with_overrides() {
+        let (app, errors) = application(indoc! {"
+            templates:
+              rust:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: component.wasm
+                plugins:
+                  - name: tracing
+                    version: 1.0.0
+            components:
+              app:a:
+                template: rust
+              app:b:
+                template: rust
+                plugins:
+                  - name: tracing
+                    version: 1.1.0
+                    priority: 10
+                    parameters:
+                      level: debug
+                uninstallExtraPlugins: true
+              app:c:
+                template: rust
+                plugins: []
+        "});
+
+        check!(errors.is_empty());
+        let app = app.unwrap();
+
+        let a = app.component_properties(&AppComponentName::from("app:a"), None);
+        let a_plugins = a.plugins.as_ref().unwrap();
+        check!(a_plugins.len() == 1);
+        check!(a_plugins[0].version == "1.0.0");
+        check!(a_plugins[0].priority == 0);
+        check!(!a.uninstall_extra_plugins);
+
+        let b = app.component_properties(&AppComponentName::from("app:b"), None);
+        let b_plugins = b.plugins.as_ref().unwrap();
+        check!(b_plugins[0].version == "1.1.0");
+        check!(b_plugins[0].priority == 10);
+        check!(b_plugins[0].parameters.get("level").map(|s| s.as_str()) == Some("debug"));
+        check!(b.uninstall_extra_plugins);
+
+        let c = app.component_properties(&AppComponentName::from("app:c"), None);
+        check!(c.plugins.as_ref().unwrap().is_empty());
+    }
+
+    #[test]
+    fn plugins_duplicated() {
+        let (app, errors) = application(indoc! {"
+            components:
+              app:a:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: component.wasm
+                plugins:
+                  - name: tracing
+                    version: 1.0.0
+                  - name: tracing
+                    version: 1.1.0
+        "});
+
+        check!(app.is_none());
+        check!(errors.len() == 1);
+        check!(errors[0].contains("defined more than once"));
+    }
+
+    #[test]
+    fn workers() {
+        let (app, errors) = application(indoc! {"
+            templates:
+              rust:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: component.wasm
+                workers:
+                  - name: '{{ componentName | to_snake_case }}-main'
+                    args: [--verbose]
+                    env:
+                      COMPONENT: '{{ componentName }}'
+            components:
+              app:a:
+                template: rust
+              app:b:
+                template: rust
+                workers:
+                  - name: b
+                  - name: b
+        "});
+
+        check!(app.is_none());
+        check!(errors.len() == 1);
+        check!(errors[0].contains("defined more than once"));
+
+        let (app, errors) = application(indoc! {"
+            templates:
+              rust:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: component.wasm
+                workers:
+                  - name: '{{ componentName | to_snake_case }}-main'
+                    args: [--verbose]
+                    env:
+                      COMPONENT: '{{ componentName }}'
+            components:
+              app:a:
+                template: rust
+        "});
+
+        check!(errors.is_empty());
+        let app = app.unwrap();
+
+        let a = app.component_properties(&AppComponentName::from("app:a"), None);
+        check!(a.workers.len() == 1);
+        check!(a.workers[0].name == "app_a-main");
+        check!(a.workers[0].args == vec!["--verbose".to_string()]);
+        check!(a.workers[0].env.get("COMPONENT").map(|s| s.as_str()) == Some("app:a"));
+    }
+
+    #[test]
+    fn environments() {
+        let (app, errors) = application(indoc! {"
+            components:
+              app:a:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: component.wasm
+                files:
+                  - sourcePath: https://example.com/dev/config.json
+                    targetPath: /config.json
+                  - sourcePath: https://example.com/data.json
+                    targetPath: /data.json
+                workers:
+                  - name: main
+                    env:
+                      LOG_LEVEL: debug
+                      REGION: local
+            environments:
+              prod:
+                profile: cloud
+                project: shop
+                components:
+                  app:a:
+                    files:
+                      - sourcePath: https://example.com/prod/config.json
+                        targetPath: /config.json
+                    env:
+                      LOG_LEVEL: info
+              staging:
+                profile: staging
+                buildProfile: debug
+                components:
+                  app:b: {}
+        "});
+
+        check!(app.is_none());
+        check!(errors.len() == 2);
+        check!(errors
+            .iter()
+            .any(|error| error.contains("debug") && error.contains("Build profile")));
+        check!(errors
+            .iter()
+            .any(|error| error.contains("app:b") && error.contains("unknown component")));
+
+        let (app, errors) = application(indoc! {"
+            components:
+              app:a:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: component.wasm
+                files:
+                  - sourcePath: https://example.com/dev/config.json
+                    targetPath: /config.json
+                  - sourcePath: https://example.com/data.json
+                    targetPath: /data.json
+                workers:
+                  - name: main
+                    env:
+                      LOG_LEVEL: debug
+                      REGION: local
+            environments:
+              prod:
+                profile: cloud
+                project: shop
+                components:
+                  app:a:
+                    files:
+                      - sourcePath: https://example.com/prod/config.json
+                        targetPath: /config.json
+                    env:
+                      LOG_LEVEL: info
+        "});
+
+        check!(errors.is_empty());
+        let app = app.unwrap();
+        let component_name = AppComponentName::from("app:a");
+        let prod = EnvironmentName::from("prod");
+
+        let environment = app.environment(&prod).unwrap();
+        check!(environment.profile == "cloud");
+        check!(environment.project.as_deref() == Some("shop"));
+        check!(environment.build_profile.is_none());
+
+        let sources = |files: Vec<InitialComponentFile>| {
+            files
+                .iter()
+                .map(|file| file.source.as_url().to_string())
+                .sorted()
+                .collect::<Vec<_>>()
+        };
+        check!(
+            sources(app.component_files(&component_name, None, None))
+                == vec![
+                    "https://example.com/data.json",
+                    "https://example.com/dev/config.json"
+                ]
+        );
+        check!(
+            sources(app.component_files(&component_name, None, Some(&prod)))
+                == vec![
+                    "https://example.com/data.json",
+                    "https://example.com/prod/config.json"
+                ]
+        );
+
+        let workers = app.component_workers(&component_name, None, Some(&prod));
+        check!(workers[0].env.get("LOG_LEVEL").map(|s| s.as_str()) == Some("info"));
+        check!(workers[0].env.get("REGION").map(|s| s.as_str()) == Some("local"));
+    }
+
+    #[test]
+    fn error_source_locations() {
+        let (app, errors) = application(indoc! {"
+            components:
+              app:a:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: component.wasm
+                defaultProfile: release
+            dependencies:
+              app:a:
+                - type: wasm-rcp
+                  target: app:a
+        "});
+
+        check!(app.is_none());
+        check!(errors.len() == 2);
+        check!(errors
+            .iter()
+            .any(|error| error.contains("golem.yaml:6:5") && error.contains("defaultProfile")));
+        check!(errors
+            .iter()
+            .any(|error| error.contains("golem.yaml:9:13") && error.contains("^^^^^^^^")));
+    }
+
+    #[test]
+    fn unknown_command_conditions_are_warnings_with_locations() {
+        let app = app_raw::ApplicationWithSource::from_yaml_string(
+            PathBuf::from("golem.yaml"),
+            indoc! {"
+                components:
+                  app:a:
+                    sourceWit: wit
+                    generatedWit: wit-generated
+                    componentWasm: component.wasm
+                    build:
+                      - command: cargo build
+                        when:
+                          cpu: x86_64
+                customCommands:
+                  deploy:
+                    - command: echo deploy
+                      when:
+                        os: linux
+                        shell: bash
+            "}
+            .to_string(),
+        )
+        .unwrap();
+        let (app, warns, errors) = Application::from_raw_apps(vec![app]).into_product();
+
+        check!(app.is_some());
+        check!(errors.is_empty());
+        check!(warns.len() == 2);
+        check!(warns
+            .iter()
+            .any(|warn| warn.contains("golem.yaml:9:11") && warn.contains("cpu")));
+        check!(warns
+            .iter()
+            .any(|warn| warn.contains("golem.yaml:15:9") && warn.contains("shell")));
+    }
+}
@@ -1,28 +1,50 @@
 use crate::fs;
 use crate::log::LogColorize;
+use crate::model::app::DependencyType;
 use crate::model::component::AppComponentType;
-use anyhow::{anyhow, Context};
+use crate::model::source_map::{SourceSpan, YamlSourceMap};
+use anyhow::anyhow;
 use golem_common::model::{ComponentFilePath, ComponentFilePermissions};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use std::time::Duration;
+
+pub const APP_MANIFEST_SCHEMA_ID: &str =
+    "https://schema.golem.cloud/app/golem/1.2.0/golem.schema.json";
 
 #[derive(Clone, Debug)]
 pub struct ApplicationWithSource {
     pub source: PathBuf,
     pub application: Application,
+    pub source_map: YamlSourceMap,
 }
 
 impl ApplicationWithSource {
     pub fn from_yaml_file(file: PathBuf) -> anyhow::Result<Self> {
-        Self::from_yaml_string(file.clone(), fs::read_to_string(file.clone())?)
-            .with_context(|| anyhow!("Failed to load source {}", file.log_color_highlight()))
+        let yaml = fs::read_to_string(file.clone())?;
+        Self::from_yaml_string(file.clone(), yaml.clone()).map_err(|err| {
+            let span = err
+                .location()
+                .map(|location| {
+                    SourceSpan::new(&file, &yaml, location.line(), location.column(), 1)
+                })
+                .map(|span| format!("\n{}", span.render()))
+                .unwrap_or_default();
+            anyhow!(err).context(format!(
+                "Failed to load source {}{}",
+                file.log_color_highlight(),
+                span
+            ))
+        })
     }
 
     pub fn from_yaml_string(source: PathBuf, string: String) -> serde_yaml::Result<Self> {
         Ok(Self {
-            source,
             application: Application::from_yaml_str(string.as_str())?,
+            source_map: YamlSourceMap::new(source.clone(), &string),
+            source,
         })
     }
 
@@ -31,25 +53,47 @@ impl ApplicationWithSource {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct Application {
+    /// Local glob patterns of other manifests, or remote manifests as HTTP(S) URLs or as
+    /// `git+<repository>#<ref>:<path>`, remote ones are vendored and locked in golem.lock.yaml
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub includes: Vec<String>,
+    /// Temporary directory used for generating and building WIT and WASM artifacts, defaults to
+    /// golem-temp
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub temp_dir: Option<String>,
+    /// Source directories for common WIT dependency packages
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub wit_deps: Vec<String>,
+    /// Manifest level variables, which can be used in component and template properties, e.g.
+    /// {{ rustProfile }}. Components without a template are only rendered when vars are defined,
+    /// template syntax can be escaped with {% raw %}...{% endraw %}
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(with = "BTreeMap<String, serde_json::Value>")]
+    pub vars: BTreeMap<String, serde_yaml::Value>,
+    /// Component definition templates by template names
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub templates: HashMap<String, ComponentTemplate>,
+    /// Components by component names
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub components: HashMap<String, Component>,
+    /// Component dependencies by component names
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub dependencies: HashMap<String, Vec<Dependency>>,
+    /// User defined custom commands
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub custom_commands: HashMap<String, Vec<ExternalCommand>>,
+    /// User defined extra paths used by the clean command
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub clean: Vec<String>,
+    /// HTTP API definitions and deployments, created or updated by app deploy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_api: Option<HttpApi>,
+    /// Named deploy target environments, selected with `app deploy --env <name>`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub environments: BTreeMap<String, Environment>,
 }
 
 impl Application {
@@ -60,61 +104,124 @@ impl Application {
     pub fn to_yaml_string(&self) -> String {
         serde_yaml::to_string(self).expect("Failed to serialize Application as YAML")
     }
+
+    /// JSON schema of the application manifest, generated from the manifest types
+    pub fn json_schema() -> serde_json::Value {
+        let mut schema = serde_json::to_value(schemars::schema_for!(Application))
+            .expect("Failed to serialize application manifest schema");
+        if let Some(schema) = schema.as_object_mut() {
+            schema.insert("$id".to_string(), APP_MANIFEST_SCHEMA_ID.into());
+            schema.insert("title".to_string(), "Golem Application Manifest".into());
+        }
+        schema
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct ComponentTemplate {
+    /// Parameters of the template with their default values, null is used for required ones
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(with = "BTreeMap<String, serde_json::Value>")]
+    pub params: BTreeMap<String, serde_yaml::Value>,
     #[serde(flatten)]
     pub component_properties: ComponentProperties,
+    /// Component definition profiles by profile names
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub profiles: HashMap<String, ComponentProperties>,
+    /// Profile used when no build profile is selected
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_profile: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct Component {
+    /// Component template used for defining this component
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub template: Option<String>,
+    /// Parameters for the referenced component template
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(with = "BTreeMap<String, serde_json::Value>")]
+    pub params: BTreeMap<String, serde_yaml::Value>,
     #[serde(flatten)]
     pub component_properties: ComponentProperties,
+    /// Component definition profiles by profile names
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub profiles: HashMap<String, ComponentProperties>,
+    /// Profile used when no build profile is selected
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_profile: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// File entry of the initial component file system
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct InitialComponentFile {
+    /// Source path for the component file: either a local file or an URL
     pub source_path: String,
+    /// Target path for the component file, must be an absolute path
+    #[schemars(with = "String")]
     pub target_path: ComponentFilePath,
+    /// Permissions of the component file
+    #[schemars(with = "Option<ComponentFilePermissionsSchema>")]
     pub permissions: Option<ComponentFilePermissions>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Schema of [ComponentFilePermissions], which does not implement [JsonSchema]
+#[derive(JsonSchema)]
+#[schemars(rename_all = "kebab-case")]
+#[allow(dead_code)]
+enum ComponentFilePermissionsSchema {
+    ReadOnly,
+    ReadWrite,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct ComponentProperties {
+    /// Source WIT directory for the user defined component WIT source(s)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub source_wit: Option<String>,
+    /// Generated WIT directory created by the golem tooling, which handles exported interface
+    /// extraction and includes resolved package and stub dependencies
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub generated_wit: Option<String>,
+    /// File path for the built WASM component
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub component_wasm: Option<String>,
+    /// File path for the linked WASM component which is ready to be uploaded to Golem
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub linked_wasm: Option<String>,
+    /// Commands used for creating the component WASM
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub build: Vec<ExternalCommand>,
+    /// User defined custom commands
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub custom_commands: HashMap<String, Vec<ExternalCommand>>,
+    /// User defined extra paths used by the clean command
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub clean: Vec<String>,
+    /// Component type, defaults to durable
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub component_type: Option<AppComponentType>,
+    /// Initial component file system
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub files: Vec<InitialComponentFile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_link: Option<PostLinkProperties>,
+    /// Plugins installed for the component during deploy, installations are created, updated or
+    /// reinstalled to match this list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugins: Option<Vec<PluginInstallation>>,
+    /// Uninstall plugins which are installed for the component but not defined in plugins during
+    /// deploy, defaults to false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uninstall_extra_plugins: Option<bool>,
+    /// Named workers created during deploy if they do not exist yet, existing workers are checked
+    /// for differences in args and env
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workers: Vec<Worker>,
 }
 
 impl ComponentProperties {
@@ -153,30 +260,284 @@ impl ComponentProperties {
             vec.push("files");
         }
 
+        if self.post_link.is_some() {
+            vec.push("postLink");
+        }
+
+        if self.plugins.is_some() {
+            vec.push("plugins");
+        }
+
+        if self.uninstall_extra_plugins.is_some() {
+            vec.push("uninstallExtraPlugins");
+        }
+
+        if !self.workers.is_empty() {
+            vec.push("workers");
+        }
+
         vec
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Built-in steps applied to the linked component, before adding metadata
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PostLinkProperties {
+    /// Remove debug info custom sections (DWARF, names and source maps)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strip_debug_info: Option<bool>,
+    /// Remove all custom sections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strip_custom_sections: Option<bool>,
+    /// Validate the component
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validate: Option<bool>,
+}
+
+/// Plugin installation of a component, reconciled with the installed plugins during deploy
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PluginInstallation {
+    /// Plugin name
+    pub name: String,
+    /// Plugin version
+    pub version: String,
+    /// Priority of the plugin, largest priority is applied first
+    #[serde(default)]
+    pub priority: i32,
+    /// Parameters passed to the plugin
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parameters: BTreeMap<String, String>,
+}
+
+/// Named worker of a component, created during deploy if it does not exist yet
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Worker {
+    /// Worker name
+    pub name: String,
+    /// Worker arguments
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Worker environment variables
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+}
+
+/// External command with optional sources and targets for up-to-date checks
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct ExternalCommand {
+    /// External command to execute
     pub command: String,
+    /// Working directory for the command, defaults to the directory of the manifest in which the
+    /// component is defined
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dir: Option<String>,
+    /// Directories that are deleted before running the command, runs before mkdirs
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub rmdirs: Vec<String>,
+    /// Directories that are created before running the command, runs after rmdirs
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub mkdirs: Vec<String>,
+    /// Inputs (paths and globs) of the command, used for up-to-date checks together with targets
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub sources: Vec<String>,
+    /// Outputs (paths and globs) of the command, used for up-to-date checks together with sources
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub targets: Vec<String>,
+    /// Environment variables for the command, values can reference other environment variables,
+    /// e.g. ${HOME}
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Env file with KEY=VALUE lines, relative to the working directory of the command, variables
+    /// defined in env take precedence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env_file: Option<String>,
+    /// Timeout for the command as an ISO 8601 duration, e.g. PT10M
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+    /// Number of retries if the command fails or times out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    /// Executes the command using the system shell (sh -c, or cmd /C on Windows), so pipes and
+    /// redirects can be used
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub shell: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<ExternalCommandCondition>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Conditions for executing a command, all the defined conditions have to be met
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalCommandCondition {
+    /// Operating system (e.g. linux, macos, windows) or OS family (unix, windows)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os: Option<String>,
+    /// CPU architecture, e.g. x86_64, aarch64
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arch: Option<String>,
+    /// Name of an environment variable that has to be set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env_set: Option<String>,
+    /// Environment variables that have to be set to the given values
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env_equals: BTreeMap<String, String>,
+    /// Tool that has to be available, e.g. wasm-opt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_available: Option<String>,
+    // Unknown keys are collected (instead of denied), so they can be reported as warnings
+    #[serde(flatten)]
+    #[schemars(skip)]
+    pub unknown: BTreeMap<String, serde_yaml::Value>,
+}
+
+impl ExternalCommand {
+    /// Parses the timeout, which is defined as an ISO 8601 duration, e.g. PT10M
+    pub fn timeout_duration(&self) -> anyhow::Result<Option<Duration>> {
+        self.timeout
+            .as_ref()
+            .map(|timeout| {
+                iso8601::duration(timeout)
+                    .map(Duration::from)
+                    .map_err(|err| {
+                        anyhow!(
+                            "Invalid timeout {}, expected an ISO 8601 duration (e.g. PT10M): {}",
+                            timeout.log_color_highlight(),
+                            err
+                        )
+                    })
+            })
+            .transpose()
+    }
+}
+
+/// Component dependency
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct Dependency {
+    /// Dependency type, e.g. wasm-rpc
     #[serde(rename = "type")]
+    #[schemars(with = "DependencyType")]
     pub type_: String,
+    /// Target component name
+    #[schemars(required)]
     pub target: Option<String>,
 }
+
+/// Deploy target environment, binding a CLI profile, a cloud project and a build profile
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Environment {
+    /// CLI profile used for deploying to the environment
+    pub profile: String,
+    /// Cloud project used for deploying to the environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Build profile used for the components of the environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_profile: Option<String>,
+    /// Component overrides by component names
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub components: BTreeMap<String, EnvironmentComponent>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct EnvironmentComponent {
+    /// Additional files, replacing the component files with the same target path
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<InitialComponentFile>,
+    /// Environment variables added to the workers of the component
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HttpApi {
+    /// HTTP API definitions by API definition IDs
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub definitions: BTreeMap<String, HttpApiDefinition>,
+    /// Deployments by CLI profile names
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub deployments: BTreeMap<String, Vec<HttpApiDeployment>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HttpApiDefinition {
+    /// Version used when creating the definition, later versions are bumped automatically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routes: Vec<HttpApiDefinitionRoute>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HttpApiDefinitionRoute {
+    /// HTTP method, case insensitive
+    pub method: String,
+    /// Path pattern, e.g. /{user}/contents
+    pub path: String,
+    /// Security scheme used for the route
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security: Option<String>,
+    pub binding: HttpApiDefinitionBinding,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HttpApiDefinitionBinding {
+    /// Binding type: default, file-server, http-handler or cors-preflight, defaults to default
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    /// Name of the component defined in the application manifest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component_name: Option<String>,
+    /// Component version, defaults to the latest version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component_version: Option<u64>,
+    /// Rib expression for the worker name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker_name: Option<String>,
+    /// Rib expression for the idempotency key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    /// Rib expression for the response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HttpApiDeployment {
+    pub host: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdomain: Option<String>,
+    /// HTTP API definition IDs to deploy
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub definitions: Vec<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::app_raw::Application;
+    use assert2::check;
+    use test_r::test;
+
+    #[test]
+    fn checked_in_schema_is_up_to_date() {
+        // Regenerate the checked-in schema with:
+        //   golem-cli app schema --output schema.golem.cloud/app/golem/1.2.0/golem.schema.json
+        let checked_in_schema: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../schema.golem.cloud/app/golem/1.2.0/golem.schema.json"
+        ))
+        .unwrap();
+
+        check!(checked_in_schema == Application::json_schema());
+    }
+}
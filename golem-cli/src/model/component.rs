@@ -29,6 +29,7 @@ use golem_wasm_ast::analysis::{
     NameTypePair, TypeEnum, TypeFlags, TypeRecord, TypeTuple, TypeVariant,
 };
 use rib::{ParsedFunctionName, ParsedFunctionSite};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::fmt::Display;
@@ -73,6 +74,42 @@ pub struct Component {
     pub files: Vec<InitialComponentFile>,
 }
 
+impl Component {
+    /// Version of a tool in the processed-by fields of the component producers sections
+    pub fn processed_by_version(&self, tool: &str) -> Option<&str> {
+        self.metadata
+            .producers
+            .iter()
+            .flat_map(|producers| &producers.fields)
+            .filter(|field| field.name == "processed-by")
+            .flat_map(|field| &field.values)
+            .find(|value| value.name == tool)
+            .map(|value| value.version.as_str())
+    }
+
+    /// Dynamically linked interfaces with the target interface names per resource
+    pub fn dynamic_linking_targets(&self) -> BTreeMap<String, BTreeMap<String, String>> {
+        self.metadata
+            .dynamic_linking
+            .iter()
+            .map(|(name, link)| {
+                (
+                    name.clone(),
+                    match link {
+                        DynamicLinkedInstance::WasmRpc(links) => links
+                            .targets
+                            .iter()
+                            .map(|(resource, target)| {
+                                (resource.clone(), target.interface_name.clone())
+                            })
+                            .collect::<BTreeMap<String, String>>(),
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
 impl From<golem_client::model::Component> for Component {
     fn from(value: golem_client::model::Component) -> Self {
         Component {
@@ -109,7 +146,7 @@ impl From<golem_cloud_client::model::Component> for Component {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 #[derive(Default)]
 pub enum AppComponentType {
@@ -210,25 +247,7 @@ impl From<&Component> for ComponentView {
             created_at: value.created_at,
             project_id: value.project_id,
             exports: show_exported_functions(&value.metadata.exports, true),
-            dynamic_linking: value
-                .metadata
-                .dynamic_linking
-                .iter()
-                .map(|(name, link)| {
-                    (
-                        name.clone(),
-                        match link {
-                            DynamicLinkedInstance::WasmRpc(links) => links
-                                .targets
-                                .iter()
-                                .map(|(resource, target)| {
-                                    (resource.clone(), target.interface_name.clone())
-                                })
-                                .collect::<BTreeMap<String, String>>(),
-                        },
-                    )
-                })
-                .collect(),
+            dynamic_linking: value.dynamic_linking_targets(),
             files: value.files.clone(),
         }
     }
@@ -12,29 +12,738 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::model::{ComponentName, WorkerName};
+use crate::command::shared_args::WorkerRolloutArgs;
+use crate::model::component::{AppComponentType, Component};
+use crate::model::{ComponentName, WorkerMetadata, WorkerName};
+use golem_client::model::{UpdateRecord, WorkerStatus};
+use golem_common::model::ComponentFilePermissions;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
 
+/// Results of updating or redeploying the existing workers of components
 #[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct TryUpdateAllWorkersResult {
-    pub triggered: Vec<WorkerUpdateAttempt>,
-    pub failed: Vec<WorkerUpdateAttempt>,
+pub struct WorkerRolloutResult {
+    pub attempts: Vec<WorkerRolloutAttempt>,
+    /// Reason of stopping the rollout, the remaining workers are skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aborted: Option<String>,
 }
 
-impl TryUpdateAllWorkersResult {
-    pub fn extend(&mut self, other: TryUpdateAllWorkersResult) {
-        self.triggered.extend(other.triggered);
-        self.failed.extend(other.failed);
+impl WorkerRolloutResult {
+    pub fn extend(&mut self, other: WorkerRolloutResult) {
+        self.attempts.extend(other.attempts);
+        if self.aborted.is_none() {
+            self.aborted = other.aborted;
+        }
+    }
+
+    pub fn count(&self, outcome: WorkerRolloutOutcome) -> usize {
+        self.attempts
+            .iter()
+            .filter(|attempt| attempt.outcome == outcome)
+            .count()
     }
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct WorkerUpdateAttempt {
+pub struct WorkerRolloutAttempt {
     pub component_name: ComponentName,
-    pub target_version: u64,
     pub worker_name: WorkerName,
+    pub target_version: u64,
+    /// Canary workers are rolled out first, and the rollout only continues if they are healthy
+    pub canary: bool,
+    pub outcome: WorkerRolloutOutcome,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
 }
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WorkerRolloutOutcome {
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+/// Decisions of a worker rollout: selecting the canary workers, deciding which workers have to
+/// be waited for, and aborting the rollout on failures. Once the rollout is aborted, the workers
+/// not yet started are recorded as skipped.
+pub struct WorkerRollout<'a> {
+    component_name: &'a ComponentName,
+    target_version: u64,
+    args: &'a WorkerRolloutArgs,
+    worker_count: usize,
+    failed_count: usize,
+    result: WorkerRolloutResult,
+}
+
+impl<'a> WorkerRollout<'a> {
+    pub fn new(
+        component_name: &'a ComponentName,
+        target_version: u64,
+        args: &'a WorkerRolloutArgs,
+        worker_count: usize,
+    ) -> Self {
+        Self {
+            component_name,
+            target_version,
+            args,
+            worker_count,
+            failed_count: 0,
+            result: WorkerRolloutResult::default(),
+        }
+    }
+
+    /// Number of workers rolled out first as canaries
+    pub fn canary_count(&self) -> usize {
+        self.args
+            .canary
+            .map_or(0, |canary| canary.get().min(self.worker_count))
+    }
+
+    pub fn concurrency(&self) -> usize {
+        self.args.worker_concurrency.get()
+    }
+
+    /// Timeout of waiting for a worker to become healthy after rolling it out. Canary workers
+    /// are always waited for, the rest of the workers only when requested.
+    pub fn health_timeout(&self, canary: bool) -> Option<Duration> {
+        (canary || self.args.wait).then(|| Duration::from_secs(self.args.wait_timeout))
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.result.aborted.is_some()
+    }
+
+    /// Records the outcome of rolling out a worker, where None means that the worker was skipped.
+    /// Returns the reason of aborting the rollout, if it was aborted by this outcome.
+    pub fn record(
+        &mut self,
+        worker_name: WorkerName,
+        canary: bool,
+        outcome: Option<Result<(), String>>,
+    ) -> Option<&str> {
+        let (outcome, error) = match outcome {
+            Some(Ok(())) => (WorkerRolloutOutcome::Succeeded, None),
+            Some(Err(error)) => (WorkerRolloutOutcome::Failed, Some(error)),
+            None => (WorkerRolloutOutcome::Skipped, None),
+        };
+
+        let mut aborted_now = false;
+        if outcome == WorkerRolloutOutcome::Failed {
+            self.failed_count += 1;
+            if !self.is_aborted() {
+                self.result.aborted = self.abort_reason(&worker_name, canary);
+                aborted_now = self.is_aborted();
+            }
+        }
+
+        self.result.attempts.push(WorkerRolloutAttempt {
+            component_name: self.component_name.clone(),
+            worker_name,
+            target_version: self.target_version,
+            canary,
+            outcome,
+            error,
+        });
+
+        if aborted_now {
+            self.result.aborted.as_deref()
+        } else {
+            None
+        }
+    }
+
+    fn abort_reason(&self, worker_name: &WorkerName, canary: bool) -> Option<String> {
+        if canary {
+            Some(format!(
+                "canary worker {}/{} is not healthy",
+                self.component_name, worker_name
+            ))
+        } else {
+            self.args.abort_threshold.and_then(|abort_threshold| {
+                exceeds_abort_threshold(self.failed_count, self.worker_count, abort_threshold)
+                    .then(|| {
+                        format!(
+                            "{} of {} workers of component {} failed, exceeding the abort threshold of {}%",
+                            self.failed_count, self.worker_count, self.component_name, abort_threshold
+                        )
+                    })
+            })
+        }
+    }
+
+    /// Returns the result with the canary attempts first, then ordered by worker name
+    pub fn finish(mut self) -> WorkerRolloutResult {
+        self.result.attempts.sort_by(|left, right| {
+            right
+                .canary
+                .cmp(&left.canary)
+                .then_with(|| left.worker_name.0.cmp(&right.worker_name.0))
+        });
+        self.result
+    }
+}
+
+fn exceeds_abort_threshold(failed_count: usize, worker_count: usize, abort_threshold: u8) -> bool {
+    failed_count * 100 > abort_threshold as usize * worker_count
+}
+
+/// Health of a worker after rolling out the target version to it
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum WorkerRolloutHealth {
+    Healthy,
+    Pending,
+    Failed(String),
+}
+
+impl WorkerRolloutHealth {
+    pub fn of(metadata: &WorkerMetadata, target_version: u64) -> Self {
+        let failed_update = metadata.updates.iter().find_map(|update| match update {
+            UpdateRecord::FailedUpdate(update) if update.target_version == target_version => {
+                Some(update.details.clone().unwrap_or_default())
+            }
+            _ => None,
+        });
+        if let Some(details) = failed_update {
+            return Self::Failed(format!(
+                "Update to version {} failed: {}",
+                target_version, details
+            ));
+        }
+        if metadata.status == WorkerStatus::Failed {
+            return Self::Failed(format!(
+                "Worker failed: {}",
+                metadata.last_error.clone().unwrap_or_default()
+            ));
+        }
+        if metadata.component_version == target_version && metadata.status != WorkerStatus::Retrying
+        {
+            return Self::Healthy;
+        }
+        Self::Pending
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ComponentDeployAction {
+    Create,
+    Update,
+    Unchanged,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentDeployPlanEntry {
+    pub component_name: ComponentName,
+    pub action: ComponentDeployAction,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub changes: Vec<String>,
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentDeployPlan {
+    pub components: Vec<ComponentDeployPlanEntry>,
+}
+
+impl ComponentDeployPlan {
+    pub fn has_changes(&self) -> bool {
+        self.components
+            .iter()
+            .any(|component| component.action != ComponentDeployAction::Unchanged)
+    }
+
+    pub fn count(&self, action: ComponentDeployAction) -> usize {
+        self.components
+            .iter()
+            .filter(|component| component.action == action)
+            .count()
+    }
+}
+
+/// Tool names used in the processed-by field of the producers section for storing the content
+/// hashes of the deployed component, as the component metadata does not contain them otherwise
+pub const DEPLOY_WASM_HASH_PRODUCER: &str = "golem-cli-deploy-wasm-hash";
+pub const DEPLOY_FILES_HASH_PRODUCER: &str = "golem-cli-deploy-files-hash";
+
+/// Local state of a component, compared with the deployed component when planning deploys
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ComponentDeployFingerprint {
+    pub component_type: AppComponentType,
+    pub wasm_hash: String,
+    pub files: BTreeMap<String, ComponentFilePermissions>,
+    pub files_hash: String,
+    pub dynamic_linking: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// State of a deployed component, based on the component metadata
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DeployedComponentFingerprint {
+    pub component_type: AppComponentType,
+    /// Missing for components which were not deployed from an application
+    pub wasm_hash: Option<String>,
+    pub files: BTreeMap<String, ComponentFilePermissions>,
+    /// Missing for components which were not deployed from an application
+    pub files_hash: Option<String>,
+    pub dynamic_linking: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl From<&Component> for DeployedComponentFingerprint {
+    fn from(component: &Component) -> Self {
+        Self {
+            component_type: component.component_type,
+            wasm_hash: component
+                .processed_by_version(DEPLOY_WASM_HASH_PRODUCER)
+                .map(|hash| hash.to_string()),
+            files: component
+                .files
+                .iter()
+                .map(|file| (file.path.to_string(), file.permissions))
+                .collect(),
+            files_hash: component
+                .processed_by_version(DEPLOY_FILES_HASH_PRODUCER)
+                .map(|hash| hash.to_string()),
+            dynamic_linking: component.dynamic_linking_targets(),
+        }
+    }
+}
+
+impl ComponentDeployFingerprint {
+    pub fn plan_entry(
+        &self,
+        component_name: ComponentName,
+        deployed: Option<&DeployedComponentFingerprint>,
+    ) -> ComponentDeployPlanEntry {
+        match deployed {
+            Some(deployed) => {
+                let changes = self.changes(deployed);
+                ComponentDeployPlanEntry {
+                    component_name,
+                    action: if changes.is_empty() {
+                        ComponentDeployAction::Unchanged
+                    } else {
+                        ComponentDeployAction::Update
+                    },
+                    changes,
+                }
+            }
+            None => ComponentDeployPlanEntry {
+                component_name,
+                action: ComponentDeployAction::Create,
+                changes: vec![],
+            },
+        }
+    }
+
+    fn changes(&self, deployed: &DeployedComponentFingerprint) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        if self.component_type != deployed.component_type {
+            changes.push(format!(
+                "component type: {} -> {}",
+                deployed.component_type, self.component_type
+            ));
+        }
+
+        match &deployed.wasm_hash {
+            Some(wasm_hash) if *wasm_hash == self.wasm_hash => {}
+            Some(_) => changes.push("component WASM".to_string()),
+            None => changes.push(
+                "component WASM: the deployed version was not deployed from an application"
+                    .to_string(),
+            ),
+        }
+
+        for (path, permissions) in &self.files {
+            match deployed.files.get(path) {
+                Some(deployed_permissions) if deployed_permissions == permissions => {}
+                Some(deployed_permissions) => changes.push(format!(
+                    "file permissions: {}: {:?} -> {:?}",
+                    path, deployed_permissions, permissions
+                )),
+                None => changes.push(format!("file added: {}", path)),
+            }
+        }
+        for path in deployed.files.keys() {
+            if !self.files.contains_key(path) {
+                changes.push(format!("file removed: {}", path));
+            }
+        }
+        if !self.files.is_empty()
+            && self.files.keys().eq(deployed.files.keys())
+            && deployed.files_hash.as_ref() != Some(&self.files_hash)
+        {
+            changes.push("file contents".to_string());
+        }
+
+        if self.dynamic_linking != deployed.dynamic_linking {
+            changes.push("dynamic linking".to_string());
+        }
+
+        changes
+    }
+
+    /// Content hashes stored in the producers section of the deployed component
+    pub fn processed_by(&self) -> Vec<(String, String)> {
+        vec![
+            (
+                DEPLOY_WASM_HASH_PRODUCER.to_string(),
+                self.wasm_hash.clone(),
+            ),
+            (
+                DEPLOY_FILES_HASH_PRODUCER.to_string(),
+                self.files_hash.clone(),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::shared_args::WorkerRolloutArgs;
+    use crate::model::component::AppComponentType;
+    use crate::model::deploy::{
+        exceeds_abort_threshold, ComponentDeployAction, ComponentDeployFingerprint,
+        DeployedComponentFingerprint, WorkerRollout, WorkerRolloutHealth, WorkerRolloutOutcome,
+    };
+    use crate::model::{ComponentName, WorkerMetadata, WorkerName};
+    use assert2::check;
+    use chrono::Utc;
+    use golem_client::model::{FailedUpdate, UpdateRecord, WorkerId, WorkerStatus};
+    use golem_common::model::ComponentFilePermissions;
+    use std::collections::{BTreeMap, HashMap};
+    use std::num::NonZeroUsize;
+    use std::time::Duration;
+    use test_r::test;
+    use uuid::Uuid;
+
+    fn fingerprint() -> ComponentDeployFingerprint {
+        ComponentDeployFingerprint {
+            component_type: AppComponentType::Durable,
+            wasm_hash: "wasm-hash".to_string(),
+            files: BTreeMap::from([
+                ("/a.txt".to_string(), ComponentFilePermissions::ReadOnly),
+                ("/b.txt".to_string(), ComponentFilePermissions::ReadWrite),
+            ]),
+            files_hash: "files-hash".to_string(),
+            dynamic_linking: BTreeMap::new(),
+        }
+    }
+
+    fn deployed(fingerprint: &ComponentDeployFingerprint) -> DeployedComponentFingerprint {
+        DeployedComponentFingerprint {
+            component_type: fingerprint.component_type,
+            wasm_hash: Some(fingerprint.wasm_hash.clone()),
+            files: fingerprint.files.clone(),
+            files_hash: Some(fingerprint.files_hash.clone()),
+            dynamic_linking: fingerprint.dynamic_linking.clone(),
+        }
+    }
+
+    #[test]
+    fn plan_entry_for_new_component() {
+        let entry = fingerprint().plan_entry("app:a".into(), None);
+        check!(entry.action == ComponentDeployAction::Create);
+        check!(entry.changes.is_empty());
+    }
+
+    #[test]
+    fn plan_entry_for_unchanged_component() {
+        let fingerprint = fingerprint();
+        let entry = fingerprint.plan_entry("app:a".into(), Some(&deployed(&fingerprint)));
+        check!(entry.action == ComponentDeployAction::Unchanged);
+        check!(entry.changes.is_empty());
+    }
+
+    #[test]
+    fn plan_entry_for_changed_component() {
+        let fingerprint = fingerprint();
+        let mut deployed = deployed(&fingerprint);
+        deployed.wasm_hash = Some("other-wasm-hash".to_string());
+
+        let entry = fingerprint.plan_entry("app:a".into(), Some(&deployed));
+        check!(entry.action == ComponentDeployAction::Update);
+        check!(entry.changes == vec!["component WASM"]);
+    }
+
+    #[test]
+    fn changes_of_properties_and_files() {
+        let fingerprint = fingerprint();
+        let mut deployed = deployed(&fingerprint);
+        deployed.component_type = AppComponentType::Ephemeral;
+        deployed.files = BTreeMap::from([
+            ("/a.txt".to_string(), ComponentFilePermissions::ReadWrite),
+            ("/c.txt".to_string(), ComponentFilePermissions::ReadOnly),
+        ]);
+        deployed.dynamic_linking = BTreeMap::from([(
+            "app:b-client/b-client".to_string(),
+            BTreeMap::from([("b-api".to_string(), "app:b-exports/b-api".to_string())]),
+        )]);
+
+        check!(
+            fingerprint.changes(&deployed)
+                == vec![
+                    "component type: Ephemeral -> Durable",
+                    "file permissions: /a.txt: ReadWrite -> ReadOnly",
+                    "file added: /b.txt",
+                    "file removed: /c.txt",
+                    "dynamic linking",
+                ]
+        );
+    }
+
+    #[test]
+    fn changes_of_file_contents() {
+        let fingerprint = fingerprint();
+        let mut deployed = deployed(&fingerprint);
+        deployed.files_hash = Some("other-files-hash".to_string());
+
+        check!(fingerprint.changes(&deployed) == vec!["file contents"]);
+    }
+
+    #[test]
+    fn changes_of_component_deployed_without_hashes() {
+        let fingerprint = fingerprint();
+        let mut deployed = deployed(&fingerprint);
+        deployed.wasm_hash = None;
+        deployed.files_hash = None;
+
+        check!(
+            fingerprint.changes(&deployed)
+                == vec![
+                    "component WASM: the deployed version was not deployed from an application",
+                    "file contents",
+                ]
+        );
+    }
+
+    fn rollout_args(canary: Option<usize>, abort_threshold: Option<u8>) -> WorkerRolloutArgs {
+        WorkerRolloutArgs {
+            canary: canary.map(|canary| NonZeroUsize::new(canary).unwrap()),
+            abort_threshold,
+            ..WorkerRolloutArgs::default()
+        }
+    }
+
+    fn failed() -> Option<Result<(), String>> {
+        Some(Err("failed".to_string()))
+    }
+
+    fn succeeded() -> Option<Result<(), String>> {
+        Some(Ok(()))
+    }
+
+    // Records the outcomes in order, returns the abort reasons returned for them
+    fn record_all(
+        rollout: &mut WorkerRollout,
+        canary: bool,
+        outcomes: Vec<(&str, Option<Result<(), String>>)>,
+    ) -> Vec<Option<String>> {
+        outcomes
+            .into_iter()
+            .map(|(worker_name, outcome)| {
+                rollout
+                    .record(WorkerName::from(worker_name), canary, outcome)
+                    .map(|reason| reason.to_string())
+            })
+            .collect()
+    }
+
+    fn outcomes(rollout: WorkerRollout) -> Vec<(String, bool, WorkerRolloutOutcome)> {
+        rollout
+            .finish()
+            .attempts
+            .into_iter()
+            .map(|attempt| (attempt.worker_name.0, attempt.canary, attempt.outcome))
+            .collect()
+    }
+
+    #[test]
+    fn abort_threshold_arithmetic() {
+        check!(!exceeds_abort_threshold(0, 10, 0));
+        check!(exceeds_abort_threshold(1, 10, 0));
+        check!(!exceeds_abort_threshold(1, 10, 10));
+        check!(exceeds_abort_threshold(2, 10, 10));
+        check!(exceeds_abort_threshold(1, 3, 33));
+        check!(!exceeds_abort_threshold(1, 3, 34));
+        check!(!exceeds_abort_threshold(10, 10, 100));
+    }
+
+    #[test]
+    fn rollout_canary_count_and_health_timeout() {
+        let component_name = ComponentName::from("component");
+
+        let args = rollout_args(Some(5), None);
+        check!(WorkerRollout::new(&component_name, 2, &args, 3).canary_count() == 3);
+        check!(WorkerRollout::new(&component_name, 2, &args, 10).canary_count() == 5);
+
+        let args = rollout_args(None, None);
+        let rollout = WorkerRollout::new(&component_name, 2, &args, 10);
+        check!(rollout.canary_count() == 0);
+        check!(rollout.concurrency() == 8);
+        check!(rollout.health_timeout(true) == Some(Duration::from_secs(60)));
+        check!(rollout.health_timeout(false).is_none());
+
+        let args = WorkerRolloutArgs {
+            wait: true,
+            wait_timeout: 10,
+            ..WorkerRolloutArgs::default()
+        };
+        let rollout = WorkerRollout::new(&component_name, 2, &args, 10);
+        check!(rollout.health_timeout(false) == Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn rollout_aborted_by_failed_canary() {
+        let component_name = ComponentName::from("component");
+        let args = rollout_args(Some(2), None);
+        let mut rollout = WorkerRollout::new(&component_name, 2, &args, 4);
+
+        let abort_reasons = record_all(
+            &mut rollout,
+            true,
+            vec![("c1", succeeded()), ("c2", failed())],
+        );
+        check!(
+            abort_reasons
+                == vec![
+                    None,
+                    Some("canary worker component/c2 is not healthy".to_string())
+                ]
+        );
+        check!(rollout.is_aborted());
+
+        let abort_reasons = record_all(&mut rollout, false, vec![("w2", None), ("w1", None)]);
+        check!(abort_reasons == vec![None, None]);
+
+        check!(
+            outcomes(rollout)
+                == vec![
+                    ("c1".to_string(), true, WorkerRolloutOutcome::Succeeded),
+                    ("c2".to_string(), true, WorkerRolloutOutcome::Failed),
+                    ("w1".to_string(), false, WorkerRolloutOutcome::Skipped),
+                    ("w2".to_string(), false, WorkerRolloutOutcome::Skipped),
+                ]
+        );
+    }
+
+    #[test]
+    fn rollout_aborted_by_exceeding_the_abort_threshold() {
+        let component_name = ComponentName::from("component");
+        let args = rollout_args(None, Some(20));
+        let mut rollout = WorkerRollout::new(&component_name, 2, &args, 10);
+        let reason =
+            "3 of 10 workers of component component failed, exceeding the abort threshold of 20%";
+
+        // Workers already running when aborting are still recorded, without aborting again
+        let abort_reasons = record_all(
+            &mut rollout,
+            false,
+            vec![
+                ("w1", failed()),
+                ("w2", succeeded()),
+                ("w3", failed()),
+                ("w4", failed()),
+                ("w5", failed()),
+                ("w6", None),
+            ],
+        );
+        check!(abort_reasons == vec![None, None, None, Some(reason.to_string()), None, None]);
+
+        let result = rollout.finish();
+        check!(result.count(WorkerRolloutOutcome::Succeeded) == 1);
+        check!(result.count(WorkerRolloutOutcome::Failed) == 4);
+        check!(result.count(WorkerRolloutOutcome::Skipped) == 1);
+        check!(result.aborted == Some(reason.to_string()));
+    }
+
+    #[test]
+    fn rollout_without_abort_threshold_is_not_aborted() {
+        let component_name = ComponentName::from("component");
+        let args = rollout_args(None, None);
+        let mut rollout = WorkerRollout::new(&component_name, 2, &args, 2);
+
+        let abort_reasons = record_all(
+            &mut rollout,
+            false,
+            vec![("w1", failed()), ("w2", failed())],
+        );
+        check!(abort_reasons == vec![None, None]);
+        check!(!rollout.is_aborted());
+        check!(rollout.finish().aborted.is_none());
+    }
+
+    fn worker_metadata(
+        status: WorkerStatus,
+        component_version: u64,
+        updates: Vec<UpdateRecord>,
+    ) -> WorkerMetadata {
+        WorkerMetadata {
+            worker_id: WorkerId {
+                component_id: Uuid::new_v4(),
+                worker_name: "worker".to_string(),
+            },
+            component_name: ComponentName::from("component"),
+            account_id: None,
+            args: vec![],
+            env: HashMap::new(),
+            status,
+            component_version,
+            retry_count: 0,
+            pending_invocation_count: 0,
+            updates,
+            created_at: Utc::now(),
+            last_error: Some("error".to_string()),
+            component_size: 0,
+            total_linear_memory_size: 0,
+            owned_resources: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn worker_rollout_health() {
+        check!(
+            WorkerRolloutHealth::of(&worker_metadata(WorkerStatus::Idle, 2, vec![]), 2)
+                == WorkerRolloutHealth::Healthy
+        );
+        check!(
+            WorkerRolloutHealth::of(&worker_metadata(WorkerStatus::Idle, 1, vec![]), 2)
+                == WorkerRolloutHealth::Pending
+        );
+        check!(
+            WorkerRolloutHealth::of(&worker_metadata(WorkerStatus::Retrying, 2, vec![]), 2)
+                == WorkerRolloutHealth::Pending
+        );
+        check!(
+            WorkerRolloutHealth::of(&worker_metadata(WorkerStatus::Failed, 2, vec![]), 2)
+                == WorkerRolloutHealth::Failed("Worker failed: error".to_string())
+        );
+
+        let failed_update = |target_version| {
+            UpdateRecord::FailedUpdate(FailedUpdate {
+                timestamp: Utc::now(),
+                target_version,
+                details: Some("incompatible".to_string()),
+            })
+        };
+        check!(
+            WorkerRolloutHealth::of(
+                &worker_metadata(WorkerStatus::Running, 1, vec![failed_update(2)]),
+                2
+            ) == WorkerRolloutHealth::Failed(
+                "Update to version 2 failed: incompatible".to_string()
+            )
+        );
+        check!(
+            WorkerRolloutHealth::of(
+                &worker_metadata(WorkerStatus::Running, 2, vec![failed_update(1)]),
+                2
+            ) == WorkerRolloutHealth::Healthy
+        );
+    }
+}
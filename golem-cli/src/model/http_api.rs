@@ -0,0 +1,206 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::app::HttpApiRouteMethod;
+use crate::model::app_raw;
+use serde_json::{json, Map, Value};
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+pub const DEFAULT_HTTP_API_DEFINITION_VERSION: &str = "0.0.1";
+
+/// Creates the API definition request (in the same format as the API definition files used by
+/// `api definition new`) for a manifest defined HTTP API definition.
+pub fn http_api_definition_request(
+    id: &str,
+    version: &str,
+    definition: &app_raw::HttpApiDefinition,
+) -> Value {
+    json!({
+        "id": id,
+        "version": version,
+        "draft": false,
+        "routes": definition.routes.iter().map(route_request).collect::<Vec<_>>(),
+    })
+}
+
+fn route_request(route: &app_raw::HttpApiDefinitionRoute) -> Value {
+    let mut binding = Map::new();
+    insert_opt(&mut binding, "bindingType", route.binding.type_.clone());
+    if let Some(component_name) = &route.binding.component_name {
+        let mut component = Map::new();
+        component.insert("name".to_string(), json!(component_name));
+        insert_opt(&mut component, "version", route.binding.component_version);
+        binding.insert("component".to_string(), Value::Object(component));
+    }
+    insert_opt(
+        &mut binding,
+        "workerName",
+        route.binding.worker_name.clone(),
+    );
+    insert_opt(
+        &mut binding,
+        "idempotencyKey",
+        route.binding.idempotency_key.clone(),
+    );
+    insert_opt(&mut binding, "response", route.binding.response.clone());
+
+    let mut route_request = Map::new();
+    route_request.insert(
+        "method".to_string(),
+        json!(HttpApiRouteMethod::from_str(&route.method)
+            .map(|method| method.as_str())
+            .unwrap_or(route.method.as_str())),
+    );
+    route_request.insert("path".to_string(), json!(route.path));
+    insert_opt(&mut route_request, "security", route.security.clone());
+    route_request.insert("binding".to_string(), Value::Object(binding));
+
+    Value::Object(route_request)
+}
+
+fn insert_opt<T: Into<Value>>(map: &mut Map<String, Value>, key: &str, value: Option<T>) {
+    if let Some(value) = value {
+        map.insert(key.to_string(), value.into());
+    }
+}
+
+/// Checks if an existing API definition (as returned by the API definition service) has the same
+/// routes as the request, fields which are not part of the request are ignored.
+pub fn http_api_definition_matches(request: &Value, existing: &Value) -> bool {
+    json_contains(&request["routes"], &existing["routes"])
+}
+
+fn json_contains(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::Null, _) => true,
+        (Value::Object(expected), Value::Object(actual)) => expected
+            .iter()
+            .all(|(key, value)| json_contains(value, actual.get(key).unwrap_or(&Value::Null))),
+        (Value::Array(expected), Value::Array(actual)) => {
+            expected.len() == actual.len()
+                && expected
+                    .iter()
+                    .zip(actual)
+                    .all(|(expected, actual)| json_contains(expected, actual))
+        }
+        (expected, actual) => expected == actual,
+    }
+}
+
+/// Compares versions by their numeric segments, falling back to string comparison for
+/// non-numeric ones
+pub fn compare_versions(left: &str, right: &str) -> Ordering {
+    let mut left_segments = left.split(['.', '-']);
+    let mut right_segments = right.split(['.', '-']);
+    loop {
+        match (left_segments.next(), right_segments.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(left), Some(right)) => {
+                let ordering = match (left.parse::<u64>(), right.parse::<u64>()) {
+                    (Ok(left), Ok(right)) => left.cmp(&right),
+                    _ => left.cmp(right),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+/// Increments the last numeric segment of the version, or appends a new one if there is none
+pub fn bump_version(version: &str) -> String {
+    let mut segments = version
+        .split('.')
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    match segments
+        .iter_mut()
+        .rev()
+        .find_map(|segment| segment.parse::<u64>().ok().map(|number| (segment, number)))
+    {
+        Some((segment, number)) => {
+            *segment = (number + 1).to_string();
+            segments.join(".")
+        }
+        None => format!("{}.1", version),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::app_raw;
+    use crate::model::http_api::{
+        bump_version, compare_versions, http_api_definition_matches, http_api_definition_request,
+    };
+    use assert2::check;
+    use indoc::indoc;
+    use serde_json::json;
+    use std::cmp::Ordering;
+    use test_r::test;
+
+    #[test]
+    fn definition_request_and_matching() {
+        let definition: app_raw::HttpApiDefinition = serde_yaml::from_str(indoc! {"
+            routes:
+              - method: GET
+                path: /{user}/contents
+                binding:
+                  type: default
+                  componentName: app:cart
+                  response: '${ {status: 200u64} }'
+        "})
+        .unwrap();
+
+        let request = http_api_definition_request("cart-api", "0.0.1", &definition);
+        check!(
+            request
+                == json!({
+                    "id": "cart-api",
+                    "version": "0.0.1",
+                    "draft": false,
+                    "routes": [{
+                        "method": "Get",
+                        "path": "/{user}/contents",
+                        "binding": {
+                            "bindingType": "default",
+                            "component": { "name": "app:cart" },
+                            "response": "${ {status: 200u64} }",
+                        },
+                    }],
+                })
+        );
+
+        let mut existing = request.clone();
+        existing["createdAt"] = json!("2025-01-01T00:00:00Z");
+        existing["routes"][0]["binding"]["component"]["version"] = json!(3);
+        check!(http_api_definition_matches(&request, &existing));
+
+        existing["routes"][0]["path"] = json!("/{user}/items");
+        check!(!http_api_definition_matches(&request, &existing));
+    }
+
+    #[test]
+    fn version_bumping() {
+        check!(bump_version("0.0.1") == "0.0.2");
+        check!(bump_version("1.9") == "1.10");
+        check!(bump_version("v1") == "v1.1");
+        check!(compare_versions("0.0.10", "0.0.9") == Ordering::Greater);
+        check!(compare_versions("0.1.0", "0.1") == Ordering::Greater);
+        check!(compare_versions("1.0.0", "1.0.0") == Ordering::Equal);
+    }
+}
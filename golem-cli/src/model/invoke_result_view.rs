@@ -14,11 +14,11 @@
 
 use crate::model::component::{function_result_types, Component};
 use crate::model::text::fmt::log_error;
-use crate::model::wave::type_wave_compatible;
+use crate::model::wave::{render_value_with_handles, type_wave_compatible};
 use crate::model::IdempotencyKey;
 use anyhow::{anyhow, bail};
 use golem_client::model::{InvokeResult, TypeAnnotatedValue};
-use golem_wasm_rpc::{print_type_annotated_value, protobuf};
+use golem_wasm_rpc::{print_type_annotated_value, protobuf, Value};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -84,13 +84,19 @@ impl InvokeResultView {
             bail!("Unexpected number of results.".to_string());
         }
 
-        if !result_types.iter().all(|typ| type_wave_compatible(typ)) {
-            bail!("Result type is not supported by wave".to_string(),);
-        }
-
         let wave = results
             .into_iter()
-            .map(Self::try_wave_format)
+            .zip(result_types)
+            .map(|(result, typ)| {
+                if type_wave_compatible(typ) {
+                    Self::try_wave_format(result)
+                } else {
+                    // Results with resource handles
+                    let value = Value::try_from(result)
+                        .map_err(|err| anyhow!("Failed to convert result value: {err}"))?;
+                    render_value_with_handles(&value, typ)
+                }
+            })
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(wave)
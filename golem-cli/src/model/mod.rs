@@ -16,9 +16,11 @@ pub mod app;
 pub mod app_raw;
 pub mod component;
 pub mod deploy;
+pub mod http_api;
 pub mod invoke_result_view;
 pub mod plugin_manifest;
 pub mod project;
+pub mod source_map;
 pub mod template;
 pub mod text;
 pub mod to_cloud;
@@ -583,6 +585,23 @@ impl TrimDateTime for WorkersMetadataResponseView {
     }
 }
 
+/// Per-worker results of an operation executed on multiple workers selected by filters
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerBulkOperationResult {
+    pub succeeded: Vec<WorkerOperationAttempt>,
+    pub failed: Vec<WorkerOperationAttempt>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerOperationAttempt {
+    pub component_name: ComponentName,
+    pub worker_name: WorkerName,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ApiDeployment {
     #[serde(rename = "apiDefinitions")]
@@ -0,0 +1,282 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::LogColorize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::Marker;
+
+/// Part of a source file, with 1-based line and column numbers
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceSpan {
+    pub source: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+    pub line_content: String,
+}
+
+impl SourceSpan {
+    pub fn new(source: &Path, content: &str, line: usize, column: usize, length: usize) -> Self {
+        Self {
+            source: source.to_path_buf(),
+            line,
+            column,
+            length,
+            line_content: content
+                .lines()
+                .nth(line.saturating_sub(1))
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
+
+    /// Renders the span as an annotated source snippet, similar to rustc diagnostics
+    pub fn render(&self) -> String {
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let marker = format!(
+            "{}{}",
+            " ".repeat(self.column.saturating_sub(1)),
+            "^".repeat(self.length.max(1))
+        );
+
+        format!(
+            "{gutter}--> {}:{}:{}\n{gutter} |\n{} | {}\n{gutter} | {}",
+            self.source.display(),
+            self.line,
+            self.column,
+            line_number.log_color_highlight(),
+            self.line_content,
+            marker.log_color_error_highlight(),
+        )
+    }
+}
+
+/// Locations of the nodes of a YAML document, addressed by their path of mapping keys and
+/// sequence indexes (e.g. `["components", "app:cart", "profiles", "debug"]`).
+///
+/// serde_yaml does not keep spans, so the document is parsed again for collecting them.
+#[derive(Clone, Debug, Default)]
+pub struct YamlSourceMap {
+    source: PathBuf,
+    content: String,
+    keys: HashMap<Vec<String>, Location>,
+    values: HashMap<Vec<String>, Location>,
+}
+
+impl YamlSourceMap {
+    pub fn new(source: PathBuf, yaml: &str) -> Self {
+        let mut collector = LocationCollector::default();
+        // Syntax errors are reported by serde_yaml, so the source map is best-effort
+        let _ = Parser::new(yaml.chars()).load(&mut collector, false);
+
+        Self {
+            source,
+            content: yaml.to_string(),
+            keys: collector.keys,
+            values: collector.values,
+        }
+    }
+
+    pub fn source(&self) -> &Path {
+        &self.source
+    }
+
+    /// Span of the mapping key at the given path, for sequence elements the span of the element
+    pub fn key_span(&self, path: &[&str]) -> Option<SourceSpan> {
+        let path = Self::path(path);
+        self.keys
+            .get(&path)
+            .or_else(|| self.values.get(&path))
+            .map(|location| self.span(location))
+    }
+
+    /// Span of the value at the given path
+    pub fn value_span(&self, path: &[&str]) -> Option<SourceSpan> {
+        self.values
+            .get(&Self::path(path))
+            .map(|location| self.span(location))
+    }
+
+    fn span(&self, location: &Location) -> SourceSpan {
+        SourceSpan::new(
+            &self.source,
+            &self.content,
+            location.line,
+            location.column,
+            location.length,
+        )
+    }
+
+    fn path(path: &[&str]) -> Vec<String> {
+        path.iter().map(|segment| segment.to_string()).collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Location {
+    line: usize,
+    column: usize,
+    length: usize,
+}
+
+impl Location {
+    fn new(marker: Marker, length: usize) -> Self {
+        Self {
+            line: marker.line(),
+            column: marker.col() + 1,
+            length,
+        }
+    }
+}
+
+enum Frame {
+    Mapping { key: Option<String> },
+    Sequence { index: usize },
+}
+
+#[derive(Default)]
+struct LocationCollector {
+    stack: Vec<Frame>,
+    keys: HashMap<Vec<String>, Location>,
+    values: HashMap<Vec<String>, Location>,
+}
+
+impl LocationCollector {
+    fn path(&self) -> Vec<String> {
+        self.stack
+            .iter()
+            .filter_map(|frame| match frame {
+                Frame::Mapping { key } => key.clone(),
+                Frame::Sequence { index } => Some(index.to_string()),
+            })
+            .collect()
+    }
+
+    fn expects_key(&self) -> bool {
+        matches!(self.stack.last(), Some(Frame::Mapping { key: None }))
+    }
+
+    fn add_value(&mut self, marker: Marker, length: usize) {
+        self.values
+            .entry(self.path())
+            .or_insert(Location::new(marker, length));
+    }
+
+    fn value_done(&mut self) {
+        match self.stack.last_mut() {
+            Some(Frame::Mapping { key }) => *key = None,
+            Some(Frame::Sequence { index }) => *index += 1,
+            None => {}
+        }
+    }
+}
+
+impl MarkedEventReceiver for LocationCollector {
+    fn on_event(&mut self, event: Event, marker: Marker) {
+        match event {
+            Event::Scalar(value, ..) => {
+                let length = value.chars().count();
+                if self.expects_key() {
+                    if let Some(Frame::Mapping { key }) = self.stack.last_mut() {
+                        *key = Some(value);
+                    }
+                    self.keys.insert(self.path(), Location::new(marker, length));
+                } else {
+                    self.add_value(marker, length);
+                    self.value_done();
+                }
+            }
+            Event::Alias(..) => {
+                self.add_value(marker, 1);
+                self.value_done();
+            }
+            Event::MappingStart(..) => {
+                self.add_value(marker, 1);
+                self.stack.push(Frame::Mapping { key: None });
+            }
+            Event::SequenceStart(..) => {
+                self.add_value(marker, 1);
+                self.stack.push(Frame::Sequence { index: 0 });
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.stack.pop();
+                self.value_done();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::source_map::YamlSourceMap;
+    use assert2::check;
+    use indoc::indoc;
+    use std::path::PathBuf;
+    use test_r::test;
+
+    #[test]
+    fn key_and_value_spans() {
+        colored::control::set_override(false);
+
+        let source_map = YamlSourceMap::new(
+            PathBuf::from("golem.yaml"),
+            indoc! {"
+                components:
+                  app:cart:
+                    template: rust
+                dependencies:
+                  app:cart:
+                    - type: wasm-rpc
+                      target: app:inventory
+                    - type: wasm-rcp
+                      target: app:payment
+            "},
+        );
+
+        let span = source_map
+            .key_span(&["components", "app:cart", "template"])
+            .unwrap();
+        check!(span.line == 3);
+        check!(span.column == 5);
+        check!(span.length == 8);
+
+        let span = source_map
+            .value_span(&["dependencies", "app:cart", "1", "type"])
+            .unwrap();
+        check!(span.line == 8);
+        check!(span.column == 13);
+        check!(
+            span.render()
+                == indoc! {"
+                     --> golem.yaml:8:13
+                      |
+                    8 |     - type: wasm-rcp
+                      |             ^^^^^^^^"}
+        );
+
+        let span = source_map
+            .key_span(&["dependencies", "app:cart", "1"])
+            .unwrap();
+        check!(span.line == 8);
+        check!(span.column == 7);
+
+        check!(source_map
+            .key_span(&["dependencies", "app:cart", "2"])
+            .is_none());
+    }
+}
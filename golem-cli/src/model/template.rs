@@ -1,6 +1,6 @@
 use crate::model::app_raw;
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 pub trait Template<C: Serialize> {
     type Rendered;
@@ -67,6 +67,22 @@ impl<C: Serialize, T: Template<C>> Template<C> for HashMap<String, T> {
     }
 }
 
+impl<C: Serialize, T: Template<C>> Template<C> for BTreeMap<String, T> {
+    type Rendered = BTreeMap<String, T::Rendered>;
+
+    fn render(
+        &self,
+        env: &minijinja::Environment,
+        ctx: &C,
+    ) -> Result<Self::Rendered, minijinja::Error> {
+        let mut rendered = BTreeMap::<String, T::Rendered>::new();
+        for (key, template) in self {
+            rendered.insert(key.clone(), template.render(env, ctx)?);
+        }
+        Ok(rendered)
+    }
+}
+
 impl<C: Serialize> Template<C> for app_raw::ExternalCommand {
     type Rendered = app_raw::ExternalCommand;
 
@@ -82,6 +98,28 @@ impl<C: Serialize> Template<C> for app_raw::ExternalCommand {
             mkdirs: self.mkdirs.render(env, ctx)?,
             sources: self.sources.render(env, ctx)?,
             targets: self.targets.render(env, ctx)?,
+            env: self.env.render(env, ctx)?,
+            env_file: self.env_file.render(env, ctx)?,
+            timeout: self.timeout.clone(),
+            retries: self.retries,
+            shell: self.shell,
+            when: self.when.clone(),
+        })
+    }
+}
+
+impl<C: Serialize> Template<C> for app_raw::Worker {
+    type Rendered = app_raw::Worker;
+
+    fn render(
+        &self,
+        env: &minijinja::Environment,
+        ctx: &C,
+    ) -> Result<Self::Rendered, minijinja::Error> {
+        Ok(app_raw::Worker {
+            name: self.name.render(env, ctx)?,
+            args: self.args.render(env, ctx)?,
+            env: self.env.render(env, ctx)?,
         })
     }
 }
@@ -153,6 +191,10 @@ impl<C: Serialize> Template<C> for app_raw::ComponentProperties {
             clean: self.clean.render(env, ctx)?,
             component_type: self.component_type,
             files: self.files.clone(),
+            post_link: self.post_link.clone(),
+            plugins: self.plugins.clone(),
+            uninstall_extra_plugins: self.uninstall_extra_plugins,
+            workers: self.workers.render(env, ctx)?,
         })
     }
 }
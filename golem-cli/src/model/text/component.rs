@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::log::{logln, LogColorize};
 use crate::model::component::ComponentView;
+use crate::model::deploy::{ComponentDeployAction, ComponentDeployPlan, ComponentDeployPlanEntry};
 use crate::model::text::fmt::*;
 use crate::model::ComponentName;
 use cli_table::{format::Justify, Table};
@@ -49,6 +51,42 @@ impl TextView for Vec<ComponentView> {
     }
 }
 
+#[derive(Table)]
+struct ComponentDeployPlanTableView {
+    #[table(title = "Action")]
+    pub action: String,
+    #[table(title = "Name")]
+    pub component_name: ComponentName,
+    #[table(title = "Changes")]
+    pub changes: String,
+}
+
+impl From<&ComponentDeployPlanEntry> for ComponentDeployPlanTableView {
+    fn from(value: &ComponentDeployPlanEntry) -> Self {
+        Self {
+            action: match value.action {
+                ComponentDeployAction::Create => "+ create".log_color_ok_highlight().to_string(),
+                ComponentDeployAction::Update => "~ update".log_color_warn().to_string(),
+                ComponentDeployAction::Unchanged => "  unchanged".to_string(),
+            },
+            component_name: value.component_name.clone(),
+            changes: value.changes.join("\n"),
+        }
+    }
+}
+
+impl TextView for ComponentDeployPlan {
+    fn log(&self) {
+        log_table::<_, ComponentDeployPlanTableView>(self.components.as_slice());
+        logln(format!(
+            "Plan: {} to create, {} to update, {} unchanged.",
+            format_message_highlight(&self.count(ComponentDeployAction::Create)),
+            format_message_highlight(&self.count(ComponentDeployAction::Update)),
+            format_message_highlight(&self.count(ComponentDeployAction::Unchanged)),
+        ));
+    }
+}
+
 fn component_view_fields(view: &ComponentView) -> Vec<(String, String)> {
     let mut fields = FieldsBuilder::new();
 
@@ -13,12 +13,12 @@
 // limitations under the License.
 
 use crate::log::{logln, LogColorize};
-use crate::model::deploy::TryUpdateAllWorkersResult;
+use crate::model::deploy::{WorkerRolloutAttempt, WorkerRolloutOutcome, WorkerRolloutResult};
 use crate::model::invoke_result_view::InvokeResultView;
 use crate::model::text::fmt::*;
 use crate::model::{
-    ComponentName, IdempotencyKey, WorkerMetadata, WorkerMetadataView, WorkerName,
-    WorkersMetadataResponseView,
+    ComponentName, IdempotencyKey, WorkerBulkOperationResult, WorkerMetadata, WorkerMetadataView,
+    WorkerName, WorkerOperationAttempt, WorkersMetadataResponseView,
 };
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
@@ -248,9 +248,115 @@ impl TextView for IdempotencyKey {
     }
 }
 
-impl TextView for TryUpdateAllWorkersResult {
+#[derive(Table)]
+struct WorkerRolloutAttemptTableView {
+    #[table(title = "Component name")]
+    pub component_name: ComponentName,
+    #[table(title = "Worker name")]
+    pub worker_name: WorkerName,
+    #[table(title = "Target\nversion", justify = "Justify::Right")]
+    pub target_version: u64,
+    #[table(title = "Canary")]
+    pub canary: String,
+    #[table(title = "Result")]
+    pub result: String,
+}
+
+impl From<&WorkerRolloutAttempt> for WorkerRolloutAttemptTableView {
+    fn from(value: &WorkerRolloutAttempt) -> Self {
+        Self {
+            component_name: value.component_name.clone(),
+            worker_name: value.worker_name.clone(),
+            target_version: value.target_version,
+            canary: if value.canary { "yes" } else { "" }.to_string(),
+            result: match value.outcome {
+                WorkerRolloutOutcome::Succeeded => "Succeeded".green().to_string(),
+                WorkerRolloutOutcome::Failed => {
+                    format!("Failed: {}", value.error.as_deref().unwrap_or_default())
+                        .red()
+                        .to_string()
+                }
+                WorkerRolloutOutcome::Skipped => "Skipped".yellow().to_string(),
+            },
+        }
+    }
+}
+
+impl TextView for WorkerRolloutResult {
+    fn log(&self) {
+        if self.attempts.is_empty() {
+            return;
+        }
+
+        let unsuccessful_attempts = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.outcome != WorkerRolloutOutcome::Succeeded)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        logln("");
+        if !unsuccessful_attempts.is_empty() {
+            log_table::<_, WorkerRolloutAttemptTableView>(&unsuccessful_attempts);
+            logln("");
+        }
+        logln(format!(
+            "Workers succeeded: {}, failed: {}, skipped: {}",
+            self.count(WorkerRolloutOutcome::Succeeded)
+                .to_string()
+                .log_color_highlight(),
+            self.count(WorkerRolloutOutcome::Failed)
+                .to_string()
+                .log_color_highlight(),
+            self.count(WorkerRolloutOutcome::Skipped)
+                .to_string()
+                .log_color_highlight()
+        ));
+        if let Some(reason) = &self.aborted {
+            logln(format_warn(&format!("Rollout aborted: {}", reason)));
+        }
+    }
+}
+
+#[derive(Table)]
+struct WorkerOperationAttemptTableView {
+    #[table(title = "Component name")]
+    pub component_name: ComponentName,
+    #[table(title = "Worker name")]
+    pub worker_name: WorkerName,
+    #[table(title = "Result")]
+    pub result: String,
+}
+
+impl From<&WorkerOperationAttempt> for WorkerOperationAttemptTableView {
+    fn from(value: &WorkerOperationAttempt) -> Self {
+        Self {
+            component_name: value.component_name.clone(),
+            worker_name: value.worker_name.clone(),
+            result: match &value.error {
+                Some(error) => format!("Failed: {}", error).red().to_string(),
+                None => "Succeeded".green().to_string(),
+            },
+        }
+    }
+}
+
+impl TextView for WorkerBulkOperationResult {
     fn log(&self) {
-        // NOP
+        log_table::<_, WorkerOperationAttemptTableView>(
+            &self
+                .succeeded
+                .iter()
+                .chain(self.failed.iter())
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
+        logln("");
+        logln(format!(
+            "Succeeded: {}, failed: {}",
+            self.succeeded.len().to_string().log_color_highlight(),
+            self.failed.len().to_string().log_color_highlight()
+        ));
     }
 }
 
@@ -280,7 +386,6 @@ impl TextView for InvokeResultView {
             logln(format_warn(indoc!(
                 "
                     Failed to convert invocation result to WAVE format.
-                    At the moment WAVE does not support Handle (aka Resource) data type.
 
                     "
             )));
@@ -12,9 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::model::component::render_type;
+use anyhow::{anyhow, bail};
 use golem_wasm_ast::analysis::{
     AnalysedFunction, AnalysedType, TypeRecord, TypeResult, TypeTuple, TypeVariant,
 };
+use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+use golem_wasm_rpc::{print_type_annotated_value, Value, ValueAndType};
 
 pub fn type_wave_compatible(typ: &AnalysedType) -> bool {
     fn variant_wave_compatible(tv: &TypeVariant) -> bool {
@@ -66,3 +70,131 @@ pub fn function_wave_compatible(func: &AnalysedFunction) -> bool {
     func.parameters.iter().all(|p| type_wave_compatible(&p.typ))
         && func.results.iter().all(|r| type_wave_compatible(&r.typ))
 }
+
+/// Renders the value in WAVE format, except for resource handles, which are not supported by
+/// WAVE, so they are rendered as opaque `<worker-uri>/<resource-id>` URIs. These URIs can be
+/// used as arguments for later invocations on the same worker.
+pub fn render_value_with_handles(value: &Value, typ: &AnalysedType) -> anyhow::Result<String> {
+    fn render_all<'a>(
+        values: impl Iterator<Item = (&'a Value, &'a AnalysedType)>,
+    ) -> anyhow::Result<String> {
+        Ok(values
+            .map(|(value, typ)| render_value_with_handles(value, typ))
+            .collect::<anyhow::Result<Vec<_>>>()?
+            .join(", "))
+    }
+
+    fn render_case(
+        name: &str,
+        value: Option<&Value>,
+        typ: Option<&AnalysedType>,
+    ) -> anyhow::Result<String> {
+        match (value, typ) {
+            (Some(value), Some(typ)) => Ok(format!(
+                "{}({})",
+                name,
+                render_value_with_handles(value, typ)?
+            )),
+            _ => Ok(name.to_string()),
+        }
+    }
+
+    if type_wave_compatible(typ) {
+        let value_and_type = ValueAndType::new(value.clone(), typ.clone());
+        let tav: TypeAnnotatedValue = (&value_and_type)
+            .try_into()
+            .map_err(|err| anyhow!("Failed to convert value: {}", err))?;
+        return print_type_annotated_value(&tav)
+            .map_err(|err| anyhow!("Failed to format value as WAVE: {}", err));
+    }
+
+    match (value, typ) {
+        (Value::Handle { uri, resource_id }, AnalysedType::Handle(_)) => {
+            Ok(format!("{}/{}", uri.value, resource_id))
+        }
+        (Value::Record(values), AnalysedType::Record(TypeRecord { fields })) => Ok(format!(
+            "{{{}}}",
+            values
+                .iter()
+                .zip(fields)
+                .map(|(value, field)| Ok(format!(
+                    "{}: {}",
+                    field.name,
+                    render_value_with_handles(value, &field.typ)?
+                )))
+                .collect::<anyhow::Result<Vec<_>>>()?
+                .join(", ")
+        )),
+        (Value::Tuple(values), AnalysedType::Tuple(TypeTuple { items })) => {
+            Ok(format!("({})", render_all(values.iter().zip(items))?))
+        }
+        (Value::List(values), AnalysedType::List(list)) => Ok(format!(
+            "[{}]",
+            render_all(values.iter().map(|value| (value, list.inner.as_ref())))?
+        )),
+        (Value::Option(value), AnalysedType::Option(option)) => match value {
+            Some(value) => render_case("some", Some(value.as_ref()), Some(option.inner.as_ref())),
+            None => Ok("none".to_string()),
+        },
+        (Value::Result(value), AnalysedType::Result(result)) => match value {
+            Ok(value) => render_case("ok", value.as_deref(), result.ok.as_deref()),
+            Err(value) => render_case("err", value.as_deref(), result.err.as_deref()),
+        },
+        (
+            Value::Variant {
+                case_idx,
+                case_value,
+            },
+            AnalysedType::Variant(TypeVariant { cases }),
+        ) => {
+            let case = cases
+                .get(*case_idx as usize)
+                .ok_or_else(|| anyhow!("Invalid variant case index: {}", case_idx))?;
+            render_case(&case.name, case_value.as_deref(), case.typ.as_ref())
+        }
+        _ => bail!("Value does not match its type: {}", render_type(typ)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::wave::render_value_with_handles;
+    use assert2::check;
+    use golem_wasm_ast::analysis::analysed_type::{field, handle, list, record, str};
+    use golem_wasm_ast::analysis::{AnalysedResourceId, AnalysedResourceMode};
+    use golem_wasm_rpc::{Uri, Value};
+    use test_r::test;
+
+    #[test]
+    fn render_handles_as_uris() {
+        let typ = record(vec![
+            field("name", str()),
+            field(
+                "counters",
+                list(handle(AnalysedResourceId(0), AnalysedResourceMode::Owned)),
+            ),
+        ]);
+        let value = Value::Record(vec![
+            Value::String("counters".to_string()),
+            Value::List(vec![
+                Value::Handle {
+                    uri: Uri {
+                        value: "urn:worker:c/w".to_string(),
+                    },
+                    resource_id: 1,
+                },
+                Value::Handle {
+                    uri: Uri {
+                        value: "urn:worker:c/w".to_string(),
+                    },
+                    resource_id: 2,
+                },
+            ]),
+        ]);
+
+        check!(
+            render_value_with_handles(&value, &typ).unwrap()
+                == r#"{name: "counters", counters: [urn:worker:c/w/1, urn:worker:c/w/2]}"#
+        );
+    }
+}
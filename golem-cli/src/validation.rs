@@ -13,8 +13,11 @@
 // limitations under the License.
 
 use crate::log::LogColorize;
+use crate::model::source_map::{SourceSpan, YamlSourceMap};
 use itertools::Itertools;
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::path::{Path, PathBuf};
 
 pub struct ValidationContext {
     pub name: &'static str,
@@ -195,6 +198,7 @@ pub struct ValidationBuilder {
     warns: Vec<String>,
     errors: Vec<String>,
     has_any_error_stack: Vec<bool>,
+    source_maps: HashMap<PathBuf, YamlSourceMap>,
 }
 
 impl ValidationBuilder {
@@ -204,9 +208,30 @@ impl ValidationBuilder {
             warns: vec![],
             errors: vec![],
             has_any_error_stack: vec![],
+            source_maps: HashMap::new(),
         }
     }
 
+    /// Registers the source map of a source, so errors can point to the problematic lines
+    pub fn add_source_map(&mut self, source_map: YamlSourceMap) {
+        self.source_maps
+            .insert(source_map.source().to_path_buf(), source_map);
+    }
+
+    /// Span of the mapping key at the given path in the source, see [YamlSourceMap::key_span]
+    pub fn key_span(&self, source: &Path, path: &[&str]) -> Option<SourceSpan> {
+        self.source_maps
+            .get(source)
+            .and_then(|source_map| source_map.key_span(path))
+    }
+
+    /// Span of the value at the given path in the source, see [YamlSourceMap::value_span]
+    pub fn value_span(&self, source: &Path, path: &[&str]) -> Option<SourceSpan> {
+        self.source_maps
+            .get(source)
+            .and_then(|source_map| source_map.value_span(path))
+    }
+
     pub fn push_context(&mut self, name: &'static str, value: String) {
         self.context.push(ValidationContext { name, value })
     }
@@ -256,7 +281,7 @@ impl ValidationBuilder {
         (result, !has_any_errors)
     }
 
-    fn format(&mut self, message: String) -> String {
+    fn format(&mut self, message: String, span: Option<SourceSpan>) -> String {
         let multiline = message.contains("\n");
 
         let message = {
@@ -294,20 +319,30 @@ impl ValidationBuilder {
             }
         };
 
+        let span = span
+            .map(|span| format!("\n{}", span.render()))
+            .unwrap_or_default();
+
         format!(
-            "{}{}{}",
+            "{}{}{}{}",
             if multiline && !message.starts_with("\n") {
                 "\n"
             } else {
                 ""
             },
             message,
-            context
+            context,
+            span
         )
     }
 
     pub fn add_error(&mut self, error: String) {
-        let error = self.format(error);
+        self.add_error_at(None, error);
+    }
+
+    /// Adds an error, rendered together with the annotated source snippet of the span
+    pub fn add_error_at(&mut self, span: Option<SourceSpan>, error: String) {
+        let error = self.format(error, span);
         if let Some(has_any_errors) = self.has_any_error_stack.last_mut() {
             *has_any_errors = true;
         }
@@ -315,7 +350,12 @@ impl ValidationBuilder {
     }
 
     pub fn add_warn(&mut self, warn: String) {
-        let warn = self.format(warn);
+        self.add_warn_at(None, warn);
+    }
+
+    /// Adds a warning, rendered together with the annotated source snippet of the span
+    pub fn add_warn_at(&mut self, span: Option<SourceSpan>, warn: String) {
+        let warn = self.format(warn, span);
         self.warns.push(warn);
     }
 
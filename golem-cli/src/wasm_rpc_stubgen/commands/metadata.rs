@@ -51,3 +51,30 @@ pub fn add_metadata(
         .with_context(|| format!("Writing final linked WASM to {:?}", target.as_ref()))?;
     Ok(())
 }
+
+/// Adds the given tools and versions to the processed-by field of the producers section of the
+/// WASM read from `source`, saving the result to `target`
+pub fn add_processed_by(
+    source: &impl AsRef<Path>,
+    processed_by: Vec<(String, String)>,
+    target: &impl AsRef<Path>,
+) -> anyhow::Result<()> {
+    let wasm =
+        fs::read(source).with_context(|| format!("Reading WASM from {:?}", source.as_ref()))?;
+
+    let metadata = AddMetadata {
+        processed_by,
+        ..Default::default()
+    };
+
+    let updated_wasm = metadata
+        .to_wasm(&wasm)
+        .context("Adding processed-by metadata to the WASM")?;
+
+    create_dir_all(PathExtra::new(target).parent()?)
+        .with_context(|| format!("Failed to create target dir for {:?}", target.as_ref()))?;
+
+    fs::write(target, &updated_wasm)
+        .with_context(|| format!("Writing WASM to {:?}", target.as_ref()))?;
+    Ok(())
+}
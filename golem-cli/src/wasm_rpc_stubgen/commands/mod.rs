@@ -23,3 +23,6 @@ pub mod composition;
 
 /// Top level commands for adding metadata to WASM components
 pub mod metadata;
+
+/// Top level commands for stripping and validating WASM components
+pub mod strip;
@@ -0,0 +1,223 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Context;
+use wasm_encoder::{ComponentSectionId, Encode, Section};
+use wasmparser::{Encoding, Parser, Payload, Validator, WasmFeatures};
+
+/// Returns true for custom sections only containing debug information
+pub fn is_debug_custom_section(name: &str) -> bool {
+    name.starts_with(".debug_")
+        || name == "name"
+        || name == "sourceMappingURL"
+        || name == "external_debug_info"
+}
+
+/// Removes the custom sections selected by `strip_custom_section` from the WASM module or
+/// component, including the ones in nested modules and components.
+pub fn strip_custom_sections<F>(wasm: &[u8], strip_custom_section: F) -> anyhow::Result<Vec<u8>>
+where
+    F: Fn(&str) -> bool,
+{
+    let mut output = Vec::new();
+    let mut stack = Vec::new();
+
+    for payload in Parser::new(0).parse_all(wasm) {
+        let payload = payload.context("Failed to parse WASM")?;
+        match &payload {
+            Payload::Version { encoding, .. } => {
+                output.extend_from_slice(match encoding {
+                    Encoding::Component => &wasm_encoder::Component::HEADER,
+                    Encoding::Module => &wasm_encoder::Module::HEADER,
+                });
+            }
+            Payload::ModuleSection { .. } | Payload::ComponentSection { .. } => {
+                // Nested modules and components are parsed inline, their stripped version
+                // is built separately, then appended to the parent on their end
+                stack.push(std::mem::take(&mut output));
+                continue;
+            }
+            Payload::End { .. } => {
+                let Some(mut parent) = stack.pop() else {
+                    break;
+                };
+                if output.starts_with(&wasm_encoder::Component::HEADER) {
+                    parent.push(ComponentSectionId::Component as u8);
+                } else {
+                    parent.push(ComponentSectionId::CoreModule as u8);
+                }
+                output.encode(&mut parent);
+                output = parent;
+            }
+            Payload::CustomSection(reader) if strip_custom_section(reader.name()) => {
+                continue;
+            }
+            _ => {}
+        }
+
+        if let Some((id, range)) = payload.as_section() {
+            wasm_encoder::RawSection {
+                id,
+                data: &wasm[range],
+            }
+            .append_to(&mut output);
+        }
+    }
+
+    Ok(output)
+}
+
+pub fn validate(wasm: &[u8]) -> anyhow::Result<()> {
+    Validator::new_with_features(WasmFeatures::all())
+        .validate_all(wasm)
+        .context("Failed to validate WASM")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::wasm_rpc_stubgen::commands::strip::{
+        is_debug_custom_section, strip_custom_sections, validate,
+    };
+    use assert2::check;
+    use std::borrow::Cow;
+    use test_r::test;
+    use wasm_encoder::{
+        CodeSection, Component, CustomSection, ExportKind, ExportSection, Function,
+        FunctionSection, InstanceSection, Instruction, Module, ModuleArg, ModuleSection,
+        NestedComponentSection, TypeSection, ValType,
+    };
+    use wasmparser::{Parser, Payload};
+
+    fn custom_section_names(wasm: &[u8]) -> Vec<String> {
+        Parser::new(0)
+            .parse_all(wasm)
+            .filter_map(|payload| match payload.unwrap() {
+                Payload::CustomSection(reader) => Some(reader.name().to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Raw bytes of all the non-custom sections of the nested modules and components, in order.
+    // The nested module and component sections themselves are skipped, as they also contain
+    // the custom sections.
+    fn non_custom_sections(wasm: &[u8]) -> Vec<Vec<u8>> {
+        Parser::new(0)
+            .parse_all(wasm)
+            .filter_map(|payload| match payload.unwrap() {
+                Payload::CustomSection(_)
+                | Payload::ModuleSection { .. }
+                | Payload::ComponentSection { .. } => None,
+                payload => payload
+                    .as_section()
+                    .map(|(id, range)| [&[id], &wasm[range]].concat()),
+            })
+            .collect()
+    }
+
+    fn debug_section(name: &'static str) -> CustomSection<'static> {
+        CustomSection {
+            name: Cow::Borrowed(name),
+            data: Cow::Borrowed(b"debug"),
+        }
+    }
+
+    #[test]
+    fn strip_debug_sections_from_nested_module() {
+        let mut module = Module::new();
+        module.section(&CustomSection {
+            name: Cow::Borrowed(".debug_info"),
+            data: Cow::Borrowed(b"debug"),
+        });
+        module.section(&CustomSection {
+            name: Cow::Borrowed("producers"),
+            data: Cow::Borrowed(b"producers"),
+        });
+
+        let mut component = Component::new();
+        component.section(&ModuleSection(&module));
+        component.section(&CustomSection {
+            name: Cow::Borrowed("name"),
+            data: Cow::Borrowed(b""),
+        });
+        let wasm = component.finish();
+
+        check!(custom_section_names(&wasm) == vec![".debug_info", "producers", "name"]);
+
+        let stripped = strip_custom_sections(&wasm, is_debug_custom_section).unwrap();
+        check!(custom_section_names(&stripped) == vec!["producers"]);
+        check!(stripped.len() < wasm.len());
+        validate(&stripped).unwrap();
+
+        let stripped_all = strip_custom_sections(&wasm, |_| true).unwrap();
+        check!(custom_section_names(&stripped_all).is_empty());
+        validate(&stripped_all).unwrap();
+    }
+
+    #[test]
+    fn strip_and_validate_round_trip() {
+        let mut module = Module::new();
+        let mut types = TypeSection::new();
+        types
+            .ty()
+            .function([ValType::I32, ValType::I32], [ValType::I32]);
+        module.section(&types);
+        let mut functions = FunctionSection::new();
+        functions.function(0);
+        module.section(&functions);
+        let mut exports = ExportSection::new();
+        exports.export("add", ExportKind::Func, 0);
+        module.section(&exports);
+        let mut code = CodeSection::new();
+        let mut add = Function::new([]);
+        add.instruction(&Instruction::LocalGet(0))
+            .instruction(&Instruction::LocalGet(1))
+            .instruction(&Instruction::I32Add)
+            .instruction(&Instruction::End);
+        code.function(&add);
+        module.section(&code);
+        module.section(&debug_section(".debug_line"));
+        module.section(&debug_section("name"));
+
+        let mut nested_component = Component::new();
+        nested_component.section(&ModuleSection(&module));
+        nested_component.section(&debug_section("sourceMappingURL"));
+
+        let mut component = Component::new();
+        component.section(&ModuleSection(&module));
+        let mut instances = InstanceSection::new();
+        instances.instantiate(0, Vec::<(&str, ModuleArg)>::new());
+        component.section(&instances);
+        component.section(&NestedComponentSection(&nested_component));
+        component.section(&CustomSection {
+            name: Cow::Borrowed("producers"),
+            data: Cow::Borrowed(b"producers"),
+        });
+        component.section(&debug_section("external_debug_info"));
+        let wasm = component.finish();
+        validate(&wasm).unwrap();
+
+        // Not stripping anything reproduces the original binary
+        check!(strip_custom_sections(&wasm, |_| false).unwrap() == wasm);
+
+        let stripped = strip_custom_sections(&wasm, is_debug_custom_section).unwrap();
+        validate(&stripped).unwrap();
+        check!(custom_section_names(&stripped) == vec!["producers"]);
+        check!(non_custom_sections(&stripped) == non_custom_sections(&wasm));
+
+        // Stripping is idempotent
+        check!(strip_custom_sections(&stripped, is_debug_custom_section).unwrap() == stripped);
+    }
+}